@@ -0,0 +1,11 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use valorant_api::prelude::EpisodeAndAct;
+
+fuzz_target!(|data: &[u8]| {
+    let _ = serde_json::from_slice::<EpisodeAndAct>(data);
+    if let Ok(value) = std::str::from_utf8(data) {
+        let _ = serde_json::from_value::<EpisodeAndAct>(serde_json::Value::from(value));
+    }
+});
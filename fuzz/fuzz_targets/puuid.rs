@@ -0,0 +1,10 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use valorant_api::prelude::Puuid;
+
+fuzz_target!(|data: &str| {
+    if let Ok(puuid) = data.parse::<Puuid>() {
+        assert_eq!(puuid.to_string().parse::<Puuid>(), Ok(puuid));
+    }
+});
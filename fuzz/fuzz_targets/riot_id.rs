@@ -0,0 +1,10 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use valorant_api::prelude::RiotId;
+
+fuzz_target!(|data: &str| {
+    if let Ok(id) = data.parse::<RiotId>() {
+        assert_eq!(id.to_string().parse::<RiotId>(), Ok(id));
+    }
+});
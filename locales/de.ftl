@@ -0,0 +1,18 @@
+currency-valorant-points = Valorant-Punkte
+currency-radianite-points = Radianit-Punkte
+currency-kingdom-credits = Kingdom-Credits
+placement-progress = { $played } von { $total } Platzierungsspielen gespielt
+placement-progress-provisional = { $played } von { $total } Platzierungsspielen gespielt, vorläufig { $rank }
+games-to-reach = { $median } Spiele (90 % innerhalb von { $unlucky }, { $share } % erreichen es)
+games-not-reached = Nicht erreicht
+time-just-now = gerade eben
+time-minute-ago = vor 1 Minute
+time-minutes-ago = vor { $count } Minuten
+time-hour-ago = vor 1 Stunde
+time-hours-ago = vor { $count } Stunden
+time-day-ago = vor 1 Tag
+time-days-ago = vor { $count } Tagen
+time-month-ago = vor 1 Monat
+time-months-ago = vor { $count } Monaten
+time-year-ago = vor 1 Jahr
+time-years-ago = vor { $count } Jahren
@@ -0,0 +1,19 @@
+# English messages, also used for anything another catalog leaves out.
+currency-valorant-points = Valorant Points
+currency-radianite-points = Radianite Points
+currency-kingdom-credits = Kingdom Credits
+placement-progress = { $played } of { $total } placement games played
+placement-progress-provisional = { $played } of { $total } placement games played, provisionally { $rank }
+games-to-reach = { $median } games (90% within { $unlucky }, { $share }% reach it)
+games-not-reached = Not reached
+time-just-now = just now
+time-minute-ago = 1 minute ago
+time-minutes-ago = { $count } minutes ago
+time-hour-ago = 1 hour ago
+time-hours-ago = { $count } hours ago
+time-day-ago = 1 day ago
+time-days-ago = { $count } days ago
+time-month-ago = 1 month ago
+time-months-ago = { $count } months ago
+time-year-ago = 1 year ago
+time-years-ago = { $count } years ago
@@ -0,0 +1,18 @@
+currency-valorant-points = Puntos Valorant
+currency-radianite-points = Puntos radianita
+currency-kingdom-credits = Créditos del Reino
+placement-progress = { $played } de { $total } partidas de clasificación jugadas
+placement-progress-provisional = { $played } de { $total } partidas de clasificación jugadas, provisionalmente { $rank }
+games-to-reach = { $median } partidas (90 % en { $unlucky } o menos, el { $share } % lo logra)
+games-not-reached = No alcanzado
+time-just-now = justo ahora
+time-minute-ago = hace 1 minuto
+time-minutes-ago = hace { $count } minutos
+time-hour-ago = hace 1 hora
+time-hours-ago = hace { $count } horas
+time-day-ago = hace 1 día
+time-days-ago = hace { $count } días
+time-month-ago = hace 1 mes
+time-months-ago = hace { $count } meses
+time-year-ago = hace 1 año
+time-years-ago = hace { $count } años
@@ -0,0 +1,18 @@
+currency-valorant-points = Valorant Points
+currency-radianite-points = Points de Radianite
+currency-kingdom-credits = Crédits du Royaume
+placement-progress = { $played } parties de placement sur { $total } jouées
+placement-progress-provisional = { $played } parties de placement sur { $total } jouées, provisoirement { $rank }
+games-to-reach = { $median } parties (90 % en { $unlucky } ou moins, { $share } % y parviennent)
+games-not-reached = Non atteint
+time-just-now = à l'instant
+time-minute-ago = il y a 1 minute
+time-minutes-ago = il y a { $count } minutes
+time-hour-ago = il y a 1 heure
+time-hours-ago = il y a { $count } heures
+time-day-ago = il y a 1 jour
+time-days-ago = il y a { $count } jours
+time-month-ago = il y a 1 mois
+time-months-ago = il y a { $count } mois
+time-year-ago = il y a 1 an
+time-years-ago = il y a { $count } ans
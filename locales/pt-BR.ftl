@@ -0,0 +1,18 @@
+currency-valorant-points = Valorant Points
+currency-radianite-points = Pontos Radianita
+currency-kingdom-credits = Créditos do Reino
+placement-progress = { $played } de { $total } partidas de classificação jogadas
+placement-progress-provisional = { $played } de { $total } partidas de classificação jogadas, provisoriamente { $rank }
+games-to-reach = { $median } partidas (90% em até { $unlucky }, { $share }% alcançam)
+games-not-reached = Não alcançado
+time-just-now = agora mesmo
+time-minute-ago = há 1 minuto
+time-minutes-ago = há { $count } minutos
+time-hour-ago = há 1 hora
+time-hours-ago = há { $count } horas
+time-day-ago = há 1 dia
+time-days-ago = há { $count } dias
+time-month-ago = há 1 mês
+time-months-ago = há { $count } meses
+time-year-ago = há 1 ano
+time-years-ago = há { $count } anos
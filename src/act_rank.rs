@@ -0,0 +1,117 @@
+//! The act rank triangle shown on a player's career page.
+//!
+//! The triangle is built from the ranks of a player's wins in an act. Its nine slots are laid out
+//! in rows of 1, 3 and 5, and the highest ranked wins fill them from the top.
+
+/// The rows of the triangle, top first.
+pub const ROWS: usize = 3;
+/// The number of wins the triangle shows.
+pub const SLOTS: usize = ROWS * ROWS;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ActRankTriangle {
+    // Row `i` holds `2 * i + 1` slots, alternating between upward and downward triangles
+    rows: Vec<Vec<Option<u32>>>,
+}
+
+impl ActRankTriangle {
+    /// Builds the triangle from the tiers of an act's wins, in any order.
+    pub fn from_wins(tiers: impl IntoIterator<Item = u32>) -> Self {
+        let mut tiers = tiers.into_iter().collect::<Vec<_>>();
+        tiers.sort_unstable_by(|a, b| b.cmp(a));
+        let mut best = tiers.into_iter().take(SLOTS);
+        let rows = (0..ROWS)
+            .map(|row| (0..2 * row + 1).map(|_| best.next()).collect())
+            .collect();
+        ActRankTriangle { rows }
+    }
+
+    /// The tier of each slot, `None` for slots without a win yet.
+    pub fn rows(&self) -> &[Vec<Option<u32>>] {
+        &self.rows
+    }
+
+    /// The highest tier in the triangle, the one shown as the act rank.
+    pub fn act_rank(&self) -> Option<u32> {
+        self.rows[0][0]
+    }
+
+    /// Renders the triangle as a standalone SVG image, each slot coloured by its rank.
+    #[cfg(feature = "svg")]
+    pub fn to_svg(&self) -> String {
+        const WIDTH: f32 = 40.0;
+        const HEIGHT: f32 = 35.0;
+        let mut svg = format!(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{}" height="{}">"#,
+            WIDTH * ROWS as f32,
+            HEIGHT * ROWS as f32
+        );
+        for (row, slots) in self.rows.iter().enumerate() {
+            let left = (ROWS - 1 - row) as f32 * WIDTH / 2.0;
+            let top = row as f32 * HEIGHT;
+            let bottom = top + HEIGHT;
+            for (slot, tier) in slots.iter().enumerate() {
+                let x = left + (slot / 2) as f32 * WIDTH;
+                let points = if slot % 2 == 0 {
+                    [(x, bottom), (x + WIDTH / 2.0, top), (x + WIDTH, bottom)]
+                } else {
+                    [
+                        (x + WIDTH / 2.0, top),
+                        (x + WIDTH * 1.5, top),
+                        (x + WIDTH, bottom),
+                    ]
+                };
+                let points = points.map(|(x, y)| format!("{x},{y}")).join(" ");
+                svg.push_str(&format!(
+                    r##"<polygon points="{points}" fill="{}" stroke="#111"/>"##,
+                    tier.map_or("#2a2a2a", colour)
+                ));
+            }
+        }
+        svg.push_str("</svg>");
+        svg
+    }
+}
+
+// One colour per rank, from Iron (tiers 3 to 5) up to Radiant (tier 27)
+#[cfg(any(feature = "svg", feature = "image"))]
+pub(crate) fn colour(tier: u32) -> &'static str {
+    const COLOURS: [&str; 9] = [
+        "#5a5a5a", "#a0714f", "#c0c7c9", "#e0b548", "#3aa0a8", "#b489f0", "#2fbf71", "#d8405e",
+        "#fff3a6",
+    ];
+    COLOURS[(tier.saturating_sub(3) as usize / 3).min(COLOURS.len() - 1)]
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn best_wins_fill_from_the_top() {
+        let triangle = ActRankTriangle::from_wins([14, 16, 15, 16, 17, 13, 12, 15, 14, 16, 18]);
+        assert_eq!(triangle.act_rank(), Some(18));
+        assert_eq!(
+            triangle.rows(),
+            [
+                vec![Some(18)],
+                vec![Some(17), Some(16), Some(16)],
+                vec![Some(16), Some(15), Some(15), Some(14), Some(14)],
+            ]
+        );
+
+        let sparse = ActRankTriangle::from_wins([9, 11]);
+        assert_eq!(sparse.rows()[1], [Some(9), None, None]);
+        assert_eq!(ActRankTriangle::from_wins([]).act_rank(), None);
+    }
+
+    #[cfg(feature = "svg")]
+    #[test]
+    fn renders_svg() {
+        let svg = ActRankTriangle::from_wins([27, 16]).to_svg();
+        assert_eq!(svg.matches("<polygon").count(), SLOTS);
+        assert!(svg.contains(r##"fill="#fff3a6""##));
+        assert!(svg.contains(r##"fill="#3aa0a8""##));
+        assert!(svg.ends_with("</svg>"));
+    }
+}
@@ -0,0 +1,166 @@
+//! Records the Riot IDs seen for each PUUID, so moderators can recognize players who renamed
+//! themselves.
+//!
+//! A PUUID never changes, while `name#tag` can be changed every few weeks. Recording every
+//! account lookup and fetched match builds up each player's aliases over time.
+
+use crate::account_data::AccountData;
+use crate::ids::{Puuid, RiotId};
+use crate::match_data::MatchData;
+use crate::storage::{Storage, StorageExt};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::io;
+
+const ALIASES: &str = "aliases";
+
+/// A Riot ID one player went by.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Alias {
+    riot_id: RiotId,
+    first_seen: DateTime<Utc>,
+    last_seen: DateTime<Utc>,
+}
+
+impl Alias {
+    pub fn riot_id(&self) -> &RiotId {
+        &self.riot_id
+    }
+
+    pub fn first_seen(&self) -> DateTime<Utc> {
+        self.first_seen
+    }
+
+    pub fn last_seen(&self) -> DateTime<Utc> {
+        self.last_seen
+    }
+}
+
+pub struct AliasHistory<S> {
+    storage: S,
+}
+
+impl<S: Storage> AliasHistory<S> {
+    pub fn new(storage: S) -> Self {
+        AliasHistory { storage }
+    }
+
+    /// Records that the player went by `riot_id` at `observed_at`. IDs differing only in case
+    /// are the same alias, since the API doesn't always report the casing the player chose.
+    pub fn record(
+        &self,
+        puuid: &Puuid,
+        riot_id: &RiotId,
+        observed_at: DateTime<Utc>,
+    ) -> io::Result<()> {
+        let mut aliases = self.known_aliases(puuid)?;
+        let typed = riot_id.to_string();
+        match aliases
+            .iter_mut()
+            .find(|alias| alias.riot_id.matches(&typed))
+        {
+            Some(alias) => {
+                alias.first_seen = alias.first_seen.min(observed_at);
+                alias.last_seen = alias.last_seen.max(observed_at);
+            }
+            None => aliases.push(Alias {
+                riot_id: riot_id.clone(),
+                first_seen: observed_at,
+                last_seen: observed_at,
+            }),
+        }
+        aliases.sort_by_key(|alias| alias.first_seen);
+        self.storage.put_json(ALIASES, puuid.as_str(), &aliases)
+    }
+
+    pub fn record_account(
+        &self,
+        account: &AccountData,
+        observed_at: DateTime<Utc>,
+    ) -> io::Result<()> {
+        match (
+            account.puuid().parse(),
+            RiotId::new(account.name(), account.tag()),
+        ) {
+            (Ok(puuid), Ok(riot_id)) => self.record(&puuid, &riot_id, observed_at),
+            _ => Ok(()),
+        }
+    }
+
+    /// Records the Riot ID of every player in the match as of its start. Players whose IDs
+    /// don't parse, like deleted accounts, are skipped.
+    pub fn record_match(&self, data: &MatchData) -> io::Result<()> {
+        for player in data.players() {
+            if let (Ok(puuid), Ok(riot_id)) = (
+                player.puuid().parse(),
+                RiotId::new(player.name(), player.tag()),
+            ) {
+                self.record(&puuid, &riot_id, data.started_at())?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Every alias the player was seen with, by when it was first seen.
+    pub fn known_aliases(&self, puuid: &Puuid) -> io::Result<Vec<Alias>> {
+        Ok(self
+            .storage
+            .get_json(ALIASES, puuid.as_str())?
+            .unwrap_or_default())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::fixtures::{decode, MATCH_HISTORY};
+    use crate::storage::MemoryStorage;
+    use chrono::Duration;
+
+    #[test]
+    fn renames_are_tracked_per_player() {
+        let history = AliasHistory::new(MemoryStorage::new());
+        let puuid: Puuid = "b44adaae-ab83-5001-a296-89ea0de0bce3".parse().unwrap();
+        let start: DateTime<Utc> = "2023-02-01T00:00:00Z".parse().unwrap();
+        let id = |value: &str| value.parse::<RiotId>().unwrap();
+
+        history
+            .record(&puuid, &id("Anonymous#0000"), start)
+            .unwrap();
+        history
+            .record(&puuid, &id("NitroSniper#NERD"), start + Duration::days(30))
+            .unwrap();
+        history
+            .record(&puuid, &id("anonymous#0000"), start + Duration::days(3))
+            .unwrap();
+        // A late observation from before the first one moves the alias back in time
+        history
+            .record(&puuid, &id("Anonymous#0000"), start - Duration::days(1))
+            .unwrap();
+
+        let aliases = history.known_aliases(&puuid).unwrap();
+        assert_eq!(aliases.len(), 2);
+        assert_eq!(aliases[0].riot_id().to_string(), "Anonymous#0000");
+        assert_eq!(aliases[0].first_seen(), start - Duration::days(1));
+        assert_eq!(aliases[0].last_seen(), start + Duration::days(3));
+        assert_eq!(aliases[1].riot_id().name(), "NitroSniper");
+
+        let stranger: Puuid = "00000000-0000-0000-0000-000000000000".parse().unwrap();
+        assert!(history.known_aliases(&stranger).unwrap().is_empty());
+    }
+
+    #[test]
+    fn matches_record_every_player() {
+        let data: Vec<MatchData> = decode(MATCH_HISTORY);
+        let history = AliasHistory::new(MemoryStorage::new());
+        history.record_match(&data[0]).unwrap();
+
+        let player = &data[0].players()[0];
+        let aliases = history
+            .known_aliases(&player.puuid().parse().unwrap())
+            .unwrap();
+        assert_eq!(aliases.len(), 1);
+        assert_eq!(aliases[0].riot_id().name(), player.name());
+        assert_eq!(aliases[0].first_seen(), data[0].started_at());
+    }
+}
@@ -0,0 +1,349 @@
+//! Estimates built from a player's rank and results.
+
+use crate::domain::{Match, Participant};
+use std::fmt;
+
+// Tier IDs as reported by the API: 3 is Iron 1 and 27 is Radiant
+const LOWEST_TIER: u32 = 3;
+const HIGHEST_TIER: u32 = 27;
+const RR_PER_TIER: u32 = 100;
+
+/// A Monte Carlo simulation of ranked games, starting from a player's current rank.
+///
+/// Every simulated game is won with the same probability and moves RR by the average gain or
+/// loss. Reaching 100 RR promotes, and losing below 0 RR demotes.
+#[derive(Debug, Clone)]
+pub struct RankSimulation {
+    tier: u32,
+    rr: u32,
+    average_gain: u32,
+    average_loss: u32,
+    win_probability: f64,
+    runs: u32,
+    max_games: u32,
+    seed: u64,
+}
+
+impl RankSimulation {
+    pub fn new(tier: u32, rr: u32) -> Self {
+        RankSimulation {
+            tier: tier.clamp(LOWEST_TIER, HIGHEST_TIER),
+            rr: rr.min(RR_PER_TIER - 1),
+            average_gain: 20,
+            average_loss: 18,
+            win_probability: 0.5,
+            runs: 10_000,
+            max_games: 1_000,
+            seed: 0,
+        }
+    }
+
+    /// 20 RR by default.
+    pub fn average_gain(mut self, rr: u32) -> Self {
+        self.average_gain = rr;
+        self
+    }
+
+    /// 18 RR by default.
+    pub fn average_loss(mut self, rr: u32) -> Self {
+        self.average_loss = rr;
+        self
+    }
+
+    /// 0.5 by default, clamped to `0.0..=1.0`.
+    pub fn win_probability(mut self, probability: f64) -> Self {
+        self.win_probability = probability.clamp(0.0, 1.0);
+        self
+    }
+
+    /// How many seasons to simulate, 10 000 by default.
+    pub fn runs(mut self, runs: u32) -> Self {
+        self.runs = runs.max(1);
+        self
+    }
+
+    /// Runs that haven't reached the target after this many games give up, 1 000 by default.
+    pub fn max_games(mut self, games: u32) -> Self {
+        self.max_games = games;
+        self
+    }
+
+    /// The same seed always gives the same distribution.
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// The number of games each run needed to reach `target_tier`.
+    pub fn games_to_reach(&self, target_tier: u32) -> GamesDistribution {
+        let target_tier = target_tier.clamp(LOWEST_TIER, HIGHEST_TIER);
+        let mut rng = SplitMix64(self.seed);
+        let mut games = Vec::new();
+        let mut gave_up = 0;
+        for _ in 0..self.runs {
+            match self.run(target_tier, &mut rng) {
+                Some(needed) => games.push(needed),
+                None => gave_up += 1,
+            }
+        }
+        games.sort_unstable();
+        GamesDistribution { games, gave_up }
+    }
+
+    fn run(&self, target_tier: u32, rng: &mut SplitMix64) -> Option<u32> {
+        let (mut tier, mut rr) = (self.tier, self.rr);
+        for played in 0..=self.max_games {
+            if tier >= target_tier {
+                return Some(played);
+            }
+            if rng.next_f64() < self.win_probability {
+                rr += self.average_gain;
+                while rr >= RR_PER_TIER && tier < HIGHEST_TIER {
+                    tier += 1;
+                    rr -= RR_PER_TIER;
+                }
+            } else if rr >= self.average_loss {
+                rr -= self.average_loss;
+            } else if tier > LOWEST_TIER {
+                tier -= 1;
+                rr = (rr + RR_PER_TIER).saturating_sub(self.average_loss);
+            } else {
+                rr = 0;
+            }
+        }
+        None
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct GamesDistribution {
+    // Sorted, one entry per run that reached the target
+    games: Vec<u32>,
+    gave_up: u32,
+}
+
+impl GamesDistribution {
+    /// The share of runs that reached the target within the game limit.
+    pub fn reached_share(&self) -> f64 {
+        let runs = self.games.len() as u32 + self.gave_up;
+        f64::from(self.games.len() as u32) / f64::from(runs)
+    }
+
+    /// The games needed by the given share of the runs that reached the target, e.g. `0.9` for
+    /// the 90th percentile. `None` when no run did.
+    pub fn percentile(&self, share: f64) -> Option<u32> {
+        let last = self.games.len().checked_sub(1)?;
+        let index = (share.clamp(0.0, 1.0) * last as f64).round() as usize;
+        Some(self.games[index])
+    }
+
+    pub fn median(&self) -> Option<u32> {
+        self.percentile(0.5)
+    }
+
+    pub fn mean(&self) -> Option<f64> {
+        if self.games.is_empty() {
+            return None;
+        }
+        let total: u64 = self.games.iter().map(|&games| u64::from(games)).sum();
+        Some(total as f64 / self.games.len() as f64)
+    }
+}
+
+impl fmt::Display for GamesDistribution {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.median(), self.percentile(0.9)) {
+            (Some(median), Some(unlucky)) => write!(
+                f,
+                "{median} games (90% within {unlucky}, {:.0}% reach it)",
+                self.reached_share() * 100.0
+            ),
+            _ => f.write_str("Not reached"),
+        }
+    }
+}
+
+/// One side of a lobby, as seen by a [`WinProbabilityModel`].
+#[derive(Debug, Clone)]
+pub struct TeamSide<'a> {
+    pub team: &'a str,
+    pub players: Vec<&'a Participant>,
+    /// The average tier of the ranked players, `None` when nobody is ranked.
+    pub average_tier: Option<f64>,
+}
+
+impl<'a> TeamSide<'a> {
+    fn new(team: &'a str, players: Vec<&'a Participant>) -> Self {
+        let tiers = players
+            .iter()
+            .filter_map(|player| Some(f64::from(player.rank.as_ref()?.tier)))
+            .collect::<Vec<_>>();
+        let average_tier =
+            (!tiers.is_empty()).then(|| tiers.iter().sum::<f64>() / tiers.len() as f64);
+        TeamSide {
+            team,
+            players,
+            average_tier,
+        }
+    }
+}
+
+/// Predicts the chance that `team` beats `opponents`, from `0.0` to `1.0`.
+///
+/// Implement this to plug a custom model into [`scout_lobby`].
+pub trait WinProbabilityModel: Send + Sync {
+    fn win_probability(&self, team: &TeamSide<'_>, opponents: &TeamSide<'_>) -> f64;
+}
+
+/// The default model: a logistic curve over the difference in average tier, where a one tier
+/// edge is worth about 56%.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RankDifferenceModel;
+
+impl WinProbabilityModel for RankDifferenceModel {
+    fn win_probability(&self, team: &TeamSide<'_>, opponents: &TeamSide<'_>) -> f64 {
+        match (team.average_tier, opponents.average_tier) {
+            (Some(ours), Some(theirs)) => 1.0 / (1.0 + (-(ours - theirs) / 4.0).exp()),
+            _ => 0.5,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct LobbyReport {
+    pub teams: Vec<TeamReport>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TeamReport {
+    pub team: String,
+    pub players: usize,
+    pub average_tier: Option<f64>,
+    /// Only predicted in lobbies of exactly two teams.
+    pub win_probability: Option<f64>,
+}
+
+/// Groups a lobby by team and predicts each team's chance to win with `model`.
+pub fn scout_lobby(game: &Match, model: &dyn WinProbabilityModel) -> LobbyReport {
+    let mut teams: Vec<(&str, Vec<&Participant>)> = Vec::new();
+    for participant in &game.participants {
+        match teams.iter_mut().find(|(team, _)| *team == participant.team) {
+            Some((_, players)) => players.push(participant),
+            None => teams.push((&participant.team, vec![participant])),
+        }
+    }
+    let sides = teams
+        .into_iter()
+        .map(|(team, players)| TeamSide::new(team, players))
+        .collect::<Vec<_>>();
+
+    let teams = sides
+        .iter()
+        .enumerate()
+        .map(|(index, side)| {
+            let win_probability = match sides.as_slice() {
+                [first, second] => {
+                    let opponents = if index == 0 { second } else { first };
+                    Some(model.win_probability(side, opponents).clamp(0.0, 1.0))
+                }
+                _ => None,
+            };
+            TeamReport {
+                team: side.team.to_string(),
+                players: side.players.len(),
+                average_tier: side.average_tier,
+                win_probability,
+            }
+        })
+        .collect();
+    LobbyReport { teams }
+}
+
+// SplitMix64, as in the fakes, so simulations don't pull in a random number crate
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    // Uniform in 0..1 from the top 53 bits
+    fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn simulates_games_to_a_target() {
+        // Platinum 2 at 50 RR, always winning 25: two wins reach Platinum 3
+        let certain = RankSimulation::new(16, 50)
+            .average_gain(25)
+            .win_probability(1.0)
+            .runs(10);
+        let games = certain.games_to_reach(17);
+        assert_eq!(games.median(), Some(2));
+        assert_eq!(games.mean(), Some(2.0));
+        assert_eq!(certain.games_to_reach(16).median(), Some(0));
+
+        let never = RankSimulation::new(16, 50)
+            .win_probability(0.0)
+            .max_games(50);
+        let games = never.games_to_reach(17);
+        assert_eq!(games.reached_share(), 0.0);
+        assert_eq!(games.median(), None);
+        assert_eq!(games.to_string(), "Not reached");
+
+        let coin_flip = RankSimulation::new(16, 50).seed(7).runs(2_000);
+        let games = coin_flip.games_to_reach(19);
+        assert_eq!(games, coin_flip.games_to_reach(19));
+        assert!(games.median().unwrap() <= games.percentile(0.9).unwrap());
+        // Gaining slightly more than losing climbs, slowly
+        assert!(games.reached_share() > 0.9);
+        assert!(games.mean().unwrap() > 20.0);
+    }
+
+    #[test]
+    fn scouts_lobbies_with_a_custom_model() {
+        let matches: Vec<crate::match_data::MatchData> =
+            crate::fixtures::decode(crate::fixtures::MATCH_HISTORY);
+        let game = Match::from(&matches[0]);
+
+        let report = scout_lobby(&game, &RankDifferenceModel);
+        let [red, blue] = &report.teams[..] else {
+            panic!("expected two teams");
+        };
+        assert_eq!((red.team.as_str(), red.players), ("Red", 5));
+        assert_eq!(blue.average_tier, Some(16.4));
+        // Blue is ranked higher on average
+        let blue_chance = blue.win_probability.unwrap();
+        assert!(blue_chance > 0.5);
+        assert!((red.win_probability.unwrap() + blue_chance - 1.0).abs() < 1e-9);
+
+        struct MostKills;
+        impl WinProbabilityModel for MostKills {
+            fn win_probability(&self, team: &TeamSide<'_>, opponents: &TeamSide<'_>) -> f64 {
+                let kills = |side: &TeamSide<'_>| side.players.iter().map(|p| p.kills).sum::<u32>();
+                if kills(team) > kills(opponents) {
+                    1.0
+                } else {
+                    0.0
+                }
+            }
+        }
+        let report = scout_lobby(&game, &MostKills);
+        let chances = report
+            .teams
+            .iter()
+            .map(|team| team.win_probability.unwrap())
+            .sum::<f64>();
+        assert_eq!(chances, 1.0);
+    }
+}
@@ -0,0 +1,355 @@
+//! Which version of each endpoint family the client requests.
+//!
+//! Every family decodes into one model whatever version serves it, e.g. [`MMRData`] for the MMR
+//! endpoints. Pinning a family to another version changes the route, and the client converts
+//! the payload into the family's model, so code written against `v2/mmr` keeps working while
+//! `v3/mmr` is being tried out.
+//!
+//! [`MMRData`]: crate::mmr_data::MMRData
+
+#[cfg(feature = "client")]
+use crate::account_data::{AccountData, AccountDataV2};
+#[cfg(feature = "client")]
+use crate::mmr_data::{MMRData, MMRDataV3, SeasonFilter};
+#[cfg(feature = "client")]
+use crate::mmr_history::{MMRHistoryEntry, MMRHistoryEntryV2};
+#[cfg(feature = "client")]
+use crate::routing::Platform;
+#[cfg(feature = "client")]
+use crate::{AccountRegion, ValorantApiType};
+#[cfg(feature = "client")]
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum EndpointFamily {
+    Account,
+    Mmr,
+    MmrHistory,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[non_exhaustive]
+pub enum ApiVersion {
+    V1,
+    V2,
+    V3,
+}
+
+impl fmt::Display for ApiVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            ApiVersion::V1 => "v1",
+            ApiVersion::V2 => "v2",
+            ApiVersion::V3 => "v3",
+        })
+    }
+}
+
+impl EndpointFamily {
+    /// The version the family's model is written against.
+    pub fn default_version(self) -> ApiVersion {
+        self.supported_versions()[0]
+    }
+
+    /// The versions the client can convert into the family's model, the default first.
+    pub fn supported_versions(self) -> &'static [ApiVersion] {
+        match self {
+            EndpointFamily::Account => &[ApiVersion::V1, ApiVersion::V2],
+            EndpointFamily::Mmr => &[ApiVersion::V2, ApiVersion::V3],
+            EndpointFamily::MmrHistory => &[ApiVersion::V1, ApiVersion::V2],
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnsupportedVersion {
+    pub family: EndpointFamily,
+    pub version: ApiVersion,
+}
+
+impl fmt::Display for UnsupportedVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:?} endpoints can't be pinned to {}",
+            self.family, self.version
+        )
+    }
+}
+
+impl std::error::Error for UnsupportedVersion {}
+
+/// The version of every endpoint family, each at its default unless pinned.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ApiVersions {
+    pinned: HashMap<EndpointFamily, ApiVersion>,
+}
+
+impl ApiVersions {
+    pub fn new() -> Self {
+        ApiVersions::default()
+    }
+
+    pub fn pin(
+        mut self,
+        family: EndpointFamily,
+        version: ApiVersion,
+    ) -> Result<Self, UnsupportedVersion> {
+        if !family.supported_versions().contains(&version) {
+            return Err(UnsupportedVersion { family, version });
+        }
+        self.pinned.insert(family, version);
+        Ok(self)
+    }
+
+    pub fn get(&self, family: EndpointFamily) -> ApiVersion {
+        self.pinned
+            .get(&family)
+            .copied()
+            .unwrap_or_else(|| family.default_version())
+    }
+
+    // The route of a request under these versions, and the version its payload comes in when
+    // that isn't the model's own
+    #[cfg(feature = "client")]
+    pub(crate) fn route(&self, api_type: &ValorantApiType<'_>) -> (String, Option<ApiVersion>) {
+        let pinned = |family: EndpointFamily| {
+            let version = self.get(family);
+            (version != family.default_version()).then_some(version)
+        };
+        match api_type {
+            ValorantApiType::AccountData { name, tag } => match pinned(EndpointFamily::Account) {
+                Some(version) => (format!("{version}/account/{name}/{tag}"), Some(version)),
+                None => (api_type.to_url(), None),
+            },
+            ValorantApiType::AccountDataByPuuid { puuid } => {
+                match pinned(EndpointFamily::Account) {
+                    Some(version) => (format!("{version}/by-puuid/account/{puuid}"), Some(version)),
+                    None => (api_type.to_url(), None),
+                }
+            }
+            // `v3/mmr` can't be filtered by act, so filtered requests stay on `v2`
+            ValorantApiType::MMRData {
+                region,
+                name,
+                tag,
+                filter: None | Some(SeasonFilter::Current),
+            } => match pinned(EndpointFamily::Mmr) {
+                Some(version) => (
+                    platform_route(version, "mmr", *region, name, tag),
+                    Some(version),
+                ),
+                None => (api_type.to_url(), None),
+            },
+            ValorantApiType::MMRHistory { region, name, tag } => {
+                match pinned(EndpointFamily::MmrHistory) {
+                    Some(version) => (
+                        platform_route(version, "mmr-history", *region, name, tag),
+                        Some(version),
+                    ),
+                    None => (api_type.to_url(), None),
+                }
+            }
+            _ => (api_type.to_url(), None),
+        }
+    }
+}
+
+// Newer versions of the player endpoints are split by platform; PC is the one with ranks
+#[cfg(feature = "client")]
+fn platform_route(
+    version: ApiVersion,
+    endpoint: &str,
+    region: AccountRegion,
+    name: &str,
+    tag: &str,
+) -> String {
+    format!(
+        "{version}/{endpoint}/{}/{}/{name}/{tag}",
+        region.segment(),
+        Platform::Pc.segment()
+    )
+}
+
+#[cfg(feature = "client")]
+#[derive(Deserialize)]
+struct HistoryV2 {
+    history: Vec<MMRHistoryEntryV2>,
+}
+
+/// Rewrites the `data` of a response fetched from a pinned version into the shape of the
+/// default version. Bodies that don't decode are returned as they are, so the usual decoding
+/// reports the error.
+#[cfg(feature = "client")]
+pub(crate) fn normalize(api_type: &ValorantApiType<'_>, body: Vec<u8>) -> Vec<u8> {
+    convert(api_type, &body).unwrap_or(body)
+}
+
+#[cfg(feature = "client")]
+fn convert(api_type: &ValorantApiType<'_>, body: &[u8]) -> Option<Vec<u8>> {
+    let mut envelope: serde_json::Value = serde_json::from_slice(body).ok()?;
+    let data = envelope.get_mut("data")?;
+    let converted = match api_type {
+        ValorantApiType::AccountData { .. } | ValorantApiType::AccountDataByPuuid { .. } => {
+            let v2: AccountDataV2 = serde_json::from_value(data.take()).ok()?;
+            serde_json::to_value(AccountData::from(v2))
+        }
+        ValorantApiType::MMRData { .. } => {
+            let v3: MMRDataV3 = serde_json::from_value(data.take()).ok()?;
+            serde_json::to_value(MMRData::from(v3))
+        }
+        ValorantApiType::MMRHistory { .. } => {
+            let v2: HistoryV2 = serde_json::from_value(data.take()).ok()?;
+            let history: Vec<_> = v2.history.into_iter().map(MMRHistoryEntry::from).collect();
+            serde_json::to_value(history)
+        }
+        _ => return None,
+    };
+    *data = converted.ok()?;
+    serde_json::to_vec(&envelope).ok()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    #[cfg(feature = "client")]
+    use crate::fixtures::ACCOUNT;
+    #[cfg(feature = "client")]
+    use crate::mmr_data::EpisodeAndAct;
+    #[cfg(feature = "client")]
+    use crate::mock_server::{MockResponse, MockServer};
+    #[cfg(feature = "client")]
+    use crate::tier::Tier;
+    #[cfg(feature = "client")]
+    use crate::ValorantClient;
+
+    #[test]
+    fn versions_default_per_family() {
+        let versions = ApiVersions::new();
+        assert_eq!(versions.get(EndpointFamily::Mmr), ApiVersion::V2);
+        assert_eq!(versions.get(EndpointFamily::Account), ApiVersion::V1);
+
+        let versions = versions.pin(EndpointFamily::Mmr, ApiVersion::V3).unwrap();
+        assert_eq!(versions.get(EndpointFamily::Mmr), ApiVersion::V3);
+        assert_eq!(
+            versions
+                .clone()
+                .pin(EndpointFamily::Account, ApiVersion::V3),
+            Err(UnsupportedVersion {
+                family: EndpointFamily::Account,
+                version: ApiVersion::V3,
+            })
+        );
+    }
+
+    #[cfg(feature = "client")]
+    #[test]
+    fn pinned_families_change_the_route() {
+        let versions = ApiVersions::new()
+            .pin(EndpointFamily::Mmr, ApiVersion::V3)
+            .unwrap();
+        let mmr = |filter| ValorantApiType::MMRData {
+            region: AccountRegion::EU,
+            name: "Anonymous",
+            tag: "0000",
+            filter,
+        };
+        assert_eq!(
+            versions.route(&mmr(None)),
+            (
+                "v3/mmr/eu/pc/Anonymous/0000".to_string(),
+                Some(ApiVersion::V3)
+            )
+        );
+        let act = Some(SeasonFilter::Act(EpisodeAndAct::from_numbers(6, 1)));
+        assert_eq!(versions.route(&mmr(act)).1, None);
+    }
+
+    #[cfg(feature = "client")]
+    #[tokio::test]
+    async fn accounts_are_looked_up_by_puuid() {
+        let puuid = "5a4c1f0e-7d2b-5e93-a1c4-3f6b8e2d9c10";
+        let server = MockServer::routes(vec![(
+            "/v1/by-puuid/account/5a4c1f0e-7d2b-5e93-a1c4-3f6b8e2d9c10",
+            MockResponse::json(200, ACCOUNT),
+        )])
+        .await;
+        let url = server.url();
+        let client = ValorantClient::new().change_api_endpoint(&url);
+        let data = client
+            .request::<AccountData>(ValorantApiType::AccountDataByPuuid { puuid })
+            .await
+            .unwrap();
+        assert_eq!(data.puuid(), puuid);
+
+        let versions = ApiVersions::new()
+            .pin(EndpointFamily::Account, ApiVersion::V2)
+            .unwrap();
+        assert_eq!(
+            versions.route(&ValorantApiType::AccountDataByPuuid { puuid }),
+            (format!("v2/by-puuid/account/{puuid}"), Some(ApiVersion::V2))
+        );
+    }
+
+    #[cfg(feature = "client")]
+    #[tokio::test]
+    async fn pinned_payloads_decode_into_the_family_model() {
+        let v3 = r#"{
+            "status": 200,
+            "data": {
+                "account": {"puuid": "81862fc9-634f-806f-abf4-a07c56600224", "name": "Anonymous", "tag": "0000"},
+                "peak": {"season": {"short": "e6a1"}, "tier": {"id": 18, "name": "Diamond 1"}},
+                "current": {
+                    "tier": {"id": 16, "name": "Platinum 2"},
+                    "rr": 47,
+                    "last_change": -11,
+                    "elo": 1347,
+                    "games_needed_for_rating": 0
+                }
+            }
+        }"#;
+        let server = MockServer::routes(vec![
+            ("/v3/mmr/eu/pc/Anonymous/0000", MockResponse::json(200, v3)),
+            (
+                "/v1/account/Anonymous/0000",
+                MockResponse::json(200, ACCOUNT),
+            ),
+        ])
+        .await;
+        let url = server.url();
+        let client = ValorantClient::builder()
+            .api_endpoint(&url)
+            .api_versions(
+                ApiVersions::new()
+                    .pin(EndpointFamily::Mmr, ApiVersion::V3)
+                    .unwrap(),
+            )
+            .build()
+            .unwrap();
+
+        let data = client
+            .request::<MMRData>(ValorantApiType::MMRData {
+                region: AccountRegion::EU,
+                name: "Anonymous",
+                tag: "0000",
+                filter: None,
+            })
+            .await
+            .unwrap();
+        assert_eq!(data.elo(), 1347);
+        assert_eq!(data.current_tier(), Tier::Platinum2);
+
+        // Families left at their default are untouched
+        let response = client
+            .request::<AccountData>(ValorantApiType::AccountData {
+                name: "Anonymous",
+                tag: "0000",
+            })
+            .await
+            .unwrap();
+        assert_eq!(response.name(), "Anonymous");
+    }
+}
@@ -0,0 +1,224 @@
+//! An append-only archive of raw API responses.
+//!
+//! Bodies are stored once per distinct payload. Fetching the same unchanged account data every
+//! hour only adds a small index record pointing at the body that is already stored.
+
+use crate::error::ValorantError;
+use crate::storage::{Storage, StorageExt};
+use crate::{ValorantApiType, ValorantClient};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::io;
+
+const BODIES: &str = "archive_bodies";
+const INDEX: &str = "archive_index";
+
+/// One archived response.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ArchiveRecord {
+    fetched_at: DateTime<Utc>,
+    status: u16,
+    checksum: String,
+    /// Index of the earlier record of the same key with an identical body.
+    same_as: Option<usize>,
+}
+
+impl ArchiveRecord {
+    pub fn fetched_at(&self) -> DateTime<Utc> {
+        self.fetched_at
+    }
+
+    pub fn status(&self) -> u16 {
+        self.status
+    }
+
+    pub fn checksum(&self) -> &str {
+        &self.checksum
+    }
+
+    pub fn same_as(&self) -> Option<usize> {
+        self.same_as
+    }
+}
+
+pub struct ResponseArchive<S> {
+    storage: S,
+}
+
+impl<S: Storage> ResponseArchive<S> {
+    pub fn new(storage: S) -> Self {
+        ResponseArchive { storage }
+    }
+
+    /// Archives a response body under `key`, usually the request's URL. The body is only stored
+    /// if no identical body has been archived before.
+    pub fn record(
+        &self,
+        key: &str,
+        status: u16,
+        body: &[u8],
+        fetched_at: DateTime<Utc>,
+    ) -> io::Result<ArchiveRecord> {
+        let checksum = self.store_body(body)?;
+        let mut records = self.records(key)?;
+        let same_as = records
+            .iter()
+            .rposition(|record| record.checksum == checksum)
+            .map(|index| records[index].same_as.unwrap_or(index));
+        let record = ArchiveRecord {
+            fetched_at,
+            status,
+            checksum,
+            same_as,
+        };
+        records.push(record.clone());
+        self.storage.put_json(INDEX, key, &records)?;
+        Ok(record)
+    }
+
+    /// Sends the request and archives whatever the API answered.
+    pub async fn fetch(
+        &self,
+        client: &ValorantClient<'_>,
+        api_type: ValorantApiType<'_>,
+    ) -> Result<ArchiveRecord, FetchError> {
+        let key = api_type.to_url();
+        let (status, body) = client.request_raw(api_type).await?;
+        Ok(self.record(&key, status, &body, Utc::now())?)
+    }
+
+    /// Every record archived under `key`, oldest first.
+    pub fn records(&self, key: &str) -> io::Result<Vec<ArchiveRecord>> {
+        Ok(self.storage.get_json(INDEX, key)?.unwrap_or_default())
+    }
+
+    pub fn body(&self, record: &ArchiveRecord) -> io::Result<Option<Vec<u8>>> {
+        self.storage.get(BODIES, &record.checksum)
+    }
+
+    // Stores the body under its checksum, suffixed in the unlikely case that a different body
+    // already has the same checksum
+    fn store_body(&self, body: &[u8]) -> io::Result<String> {
+        let hash = format!("{:016x}", fnv1a(body));
+        for attempt in 0.. {
+            let checksum = match attempt {
+                0 => hash.clone(),
+                _ => format!("{hash}-{attempt}"),
+            };
+            match self.storage.get(BODIES, &checksum)? {
+                Some(existing) if existing == body => return Ok(checksum),
+                Some(_) => continue,
+                None => {
+                    self.storage.put(BODIES, &checksum, body)?;
+                    return Ok(checksum);
+                }
+            }
+        }
+        unreachable!()
+    }
+}
+
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum FetchError {
+    Request(ValorantError),
+    Storage(io::Error),
+}
+
+impl std::fmt::Display for FetchError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FetchError::Request(error) => write!(f, "Request failed: {error}"),
+            FetchError::Storage(error) => write!(f, "Archiving the response failed: {error}"),
+        }
+    }
+}
+
+impl std::error::Error for FetchError {}
+
+impl From<ValorantError> for FetchError {
+    fn from(error: ValorantError) -> Self {
+        FetchError::Request(error)
+    }
+}
+
+impl From<io::Error> for FetchError {
+    fn from(error: io::Error) -> Self {
+        FetchError::Storage(error)
+    }
+}
+
+// FNV-1a is stable across Rust versions, unlike the std hasher, so checksums stay valid in
+// archives written by older builds
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::fixtures::ACCOUNT;
+    use crate::mock_server::{MockResponse, MockServer};
+    use crate::storage::MemoryStorage;
+    use std::sync::Arc;
+
+    #[test]
+    fn identical_bodies_are_stored_once() {
+        let storage = Arc::new(MemoryStorage::new());
+        let archive = ResponseArchive::new(storage.clone());
+        let at = |hour| format!("2023-02-14T{hour:02}:00:00Z").parse().unwrap();
+
+        let first = archive
+            .record("v1/account/a/b", 200, b"one", at(1))
+            .unwrap();
+        let second = archive
+            .record("v1/account/a/b", 200, b"one", at(2))
+            .unwrap();
+        let third = archive
+            .record("v1/account/a/b", 200, b"two", at(3))
+            .unwrap();
+        let fourth = archive
+            .record("v1/account/a/b", 200, b"one", at(4))
+            .unwrap();
+        archive
+            .record("v1/account/c/d", 200, b"one", at(5))
+            .unwrap();
+
+        assert_eq!(first.same_as(), None);
+        assert_eq!(second.same_as(), Some(0));
+        assert_eq!(third.same_as(), None);
+        assert_eq!(fourth.same_as(), Some(0));
+        assert_eq!(storage.keys(BODIES).unwrap().len(), 2);
+        assert_eq!(archive.records("v1/account/a/b").unwrap().len(), 4);
+        assert_eq!(archive.body(&fourth).unwrap().as_deref(), Some(&b"one"[..]));
+    }
+
+    #[tokio::test]
+    async fn fetch_archives_the_raw_body() {
+        let server = MockServer::start(vec![MockResponse::json(200, ACCOUNT)]).await;
+        let url = server.url();
+        let client = ValorantClient::new().change_api_endpoint(&url);
+        let archive = ResponseArchive::new(MemoryStorage::new());
+
+        for _ in 0..2 {
+            archive
+                .fetch(
+                    &client,
+                    ValorantApiType::AccountData {
+                        name: "NitroSniper",
+                        tag: "NERD",
+                    },
+                )
+                .await
+                .unwrap();
+        }
+        let records = archive.records("v1/account/NitroSniper/NERD").unwrap();
+        assert_eq!(records[1].same_as(), Some(0));
+        assert_eq!(
+            archive.body(&records[0]).unwrap().unwrap(),
+            ACCOUNT.as_bytes()
+        );
+    }
+}
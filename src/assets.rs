@@ -0,0 +1,328 @@
+//! Client for the static assets API at valorant-api.com, which serves game content that the
+//! HenrikDev API only references by ID.
+
+#[cfg(feature = "client")]
+use crate::account_data::ProfileBanner;
+#[cfg(feature = "client")]
+use crate::error::{self, ValorantError};
+use crate::ValorantAPIData;
+#[cfg(feature = "client")]
+use crate::{ApiError, ApiResponse};
+use chrono::{DateTime, Utc};
+#[cfg(feature = "client")]
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "client")]
+use std::collections::HashMap;
+#[cfg(feature = "client")]
+use std::sync::RwLock;
+
+#[cfg(feature = "client")]
+const DEFAULT_ASSET_END_POINT: &str = "https://valorant-api.com";
+
+#[cfg(feature = "client")]
+pub struct AssetClient<'a> {
+    api_end_point: &'a str,
+    http: reqwest::Client,
+    // Cards and titles rarely change once released, so they are cached until the game build
+    // changes
+    pub(crate) cards: RwLock<HashMap<String, PlayerCard>>,
+    pub(crate) titles: RwLock<HashMap<String, PlayerTitle>>,
+    pub(crate) game_version: RwLock<Option<String>>,
+}
+
+// The assets API reports failures as a single message instead of HenrikDev's error list
+#[cfg(feature = "client")]
+#[derive(Deserialize)]
+struct AssetError {
+    status: u32,
+    error: String,
+}
+
+#[cfg(feature = "client")]
+impl<'a> AssetClient<'a> {
+    pub fn new() -> Self {
+        AssetClient::default()
+    }
+
+    pub fn change_api_endpoint(mut self, endpoint: &'a str) -> Self {
+        self.api_end_point = endpoint;
+        self
+    }
+
+    pub async fn request<T>(&self, api_type: AssetApiType<'_>) -> Result<T, ValorantError>
+    where
+        T: DeserializeOwned + ValorantAPIData,
+    {
+        let response = self
+            .http
+            .get(format!(
+                "{}/{}",
+                self.api_end_point.trim_end_matches('/'),
+                api_type.to_url()
+            ))
+            .send()
+            .await?;
+        if response.status().is_success() {
+            return error::into_data(response.json::<ApiResponse<T>>().await?);
+        }
+
+        let AssetError { status, error } = response.json().await?;
+        let error = ApiError {
+            message: error,
+            code: 0,
+            details: String::new(),
+            retry_after: None,
+        };
+        Err(ValorantError::from_failure(status, vec![error]))
+    }
+
+    /// Looks up a player card, hitting the network only the first time an ID is seen.
+    pub async fn player_card(&self, id: &str) -> Result<PlayerCard, ValorantError> {
+        if let Some(card) = self.cards.read().unwrap().get(id) {
+            return Ok(card.clone());
+        }
+        let card = self
+            .request::<PlayerCard>(AssetApiType::PlayerCard { id })
+            .await?;
+        self.cards
+            .write()
+            .unwrap()
+            .insert(id.to_string(), card.clone());
+        Ok(card)
+    }
+
+    /// Looks up a player title, hitting the network only the first time an ID is seen.
+    pub async fn player_title(&self, id: &str) -> Result<PlayerTitle, ValorantError> {
+        if let Some(title) = self.titles.read().unwrap().get(id) {
+            return Ok(title.clone());
+        }
+        let title = self
+            .request::<PlayerTitle>(AssetApiType::PlayerTitle { id })
+            .await?;
+        self.titles
+            .write()
+            .unwrap()
+            .insert(id.to_string(), title.clone());
+        Ok(title)
+    }
+}
+
+#[cfg(feature = "client")]
+impl Default for AssetClient<'_> {
+    fn default() -> Self {
+        AssetClient {
+            api_end_point: DEFAULT_ASSET_END_POINT,
+            http: reqwest::Client::new(),
+            cards: RwLock::new(HashMap::new()),
+            titles: RwLock::new(HashMap::new()),
+            game_version: RwLock::new(None),
+        }
+    }
+}
+#[non_exhaustive]
+pub enum AssetApiType<'a> {
+    Seasons,
+    PlayerCard { id: &'a str },
+    PlayerTitle { id: &'a str },
+}
+
+impl AssetApiType<'_> {
+    pub fn to_url(&self) -> String {
+        match self {
+            Self::Seasons => "v1/seasons".to_string(),
+            Self::PlayerCard { id } => format!("v1/playercards/{}", id),
+            Self::PlayerTitle { id } => format!("v1/playertitles/{}", id),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PlayerCard {
+    uuid: String,
+    display_name: String,
+    display_icon: String,
+    small_art: String,
+    wide_art: String,
+    large_art: String,
+}
+
+impl PlayerCard {
+    pub fn display_name(&self) -> &str {
+        &self.display_name
+    }
+
+    pub fn large_art(&self) -> &str {
+        &self.large_art
+    }
+
+    pub fn wide_art(&self) -> &str {
+        &self.wide_art
+    }
+}
+
+impl ValorantAPIData for PlayerCard {}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PlayerTitle {
+    uuid: String,
+    display_name: Option<String>,
+    title_text: Option<String>,
+}
+
+impl PlayerTitle {
+    /// The text shown under the player's name, absent for the empty default title.
+    pub fn title_text(&self) -> Option<&str> {
+        self.title_text.as_deref()
+    }
+}
+
+impl ValorantAPIData for PlayerTitle {}
+
+#[cfg(feature = "client")]
+impl ProfileBanner {
+    /// Fetches the card's display name and full resolution art.
+    pub async fn resolve(&self, assets: &AssetClient<'_>) -> Result<PlayerCard, ValorantError> {
+        assets.player_card(self.id()).await
+    }
+}
+
+/// An episode or act as listed by the assets API. Acts point at their episode through
+/// `parent_uuid`.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Season {
+    uuid: String,
+    display_name: String,
+    #[serde(rename = "type")]
+    season_type: Option<String>,
+    start_time: DateTime<Utc>,
+    end_time: DateTime<Utc>,
+    parent_uuid: Option<String>,
+}
+
+impl Season {
+    pub fn uuid(&self) -> &str {
+        &self.uuid
+    }
+
+    pub fn display_name(&self) -> &str {
+        &self.display_name
+    }
+
+    pub fn is_act(&self) -> bool {
+        self.season_type.as_deref() == Some("EAresSeasonType::Act")
+    }
+
+    pub fn parent_uuid(&self) -> Option<&str> {
+        self.parent_uuid.as_deref()
+    }
+
+    pub fn start_time(&self) -> DateTime<Utc> {
+        self.start_time
+    }
+
+    pub fn end_time(&self) -> DateTime<Utc> {
+        self.end_time
+    }
+}
+
+impl ValorantAPIData for Season {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::fixtures::{decode, SEASONS};
+
+    #[test]
+    fn deserialize_response() {
+        let data: Vec<Season> = decode(SEASONS);
+        assert_eq!(data.len(), 6);
+        let (episode, act) = (&data[1], &data[2]);
+        assert_eq!(episode.display_name(), "EPISODE 5");
+        assert!(!episode.is_act());
+        assert_eq!(act.display_name(), "ACT II");
+        assert!(act.is_act());
+        assert_eq!(act.parent_uuid(), Some(episode.uuid()));
+        assert!(episode.start_time() < episode.end_time());
+    }
+
+    #[cfg(feature = "client")]
+    #[tokio::test]
+    async fn cards_and_titles_are_cached() {
+        use crate::mock_server::{MockResponse, MockServer};
+
+        let server = MockServer::routes(vec![
+            (
+                "/v1/playercards/bb6ae873-43ec-efb4-3ea6-93ac00a82d4e",
+                MockResponse::json(200, PLAYER_CARD),
+            ),
+            (
+                "/v1/playertitles/e3ca05a4-4e44-9afe-3791-7d96ca8f71fa",
+                MockResponse::json(200, PLAYER_TITLE),
+            ),
+            (
+                "/v1/playercards/00000000-0000-0000-0000-000000000000",
+                MockResponse::json(
+                    404,
+                    r#"{"status": 404, "error": "the requested uuid was not found"}"#,
+                ),
+            ),
+        ])
+        .await;
+        let url = server.url();
+        let assets = AssetClient::new().change_api_endpoint(&url);
+
+        let account: crate::account_data::AccountData = decode(crate::fixtures::ACCOUNT);
+        for _ in 0..2 {
+            let card = account.card().resolve(&assets).await.unwrap();
+            assert_eq!(card.display_name(), "Nerd Card");
+        }
+
+        let title = assets
+            .player_title("e3ca05a4-4e44-9afe-3791-7d96ca8f71fa")
+            .await
+            .unwrap();
+        assert_eq!(title.title_text(), Some("Nerd"));
+
+        let missing = assets
+            .player_card("00000000-0000-0000-0000-000000000000")
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            missing,
+            ValorantError::ApiError { status: 404, .. }
+        ));
+        assert_eq!(server.requests().len(), 3);
+    }
+
+    #[cfg(feature = "client")]
+    const PLAYER_CARD: &str = r#"{
+        "status": 200,
+        "data": {
+            "uuid": "bb6ae873-43ec-efb4-3ea6-93ac00a82d4e",
+            "displayName": "Nerd Card",
+            "isHiddenIfNotOwned": false,
+            "themeUuid": null,
+            "displayIcon": "https://media.valorant-api.com/playercards/bb6ae873-43ec-efb4-3ea6-93ac00a82d4e/displayicon.png",
+            "smallArt": "https://media.valorant-api.com/playercards/bb6ae873-43ec-efb4-3ea6-93ac00a82d4e/smallart.png",
+            "wideArt": "https://media.valorant-api.com/playercards/bb6ae873-43ec-efb4-3ea6-93ac00a82d4e/wideart.png",
+            "largeArt": "https://media.valorant-api.com/playercards/bb6ae873-43ec-efb4-3ea6-93ac00a82d4e/largeart.png",
+            "assetPath": "ShooterGame/Content/Personalization/PlayerCards/Nerd_PrimaryAsset"
+        }
+    }"#;
+
+    #[cfg(feature = "client")]
+    const PLAYER_TITLE: &str = r#"{
+        "status": 200,
+        "data": {
+            "uuid": "e3ca05a4-4e44-9afe-3791-7d96ca8f71fa",
+            "displayName": "Nerd Title",
+            "titleText": "Nerd",
+            "isHiddenIfNotOwned": false,
+            "assetPath": "ShooterGame/Content/Personalization/PlayerTitles/Nerd_PrimaryAsset"
+        }
+    }"#;
+}
@@ -0,0 +1,367 @@
+//! Downloads players' stored matches page by page into a storage backend.
+//!
+//! Work is driven by a [`WorkQueue`] and the next page of every player is saved after each
+//! request, so an interrupted backfill resumes where it stopped instead of starting over. Attach
+//! a [`RateBudget`](crate::rate_limit::RateBudget) to the client to keep a backfill of many
+//! players within the key's limit.
+
+use crate::deadline::{self, Deadline};
+use crate::error::ValorantError;
+use crate::queue::{Job, WorkQueue};
+use crate::storage::{Storage, StorageExt};
+use crate::validation::MAX_PAGE_SIZE;
+use crate::{AccountRegion, ValorantClient};
+use serde::{Deserialize, Serialize};
+use std::{fmt, io};
+
+/// Stored matches keyed by match ID.
+pub const MATCHES: &str = "stored_matches";
+const PROGRESS: &str = "backfill_progress";
+const QUEUE: &str = "backfill";
+const DEFAULT_PAGE_SIZE: u32 = 20;
+
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum BackfillError {
+    Request(ValorantError),
+    Storage(io::Error),
+}
+
+impl fmt::Display for BackfillError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BackfillError::Request(error) => write!(f, "Backfill request failed: {error}"),
+            BackfillError::Storage(error) => write!(f, "Backfill storage failed: {error}"),
+        }
+    }
+}
+
+impl std::error::Error for BackfillError {}
+
+impl From<ValorantError> for BackfillError {
+    fn from(error: ValorantError) -> Self {
+        BackfillError::Request(error)
+    }
+}
+
+impl From<io::Error> for BackfillError {
+    fn from(error: io::Error) -> Self {
+        BackfillError::Storage(error)
+    }
+}
+
+/// How far the backfill of one player got.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+pub struct BackfillProgress {
+    next_page: u32,
+    matches_stored: u32,
+    done: bool,
+}
+
+impl BackfillProgress {
+    pub fn next_page(&self) -> u32 {
+        self.next_page.max(1)
+    }
+
+    pub fn matches_stored(&self) -> u32 {
+        self.matches_stored
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.done
+    }
+}
+
+/// What a single [`Backfill::run`] did.
+#[derive(Debug, Default)]
+pub struct BackfillReport {
+    pub pages_fetched: u32,
+    pub matches_stored: u32,
+    /// Players whose backfill was abandoned together with the status code the API gave.
+    pub failed: Vec<(String, u32)>,
+    /// Whether [`Backfill::run_within`] stopped at its deadline with work left in the queue.
+    pub deadline_exceeded: bool,
+}
+
+pub struct Backfill<'c, 'a, S> {
+    client: &'c ValorantClient<'a>,
+    storage: S,
+    queue: WorkQueue<S>,
+    page_size: u32,
+}
+
+impl<'c, 'a, S: Storage + Clone> Backfill<'c, 'a, S> {
+    /// Opens the backfill state in `storage`, including any work left by an earlier run.
+    pub fn new(client: &'c ValorantClient<'a>, storage: S) -> io::Result<Self> {
+        Ok(Backfill {
+            client,
+            queue: WorkQueue::open(storage.clone(), QUEUE)?,
+            storage,
+            page_size: DEFAULT_PAGE_SIZE,
+        })
+    }
+
+    /// Capped at [`MAX_PAGE_SIZE`], and lowered further when the server turns out to serve
+    /// smaller pages.
+    pub fn page_size(mut self, size: u32) -> Self {
+        self.page_size = size.clamp(1, MAX_PAGE_SIZE);
+        self
+    }
+
+    /// Queues players for backfilling. Players that already finished are skipped.
+    pub fn add_player(&self, region: AccountRegion, name: &str, tag: &str) -> io::Result<()> {
+        let progress = self.progress(region, name, tag)?;
+        if progress.done {
+            return Ok(());
+        }
+        self.queue.push(Job::BackfillPage {
+            region,
+            name: name.to_string(),
+            tag: tag.to_string(),
+            page: progress.next_page(),
+        })?;
+        Ok(())
+    }
+
+    pub fn progress(
+        &self,
+        region: AccountRegion,
+        name: &str,
+        tag: &str,
+    ) -> io::Result<BackfillProgress> {
+        Ok(self
+            .storage
+            .get_json(PROGRESS, &player_key(region, name, tag))?
+            .unwrap_or_default())
+    }
+
+    /// Works through the queue until every player's history is stored. On a request or storage
+    /// error the current page stays queued, so calling `run` again resumes from it.
+    pub async fn run(&self) -> Result<BackfillReport, BackfillError> {
+        self.run_until(None).await
+    }
+
+    /// Like [`run`](Self::run), but stops once `deadline` passes. Every queued page gets an even
+    /// share of the remaining time, and a page that runs out of time stays queued for the next
+    /// run.
+    pub async fn run_within(&self, deadline: Deadline) -> Result<BackfillReport, BackfillError> {
+        self.run_until(Some(&deadline)).await
+    }
+
+    async fn run_until(
+        &self,
+        deadline: Option<&Deadline>,
+    ) -> Result<BackfillReport, BackfillError> {
+        let mut report = BackfillReport::default();
+        while let Some(job) = self.queue.peek() {
+            let Job::BackfillPage {
+                region,
+                name,
+                tag,
+                page,
+            } = &job
+            else {
+                // Only backfill pages are queued here
+                self.queue.complete(&job)?;
+                continue;
+            };
+
+            let request =
+                self.client
+                    .stored_matches_page(*region, name, tag, *page, self.page_size);
+            let Ok(response) = deadline::step(deadline, self.queue.len() as u32, request).await
+            else {
+                report.deadline_exceeded = true;
+                break;
+            };
+            report.pages_fetched += 1;
+
+            let key = player_key(*region, name, tag);
+            let mut progress = self.progress(*region, name, tag)?;
+            match response {
+                Ok(data) => {
+                    for stored in &data.matches {
+                        self.storage
+                            .put_json(MATCHES, stored.match_id().as_str(), stored)?;
+                    }
+                    // When the server shrank its pages, the page starts before the matches
+                    // stored so far. Matches are keyed by ID, so the overlap is only rewritten.
+                    let reached = data.offset() + data.matches.len() as u32;
+                    let stored = reached.saturating_sub(progress.matches_stored);
+                    progress.matches_stored += stored;
+                    progress.next_page = reached / data.size + 1;
+                    progress.done = !data.has_more();
+                    report.matches_stored += stored;
+                }
+                Err(error @ (ValorantError::Transport(_) | ValorantError::Decode(_))) => {
+                    return Err(error.into())
+                }
+                // Rejected input counts as the 400 the API would have answered it with
+                Err(error) => {
+                    progress.done = true;
+                    report
+                        .failed
+                        .push((key.clone(), error.status().unwrap_or(400)));
+                }
+            }
+            self.storage.put_json(PROGRESS, &key, &progress)?;
+
+            if !progress.done {
+                self.queue.push(Job::BackfillPage {
+                    region: *region,
+                    name: name.clone(),
+                    tag: tag.clone(),
+                    page: progress.next_page,
+                })?;
+            }
+            self.queue.complete(&job)?;
+        }
+        Ok(report)
+    }
+}
+
+fn player_key(region: AccountRegion, name: &str, tag: &str) -> String {
+    format!(
+        "{}/{}#{}",
+        region.to_value(),
+        name.to_lowercase(),
+        tag.to_lowercase()
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::fixtures::STORED_MATCHES;
+    use crate::mock_server::{MockResponse, MockServer};
+    use crate::storage::MemoryStorage;
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    const LAST_PAGE: &str = r#"{
+        "status": 200,
+        "results": {"total": 3, "returned": 1, "before": 2, "after": 0},
+        "data": [{
+            "meta": {
+                "id": "a1b2c3d4-0000-4000-8000-000000000003",
+                "map": {"id": "d960549e-485c-e861-8d71-aa9d1aed12a2", "name": "Split"},
+                "version": "release-06.03-shipping-8-826091",
+                "mode": "Competitive",
+                "started_at": "2023-02-01T18:00:00.000Z",
+                "season": {"id": "34093c29-4306-43de-452f-3f944bde22be", "short": "e6a1"},
+                "region": "eu",
+                "cluster": "Frankfurt"
+            },
+            "stats": {
+                "puuid": "b44adaae-ab83-5001-a296-89ea0de0bce3",
+                "team": "Red",
+                "level": 140,
+                "character": {"id": "add6443a-41bd-e414-f6ad-e58d267f4e95", "name": "Jett"},
+                "tier": 15,
+                "score": 4100,
+                "kills": 17,
+                "deaths": 12,
+                "assists": 4
+            },
+            "teams": {"red": 11, "blue": 13}
+        }]
+    }"#;
+
+    #[tokio::test]
+    async fn resumes_after_an_interruption() {
+        let server = MockServer::routes(vec![
+            (
+                "/NitroSniper/NERD?page=1&size=2",
+                MockResponse::json(200, STORED_MATCHES),
+            ),
+            (
+                "/NitroSniper/NERD?page=2&size=2",
+                MockResponse::json(500, "Internal Server Error"),
+            ),
+        ])
+        .await;
+        let url = server.url();
+        let client = ValorantClient::new().change_api_endpoint(&url);
+        let storage = Arc::new(MemoryStorage::new());
+
+        let backfill = Backfill::new(&client, storage.clone())
+            .unwrap()
+            .page_size(2);
+        backfill
+            .add_player(AccountRegion::EU, "NitroSniper", "NERD")
+            .unwrap();
+        backfill
+            .add_player(AccountRegion::EU, "Missing", "NERD")
+            .unwrap();
+        // Page 2 can't be decoded, which interrupts the run after the unknown player gave up
+        assert!(backfill.run().await.is_err());
+        assert!(backfill
+            .progress(AccountRegion::EU, "Missing", "NERD")
+            .unwrap()
+            .is_done());
+        let progress = backfill
+            .progress(AccountRegion::EU, "NitroSniper", "NERD")
+            .unwrap();
+        assert_eq!((progress.next_page(), progress.matches_stored()), (2, 2));
+
+        let server = MockServer::routes(vec![(
+            "/NitroSniper/NERD?page=2&size=2",
+            MockResponse::json(200, LAST_PAGE),
+        )])
+        .await;
+        let url = server.url();
+        let client = ValorantClient::new().change_api_endpoint(&url);
+        let backfill = Backfill::new(&client, storage.clone())
+            .unwrap()
+            .page_size(2);
+        let report = backfill.run().await.unwrap();
+        assert_eq!((report.pages_fetched, report.matches_stored), (1, 1));
+        assert!(report.failed.is_empty());
+        assert_eq!(server.requests().len(), 1);
+
+        assert!(backfill
+            .progress(AccountRegion::EU, "NitroSniper", "NERD")
+            .unwrap()
+            .is_done());
+        assert_eq!(storage.keys(MATCHES).unwrap().len(), 3);
+
+        // Finished players aren't queued again
+        backfill
+            .add_player(AccountRegion::EU, "NitroSniper", "NERD")
+            .unwrap();
+        assert_eq!(backfill.run().await.unwrap().pages_fetched, 0);
+    }
+
+    #[tokio::test]
+    async fn stops_at_the_deadline() {
+        let server = MockServer::routes(vec![(
+            "/NitroSniper/NERD?page=1&size=2",
+            MockResponse::json(200, STORED_MATCHES).delay(Duration::from_millis(300)),
+        )])
+        .await;
+        let url = server.url();
+        let client = ValorantClient::new().change_api_endpoint(&url);
+        let backfill = Backfill::new(&client, Arc::new(MemoryStorage::new()))
+            .unwrap()
+            .page_size(2);
+        backfill
+            .add_player(AccountRegion::EU, "NitroSniper", "NERD")
+            .unwrap();
+
+        let report = backfill
+            .run_within(Deadline::after(Duration::from_millis(100)))
+            .await
+            .unwrap();
+        assert!(report.deadline_exceeded);
+        assert_eq!(report.pages_fetched, 0);
+        // The page is still queued for the next run
+        assert_eq!(
+            backfill
+                .progress(AccountRegion::EU, "NitroSniper", "NERD")
+                .unwrap()
+                .next_page(),
+            1
+        );
+        assert_eq!(backfill.queue.len(), 1);
+    }
+}
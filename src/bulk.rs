@@ -0,0 +1,231 @@
+//! Many independent requests at once, for scrapers and bulk lookups.
+//!
+//! Unlike a `Result<Vec<T>>`, a [`BulkResult`] keeps going when single items fail and records
+//! what happened to every input.
+
+use crate::account_data::AccountData;
+use crate::error::ValorantError;
+use crate::ids::RiotId;
+use crate::validation::ValidationError;
+use crate::{ValorantAPIData, ValorantApiType, ValorantClient};
+use futures::stream::{self, StreamExt};
+use serde::de::DeserializeOwned;
+
+// Enough to keep a connection pool busy without queueing hundreds of requests at once. Attach a
+// rate budget to stay within the key's limit.
+pub(crate) const MAX_IN_FLIGHT: usize = 8;
+
+/// The outcome of every input, identified by its index in the input.
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct BulkResult<T> {
+    pub succeeded: Vec<(usize, T)>,
+    pub failed: Vec<(usize, ValorantError)>,
+    /// Inputs that were never sent, e.g. because they failed validation or repeat an earlier
+    /// input.
+    pub skipped: Vec<(usize, SkipReason)>,
+}
+
+impl<T> Default for BulkResult<T> {
+    fn default() -> Self {
+        BulkResult {
+            succeeded: Vec::new(),
+            failed: Vec::new(),
+            skipped: Vec::new(),
+        }
+    }
+}
+
+impl<T> BulkResult<T> {
+    pub fn is_complete(&self) -> bool {
+        self.failed.is_empty() && self.skipped.is_empty()
+    }
+
+    fn sort(&mut self) {
+        self.succeeded.sort_by_key(|(index, _)| *index);
+        self.failed.sort_by_key(|(index, _)| *index);
+        self.skipped.sort_by_key(|(index, _)| *index);
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum SkipReason {
+    Invalid(ValidationError),
+    /// Same as the input at this index.
+    Duplicate(usize),
+}
+
+impl ValorantClient<'_> {
+    /// Sends every request, a few at a time, and collects each one's outcome.
+    pub async fn request_many<T>(&self, api_types: Vec<ValorantApiType<'_>>) -> BulkResult<T>
+    where
+        T: DeserializeOwned + ValorantAPIData,
+    {
+        let mut result = BulkResult::default();
+        let mut valid = Vec::new();
+        for (index, api_type) in api_types.into_iter().enumerate() {
+            match api_type.validate() {
+                Ok(()) => valid.push((index, api_type)),
+                Err(error) => result.skipped.push((index, SkipReason::Invalid(error))),
+            }
+        }
+
+        // Collected before streaming, so the stream holds no closure and spawned callers can
+        // still tell the future is `Send`
+        let requests: Vec<_> = valid
+            .into_iter()
+            .map(|(index, api_type)| async move { (index, self.request::<T>(api_type).await) })
+            .collect();
+        let mut responses = stream::iter(requests).buffer_unordered(MAX_IN_FLIGHT);
+        while let Some((index, response)) = responses.next().await {
+            match response {
+                Ok(data) => result.succeeded.push((index, data)),
+                Err(error) => result.failed.push((index, error)),
+            }
+        }
+        result.sort();
+        result
+    }
+
+    /// Looks up the accounts of many Riot IDs. IDs repeating an earlier one, ignoring case, are
+    /// only looked up once.
+    pub async fn resolve_many(&self, ids: &[RiotId]) -> BulkResult<AccountData> {
+        // Each unique key with the input it first appeared at
+        let mut seen: Vec<((String, String), usize)> = Vec::new();
+        let mut duplicates = Vec::new();
+        for (index, id) in ids.iter().enumerate() {
+            let key = (id.name().to_lowercase(), id.tag().to_lowercase());
+            match seen.iter().find(|(seen_key, _)| *seen_key == key) {
+                Some((_, first)) => duplicates.push((index, SkipReason::Duplicate(*first))),
+                None => seen.push((key, index)),
+            }
+        }
+        let unique = seen.into_iter().map(|(_, index)| index).collect::<Vec<_>>();
+
+        let requests = unique
+            .iter()
+            .map(|&index| ValorantApiType::AccountData {
+                name: ids[index].name(),
+                tag: ids[index].tag(),
+            })
+            .collect::<Vec<_>>();
+        let mut result = self.request_many::<AccountData>(requests).await;
+
+        // Map the positions among the unique IDs back to the caller's input
+        for (index, _) in &mut result.succeeded {
+            *index = unique[*index];
+        }
+        for (index, _) in &mut result.failed {
+            *index = unique[*index];
+        }
+        result.skipped.extend(duplicates);
+        result.sort();
+        result
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::account_data::test::RESPONSE_200;
+    use crate::mock_server::{MockResponse, MockServer};
+    use crate::AccountRegion;
+
+    #[tokio::test]
+    async fn records_every_input() {
+        let server = MockServer::routes(vec![
+            (
+                "/v1/account/NitroSniper/NERD",
+                MockResponse::json(200, RESPONSE_200),
+            ),
+            (
+                "/v1/account/Missing/NERD",
+                MockResponse::json(404, crate::fixtures::NOT_FOUND),
+            ),
+        ])
+        .await;
+        let url = server.url();
+        let client = ValorantClient::new().change_api_endpoint(&url);
+
+        let result = client
+            .request_many::<AccountData>(vec![
+                ValorantApiType::AccountData {
+                    name: "NitroSniper",
+                    tag: "NERD",
+                },
+                ValorantApiType::AccountData {
+                    name: "Missing",
+                    tag: "NERD",
+                },
+                ValorantApiType::StoredMatches {
+                    region: AccountRegion::EU,
+                    name: "NitroSniper",
+                    tag: "NERD",
+                    filter: None,
+                    page: Some(0),
+                    size: None,
+                },
+            ])
+            .await;
+        assert_eq!(result.succeeded.len(), 1);
+        assert_eq!(result.succeeded[0].0, 0);
+        assert!(matches!(
+            result.failed[..],
+            [(1, ValorantError::ApiError { status: 404, .. })]
+        ));
+        assert!(matches!(
+            result.skipped[..],
+            [(2, SkipReason::Invalid(ValidationError::InvalidPage(0)))]
+        ));
+        assert_eq!(server.requests().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn resolve_many_dedupes_ids() {
+        let server = MockServer::routes(vec![(
+            "/v1/account/NitroSniper/NERD",
+            MockResponse::json(200, RESPONSE_200),
+        )])
+        .await;
+        let url = server.url();
+        let client = ValorantClient::new().change_api_endpoint(&url);
+
+        let ids = ["Missing#NERD", "NitroSniper#NERD", "nitrosniper#nerd"]
+            .map(|id| id.parse::<RiotId>().unwrap());
+        let result = client.resolve_many(&ids).await;
+        assert_eq!(result.succeeded.len(), 1);
+        assert_eq!(result.succeeded[0].0, 1);
+        assert_eq!(result.failed[0].0, 0);
+        assert_eq!(result.skipped, [(2, SkipReason::Duplicate(1))]);
+        assert_eq!(server.requests().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn duplicates_point_at_their_first_input() {
+        let server = MockServer::routes(vec![(
+            "/v1/account/NitroSniper/NERD",
+            MockResponse::json(200, RESPONSE_200),
+        )])
+        .await;
+        let url = server.url();
+        let client = ValorantClient::new().change_api_endpoint(&url);
+
+        // The second Riot ID first shows up after a duplicate of the first one
+        let ids = [
+            "NitroSniper#NERD",
+            "NitroSniper#NERD",
+            "Missing#NERD",
+            "missing#nerd",
+        ]
+        .map(|id| id.parse::<RiotId>().unwrap());
+        let result = client.resolve_many(&ids).await;
+        assert_eq!(result.succeeded[0].0, 0);
+        assert_eq!(result.failed[0].0, 2);
+        assert_eq!(
+            result.skipped,
+            [(1, SkipReason::Duplicate(0)), (3, SkipReason::Duplicate(2))]
+        );
+        assert_eq!(server.requests().len(), 2);
+    }
+}
@@ -0,0 +1,219 @@
+//! Records featured store rotations so shop integrations can answer when a bundle or skin was
+//! last on sale, and at what price.
+
+use crate::storage::{Storage, StorageExt};
+use crate::store::FeaturedBundle;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::io;
+
+const ROTATIONS: &str = "bundle_rotations";
+const SIGHTINGS: &str = "item_sightings";
+
+// Observations of the same rotation compute slightly different end times from
+// `seconds_remaining`, so end times this close together are treated as the same rotation
+const SAME_ROTATION_SLACK: i64 = 60 * 60;
+
+/// One stretch of time a bundle was featured in the store.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct BundleRotation {
+    first_seen: DateTime<Utc>,
+    last_seen: DateTime<Utc>,
+    ends_at: DateTime<Utc>,
+    price: u32,
+}
+
+impl BundleRotation {
+    pub fn first_seen(&self) -> DateTime<Utc> {
+        self.first_seen
+    }
+
+    pub fn last_seen(&self) -> DateTime<Utc> {
+        self.last_seen
+    }
+
+    /// When the store said the rotation would end.
+    pub fn ends_at(&self) -> DateTime<Utc> {
+        self.ends_at
+    }
+
+    pub fn price(&self) -> u32 {
+        self.price
+    }
+}
+
+/// The latest time an item was seen in a featured bundle.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ItemSighting {
+    name: String,
+    bundle_uuid: String,
+    last_seen: DateTime<Utc>,
+    base_price: u32,
+    discounted_price: u32,
+}
+
+impl ItemSighting {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn bundle_uuid(&self) -> &str {
+        &self.bundle_uuid
+    }
+
+    pub fn last_seen(&self) -> DateTime<Utc> {
+        self.last_seen
+    }
+
+    pub fn base_price(&self) -> u32 {
+        self.base_price
+    }
+
+    pub fn discounted_price(&self) -> u32 {
+        self.discounted_price
+    }
+}
+
+pub struct BundleHistory<S> {
+    storage: S,
+}
+
+impl<S: Storage> BundleHistory<S> {
+    pub fn new(storage: S) -> Self {
+        BundleHistory { storage }
+    }
+
+    /// Records the featured bundles as they were at `observed_at`. Polling the store
+    /// periodically and recording every response builds up the rotation history.
+    pub fn record(&self, bundles: &[FeaturedBundle], observed_at: DateTime<Utc>) -> io::Result<()> {
+        for bundle in bundles {
+            let ends_at = observed_at + Duration::seconds(bundle.seconds_remaining() as i64);
+            let mut rotations = self.rotations(bundle.bundle_uuid())?;
+            let existing = rotations.iter_mut().find(|rotation| {
+                (rotation.ends_at - ends_at).num_seconds().abs() <= SAME_ROTATION_SLACK
+            });
+            match existing {
+                Some(rotation) => {
+                    rotation.first_seen = rotation.first_seen.min(observed_at);
+                    rotation.last_seen = rotation.last_seen.max(observed_at);
+                }
+                None => {
+                    rotations.push(BundleRotation {
+                        first_seen: observed_at,
+                        last_seen: observed_at,
+                        ends_at,
+                        price: bundle.bundle_price(),
+                    });
+                    rotations.sort_by_key(|rotation| rotation.first_seen);
+                }
+            }
+            self.storage
+                .put_json(ROTATIONS, bundle.bundle_uuid(), &rotations)?;
+
+            for item in bundle.items() {
+                let newer = self
+                    .last_seen(item.uuid())?
+                    .is_none_or(|sighting| sighting.last_seen <= observed_at);
+                if newer {
+                    let sighting = ItemSighting {
+                        name: item.name().to_string(),
+                        bundle_uuid: bundle.bundle_uuid().to_string(),
+                        last_seen: observed_at,
+                        base_price: item.base_price(),
+                        discounted_price: item.discounted_price(),
+                    };
+                    self.storage.put_json(SIGHTINGS, item.uuid(), &sighting)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Every recorded rotation of a bundle, oldest first.
+    pub fn rotations(&self, bundle_uuid: &str) -> io::Result<Vec<BundleRotation>> {
+        Ok(self
+            .storage
+            .get_json(ROTATIONS, bundle_uuid)?
+            .unwrap_or_default())
+    }
+
+    /// When an item, usually a skin level, was last in the store.
+    pub fn last_seen(&self, item_uuid: &str) -> io::Result<Option<ItemSighting>> {
+        self.storage.get_json(SIGHTINGS, item_uuid)
+    }
+
+    /// Looks an item up by display name, ignoring case.
+    pub fn last_seen_by_name(&self, name: &str) -> io::Result<Option<ItemSighting>> {
+        for uuid in self.storage.keys(SIGHTINGS)? {
+            if let Some(sighting) = self.last_seen(&uuid)? {
+                if sighting.name.eq_ignore_ascii_case(name) {
+                    return Ok(Some(sighting));
+                }
+            }
+        }
+        Ok(None)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::fixtures::{decode, STORE_FEATURED};
+    use crate::storage::MemoryStorage;
+
+    #[test]
+    fn rotations_and_sightings() {
+        let bundles: Vec<FeaturedBundle> = decode(STORE_FEATURED);
+        let history = BundleHistory::new(MemoryStorage::new());
+        let start: DateTime<Utc> = "2023-09-01T00:00:00Z".parse().unwrap();
+
+        // Two polls of the same rotation, then a rerun of the bundle weeks later
+        history.record(&bundles, start).unwrap();
+        history
+            .record(&bundles, start + Duration::minutes(30))
+            .unwrap();
+        let rerun = start + Duration::weeks(10);
+        history.record(&bundles[..1], rerun).unwrap();
+
+        let rotations = history
+            .rotations("2116a38e-4b71-f169-0d16-ce9289af4bfa")
+            .unwrap();
+        assert_eq!(rotations.len(), 2);
+        assert_eq!(rotations[0].first_seen(), start);
+        assert_eq!(rotations[0].last_seen(), start + Duration::minutes(30));
+        assert_eq!(rotations[0].ends_at(), start + Duration::weeks(1));
+        assert_eq!(rotations[1].first_seen(), rerun);
+
+        let vandal = history
+            .last_seen_by_name("prime//2.0 vandal")
+            .unwrap()
+            .unwrap();
+        assert_eq!(vandal.last_seen(), rerun);
+        assert_eq!(vandal.discounted_price(), 1189);
+
+        let champions = history
+            .last_seen("0e9f1a2b-4c3d-8e7f-a6b5-c4d3e2f1a0b9")
+            .unwrap()
+            .unwrap();
+        assert_eq!(champions.last_seen(), start + Duration::minutes(30));
+        assert!(history.last_seen("unknown").unwrap().is_none());
+
+        // Recording an older observation late must not move the sighting back in time
+        history.record(&bundles, start).unwrap();
+        assert_eq!(
+            history
+                .last_seen("5ca3b2a4-4a0a-4bb9-0d1c-00b7d35b1e8d")
+                .unwrap()
+                .unwrap()
+                .last_seen(),
+            rerun
+        );
+        assert_eq!(
+            history
+                .rotations("2116a38e-4b71-f169-0d16-ce9289af4bfa")
+                .unwrap()
+                .len(),
+            2
+        );
+    }
+}
@@ -0,0 +1,265 @@
+//! Caches for the responses the client reuses during a
+//! [`player_cooldown`](crate::ValorantClientBuilder::player_cooldown).
+//!
+//! Clients keep recent responses in a [`MemoryCache`] by default. [`DiskCache`] keeps them
+//! across restarts, and the `cache-redis` feature's `RedisCache` shares them between processes.
+//! Implement [`ResponseCache`] to plug in any other store.
+
+use futures::future::BoxFuture;
+use std::collections::HashMap;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// A response as it came from the API.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CachedResponse {
+    pub status: u16,
+    pub body: Vec<u8>,
+}
+
+/// A backend for cached responses, open for implementation.
+///
+/// Keys are the lowercased request URLs. The client treats errors as cache misses, so a cache
+/// that is down only costs requests.
+pub trait ResponseCache: Send + Sync {
+    /// The response stored under `key`, unless it expired.
+    fn get<'a>(&'a self, key: &'a str) -> BoxFuture<'a, io::Result<Option<CachedResponse>>>;
+
+    /// Stores a response that expires after `ttl`.
+    fn put<'a>(
+        &'a self,
+        key: &'a str,
+        response: CachedResponse,
+        ttl: Duration,
+    ) -> BoxFuture<'a, io::Result<()>>;
+
+    fn invalidate<'a>(&'a self, key: &'a str) -> BoxFuture<'a, io::Result<()>>;
+}
+
+// Lets several clients share one cache
+impl<C: ResponseCache + ?Sized> ResponseCache for std::sync::Arc<C> {
+    fn get<'a>(&'a self, key: &'a str) -> BoxFuture<'a, io::Result<Option<CachedResponse>>> {
+        (**self).get(key)
+    }
+
+    fn put<'a>(
+        &'a self,
+        key: &'a str,
+        response: CachedResponse,
+        ttl: Duration,
+    ) -> BoxFuture<'a, io::Result<()>> {
+        (**self).put(key, response, ttl)
+    }
+
+    fn invalidate<'a>(&'a self, key: &'a str) -> BoxFuture<'a, io::Result<()>> {
+        (**self).invalidate(key)
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct MemoryCache {
+    responses: Mutex<HashMap<String, (Instant, CachedResponse)>>,
+}
+
+impl MemoryCache {
+    pub fn new() -> Self {
+        MemoryCache::default()
+    }
+}
+
+impl ResponseCache for MemoryCache {
+    fn get<'a>(&'a self, key: &'a str) -> BoxFuture<'a, io::Result<Option<CachedResponse>>> {
+        let responses = self.responses.lock().unwrap();
+        let response = responses
+            .get(key)
+            .filter(|(expires_at, _)| Instant::now() < *expires_at)
+            .map(|(_, response)| response.clone());
+        Box::pin(async move { Ok(response) })
+    }
+
+    fn put<'a>(
+        &'a self,
+        key: &'a str,
+        response: CachedResponse,
+        ttl: Duration,
+    ) -> BoxFuture<'a, io::Result<()>> {
+        let now = Instant::now();
+        let mut responses = self.responses.lock().unwrap();
+        // Expired responses are dropped here so the map doesn't grow with every player seen
+        responses.retain(|_, (expires_at, _)| now < *expires_at);
+        responses.insert(key.to_string(), (now + ttl, response));
+        Box::pin(async { Ok(()) })
+    }
+
+    fn invalidate<'a>(&'a self, key: &'a str) -> BoxFuture<'a, io::Result<()>> {
+        self.responses.lock().unwrap().remove(key);
+        Box::pin(async { Ok(()) })
+    }
+}
+
+/// Stores each response as a file under `root`, so a restarted bot doesn't refetch every
+/// player it answered just before.
+#[derive(Debug, Clone)]
+pub struct DiskCache {
+    root: PathBuf,
+}
+
+impl DiskCache {
+    pub fn new(root: impl AsRef<Path>) -> Self {
+        DiskCache {
+            root: root.as_ref().to_path_buf(),
+        }
+    }
+
+    // URLs contain `/` and `?`, so file names are hex encoded
+    fn path(&self, key: &str) -> PathBuf {
+        let name: String = key.bytes().map(|byte| format!("{byte:02x}")).collect();
+        self.root.join(name)
+    }
+}
+
+impl ResponseCache for DiskCache {
+    fn get<'a>(&'a self, key: &'a str) -> BoxFuture<'a, io::Result<Option<CachedResponse>>> {
+        Box::pin(async move {
+            let value = match tokio::fs::read(self.path(key)).await {
+                Ok(value) => value,
+                Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(None),
+                Err(error) => return Err(error),
+            };
+            // The file starts with the expiry in milliseconds since the epoch and the status
+            let Some((expires_at, rest)) = value.split_first_chunk::<8>() else {
+                return Ok(None);
+            };
+            let Some((status, body)) = rest.split_first_chunk::<2>() else {
+                return Ok(None);
+            };
+            if u64::from_be_bytes(*expires_at) <= unix_millis(SystemTime::now()) {
+                return Ok(None);
+            }
+            Ok(Some(CachedResponse {
+                status: u16::from_be_bytes(*status),
+                body: body.to_vec(),
+            }))
+        })
+    }
+
+    fn put<'a>(
+        &'a self,
+        key: &'a str,
+        response: CachedResponse,
+        ttl: Duration,
+    ) -> BoxFuture<'a, io::Result<()>> {
+        Box::pin(async move {
+            let mut value = unix_millis(SystemTime::now() + ttl).to_be_bytes().to_vec();
+            value.extend_from_slice(&response.status.to_be_bytes());
+            value.extend_from_slice(&response.body);
+
+            tokio::fs::create_dir_all(&self.root).await?;
+            let path = self.path(key);
+            let temporary = path.with_extension("tmp");
+            tokio::fs::write(&temporary, value).await?;
+            tokio::fs::rename(temporary, path).await
+        })
+    }
+
+    fn invalidate<'a>(&'a self, key: &'a str) -> BoxFuture<'a, io::Result<()>> {
+        Box::pin(async move {
+            match tokio::fs::remove_file(self.path(key)).await {
+                Err(error) if error.kind() != io::ErrorKind::NotFound => Err(error),
+                _ => Ok(()),
+            }
+        })
+    }
+}
+
+fn unix_millis(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .map_or(0, |since| since.as_millis() as u64)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::account_data::{test::RESPONSE_200, AccountData};
+    use crate::mock_server::{MockResponse, MockServer};
+    use crate::{ValorantApiType, ValorantClient};
+    use std::sync::Arc;
+
+    async fn expiry(cache: &dyn ResponseCache) {
+        let response = CachedResponse {
+            status: 200,
+            body: b"{}".to_vec(),
+        };
+        cache
+            .put(
+                "v1/account/a/b",
+                response.clone(),
+                Duration::from_millis(100),
+            )
+            .await
+            .unwrap();
+        cache
+            .put("v1/account/c/d", response.clone(), Duration::from_secs(30))
+            .await
+            .unwrap();
+        assert_eq!(cache.get("v1/account/a/b").await.unwrap(), Some(response));
+        assert_eq!(cache.get("v1/account/x/y").await.unwrap(), None);
+
+        tokio::time::sleep(Duration::from_millis(150)).await;
+        assert_eq!(cache.get("v1/account/a/b").await.unwrap(), None);
+        cache.invalidate("v1/account/c/d").await.unwrap();
+        assert_eq!(cache.get("v1/account/c/d").await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn built_in_caches_expire() {
+        expiry(&MemoryCache::new()).await;
+
+        let root = std::env::temp_dir().join(format!("valorant-api-cache-{}", std::process::id()));
+        expiry(&DiskCache::new(&root)).await;
+        std::fs::remove_dir_all(root).unwrap();
+    }
+
+    #[tokio::test]
+    async fn clients_share_a_cache() {
+        let server = MockServer::start(vec![MockResponse::json(200, RESPONSE_200)]).await;
+        let url = server.url();
+        let cache = Arc::new(MemoryCache::new());
+        let client = || {
+            ValorantClient::builder()
+                .api_endpoint(&url)
+                .player_cooldown(Duration::from_secs(30))
+                .response_cache(cache.clone())
+                .build()
+                .unwrap()
+        };
+
+        let (first, second) = (client(), client());
+        let account = ValorantApiType::AccountData {
+            name: "NitroSniper",
+            tag: "NERD",
+        };
+        first.request::<AccountData>(account).await.unwrap();
+        let account = ValorantApiType::AccountData {
+            name: "NitroSniper",
+            tag: "NERD",
+        };
+        second.request::<AccountData>(account).await.unwrap();
+        assert_eq!(server.requests().len(), 1);
+        assert!(cache
+            .responses
+            .lock()
+            .unwrap()
+            .contains_key("v1/account/nitrosniper/nerd"));
+
+        let account = ValorantApiType::AccountData {
+            name: "NitroSniper",
+            tag: "NERD",
+        };
+        first.invalidate(&account).await.unwrap();
+        second.request::<AccountData>(account).await.unwrap();
+        assert_eq!(server.requests().len(), 2);
+    }
+}
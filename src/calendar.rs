@@ -0,0 +1,141 @@
+//! Real-world dates for every episode and act.
+
+use crate::assets::Season;
+#[cfg(feature = "client")]
+use crate::assets::{AssetApiType, AssetClient};
+#[cfg(feature = "client")]
+use crate::error::ValorantError;
+use crate::mmr_data::EpisodeAndAct;
+use chrono::{DateTime, Utc};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ActWindow {
+    pub season: EpisodeAndAct,
+    pub id: String,
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+}
+
+impl ActWindow {
+    pub fn contains(&self, at: DateTime<Utc>) -> bool {
+        self.start <= at && at < self.end
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ActCalendar {
+    acts: Vec<ActWindow>,
+}
+
+impl ActCalendar {
+    /// Builds the calendar from the assets API's season list. Entries whose names don't follow
+    /// the `EPISODE n` / `ACT I..III` scheme (e.g. the closed beta) are skipped.
+    pub fn from_seasons(seasons: &[Season]) -> Self {
+        let mut acts = seasons
+            .iter()
+            .filter(|season| season.is_act())
+            .filter_map(|act| {
+                let episode = seasons
+                    .iter()
+                    .find(|episode| Some(episode.uuid()) == act.parent_uuid())?;
+                Some(ActWindow {
+                    season: EpisodeAndAct::from_numbers(
+                        episode_number(episode.display_name())?,
+                        act_number(act.display_name())?,
+                    ),
+                    id: act.uuid().to_string(),
+                    start: act.start_time(),
+                    end: act.end_time(),
+                })
+            })
+            .collect::<Vec<_>>();
+        acts.sort_by_key(|act| act.start);
+        ActCalendar { acts }
+    }
+
+    pub fn acts(&self) -> &[ActWindow] {
+        &self.acts
+    }
+
+    pub fn current_act(&self) -> Option<&ActWindow> {
+        self.act_at(Utc::now())
+    }
+
+    pub fn act_at(&self, at: DateTime<Utc>) -> Option<&ActWindow> {
+        self.acts.iter().find(|act| act.contains(at))
+    }
+
+    pub fn window(&self, season: &EpisodeAndAct) -> Option<&ActWindow> {
+        self.acts.iter().find(|act| &act.season == season)
+    }
+}
+
+#[cfg(feature = "client")]
+impl AssetClient<'_> {
+    pub async fn act_calendar(&self) -> Result<ActCalendar, ValorantError> {
+        let seasons = self.request::<Vec<Season>>(AssetApiType::Seasons).await?;
+        Ok(ActCalendar::from_seasons(&seasons))
+    }
+}
+
+impl crate::ValorantAPIData for ActCalendar {}
+
+pub(crate) fn episode_number(name: &str) -> Option<u32> {
+    name.strip_prefix("EPISODE ")?.trim().parse().ok()
+}
+
+pub(crate) fn act_number(name: &str) -> Option<u32> {
+    match name.strip_prefix("ACT ")?.trim() {
+        "I" => Some(1),
+        "II" => Some(2),
+        "III" => Some(3),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::fixtures::{decode, SEASONS};
+
+    fn calendar() -> ActCalendar {
+        ActCalendar::from_seasons(&decode::<Vec<Season>>(SEASONS))
+    }
+
+    #[test]
+    fn builds_acts_in_order() {
+        let calendar = calendar();
+        let seasons = calendar
+            .acts()
+            .iter()
+            .map(|act| act.season.to_value())
+            .collect::<Vec<_>>();
+        assert_eq!(seasons, ["e5a2", "e5a3", "e6a1"]);
+    }
+
+    #[test]
+    fn act_at() {
+        let calendar = calendar();
+        let at = "2023-02-14T21:00:00Z".parse().unwrap();
+        assert_eq!(calendar.act_at(at).unwrap().season.to_value(), "e6a1");
+
+        let at = "2022-11-01T00:00:00Z".parse().unwrap();
+        assert_eq!(calendar.act_at(at).unwrap().season.to_value(), "e5a3");
+
+        let before = "2020-01-01T00:00:00Z".parse().unwrap();
+        assert!(calendar.act_at(before).is_none());
+    }
+
+    #[test]
+    fn window() {
+        let calendar = calendar();
+        let window = calendar.window(&EpisodeAndAct::from_numbers(5, 3)).unwrap();
+        assert_eq!(
+            window.start,
+            "2022-10-18T00:00:00Z".parse::<DateTime<Utc>>().unwrap()
+        );
+        assert!(calendar
+            .window(&EpisodeAndAct::from_numbers(1, 1))
+            .is_none());
+    }
+}
@@ -0,0 +1,229 @@
+//! Failures injected into the client's own requests, for testing how services built on it
+//! degrade.
+//!
+//! A [`FailureInjector`] attached with
+//! [`ValorantClientBuilder::failure_injector`](crate::ValorantClientBuilder::failure_injector)
+//! turns requests into timeouts, truncated bodies or server errors at the given rates, for all
+//! endpoints or per endpoint. Injected failures go through the same paths real ones do: retry
+//! policies, endpoint statistics and telemetry all see them. Faults are drawn from a seeded
+//! generator, so a failing run can be replayed. Apps get this module with the `test-support`
+//! feature.
+
+use crate::fake::FakeRng;
+use crate::ValorantClient;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// How often each kind of failure is injected, as probabilities between 0 and 1. A request
+/// gets at most one fault, so the rates add up.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct FaultRates {
+    timeout: f64,
+    partial_body: f64,
+    server_error: f64,
+}
+
+impl FaultRates {
+    /// No failures at all, e.g. to spare one endpoint.
+    pub fn new() -> Self {
+        FaultRates::default()
+    }
+
+    /// Requests that time out before an answer arrives.
+    pub fn timeouts(mut self, probability: f64) -> Self {
+        self.timeout = probability.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Answers whose body is cut off halfway, so they fail to decode.
+    pub fn partial_bodies(mut self, probability: f64) -> Self {
+        self.partial_body = probability.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Answers of 500, 502, 503 or 504 instead of the API's, without sending the request.
+    pub fn server_errors(mut self, probability: f64) -> Self {
+        self.server_error = probability.clamp(0.0, 1.0);
+        self
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Fault {
+    Timeout,
+    PartialBody,
+    ServerError(u16),
+}
+
+#[derive(Debug)]
+pub struct FailureInjector {
+    rng: Mutex<FakeRng>,
+    rates: FaultRates,
+    endpoints: HashMap<String, FaultRates>,
+}
+
+impl FailureInjector {
+    /// Injects no failures until rates are set. The same seed injects the same faults into the
+    /// same sequence of requests.
+    pub fn new(seed: u64) -> Self {
+        FailureInjector {
+            rng: Mutex::new(FakeRng::seeded(seed)),
+            rates: FaultRates::default(),
+            endpoints: HashMap::new(),
+        }
+    }
+
+    /// The rates of every endpoint without rates of its own.
+    pub fn rates(mut self, rates: FaultRates) -> Self {
+        self.rates = rates;
+        self
+    }
+
+    /// The rates of one endpoint, named like [`EndpointStats`](crate::stats::EndpointStats)
+    /// name them, e.g. `v2/mmr` or `v1/by-puuid/account`.
+    pub fn endpoint(mut self, endpoint: &str, rates: FaultRates) -> Self {
+        self.endpoints.insert(endpoint.to_string(), rates);
+        self
+    }
+
+    /// The fault to inject into the next request to `endpoint`, if any.
+    pub fn draw(&self, endpoint: &str) -> Option<Fault> {
+        let rates = self.endpoints.get(endpoint).unwrap_or(&self.rates);
+        let mut rng = self.rng.lock().unwrap();
+        let roll = rng.next_u64() as f64 / u64::MAX as f64;
+        if roll < rates.timeout {
+            Some(Fault::Timeout)
+        } else if roll < rates.timeout + rates.partial_body {
+            Some(Fault::PartialBody)
+        } else if roll < rates.timeout + rates.partial_body + rates.server_error {
+            Some(Fault::ServerError(*rng.pick(&[500, 502, 503, 504])))
+        } else {
+            None
+        }
+    }
+}
+
+impl ValorantClient<'_> {
+    pub(crate) async fn execute_with_faults(
+        &self,
+        endpoint: &str,
+        mut request: reqwest::Request,
+    ) -> Result<reqwest::Response, reqwest::Error> {
+        let fault = self
+            .failure_injector
+            .as_ref()
+            .and_then(|injector| injector.draw(endpoint));
+        match fault {
+            None => self.http.execute(request).await,
+            // A deadline that has passed by the time the request is polled fails it with a
+            // genuine timeout error
+            Some(Fault::Timeout) => {
+                *request.timeout_mut() = Some(Duration::from_nanos(1));
+                self.http.execute(request).await
+            }
+            Some(Fault::PartialBody) => {
+                let response = self.http.execute(request).await?;
+                let (status, mut headers) = (response.status(), response.headers().clone());
+                let body = response.bytes().await?;
+                headers.remove(reqwest::header::CONTENT_LENGTH);
+                let mut partial = http::Response::new(body[..body.len() / 2].to_vec());
+                *partial.status_mut() = status;
+                *partial.headers_mut() = headers;
+                Ok(partial.into())
+            }
+            Some(Fault::ServerError(status)) => {
+                let body = format!(
+                    r#"{{"status":{status},"errors":[{{"message":"Injected failure","code":0,"details":"null"}}]}}"#
+                );
+                let status = reqwest::StatusCode::from_u16(status).unwrap_or_default();
+                Ok(crate::replay_response(status, body.into_bytes()))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::fixtures::{ACCOUNT, MMR};
+    use crate::mock_server::{MockResponse, MockServer};
+    use crate::prelude::*;
+
+    #[test]
+    fn faults_follow_the_seed_and_rates() {
+        let draws = |seed| {
+            let injector = FailureInjector::new(seed)
+                .rates(FaultRates::new().timeouts(0.2).server_errors(0.3))
+                .endpoint("v1/account", FaultRates::new());
+            let faults: Vec<_> = (0..200).map(|_| injector.draw("v2/mmr")).collect();
+            assert_eq!(injector.draw("v1/account"), None);
+            faults
+        };
+        let faults = draws(7);
+        assert_eq!(faults, draws(7));
+        let timeouts = faults
+            .iter()
+            .filter(|fault| **fault == Some(Fault::Timeout));
+        let errors = faults
+            .iter()
+            .filter(|fault| matches!(fault, Some(Fault::ServerError(500..=504))));
+        assert!((20..60).contains(&timeouts.count()));
+        assert!((35..85).contains(&errors.count()));
+        assert!(!faults.contains(&Some(Fault::PartialBody)));
+    }
+
+    #[tokio::test]
+    async fn injected_faults_reach_the_caller() {
+        let server = MockServer::routes(vec![
+            ("/eu/Anonymous/0000", MockResponse::json(200, MMR)),
+            ("/Anonymous/0000", MockResponse::json(200, ACCOUNT)),
+        ])
+        .await;
+        let url = server.url();
+        let client = |rates| {
+            ValorantClient::builder()
+                .api_endpoint(&url)
+                .failure_injector(FailureInjector::new(1).endpoint("v1/account", rates))
+                .build()
+                .unwrap()
+        };
+        let account = || ValorantApiType::AccountData {
+            name: "Anonymous",
+            tag: "0000",
+        };
+
+        let error = client(FaultRates::new().timeouts(1.0))
+            .request::<AccountData>(account())
+            .await
+            .unwrap_err();
+        assert!(matches!(error, ValorantError::Transport(error) if error.is_timeout()));
+
+        let error = client(FaultRates::new().partial_bodies(1.0))
+            .request::<AccountData>(account())
+            .await
+            .unwrap_err();
+        assert!(matches!(error, ValorantError::Decode(_)));
+
+        let unavailable = client(FaultRates::new().server_errors(1.0));
+        let sent = server.requests().len();
+        let error = unavailable
+            .request::<AccountData>(account())
+            .await
+            .unwrap_err();
+        assert!(error.is_retryable());
+        assert_eq!(server.requests().len(), sent);
+
+        // Endpoints without rates of their own are left alone
+        unavailable
+            .request::<MMRData>(ValorantApiType::MMRData {
+                region: AccountRegion::EU,
+                name: "Anonymous",
+                tag: "0000",
+                filter: None,
+            })
+            .await
+            .unwrap();
+    }
+}
@@ -0,0 +1,272 @@
+//! A player's recent ranked games with their rank and RR before and after each one.
+//!
+//! The API passes this payload through from Riot's `competitiveupdates` endpoint, so it keeps
+//! Riot's field names and comes without the usual `status`/`data` envelope. It covers the last
+//! 20 games and is much cheaper than fetching full match details just to track a rank.
+
+#[cfg(feature = "client")]
+use crate::error::{self, ValorantError};
+use crate::ids::{MatchId, Puuid};
+use crate::mmr_history::RankMovement;
+use crate::sanity::{self, SanityWarning};
+use crate::ValorantAPIData;
+#[cfg(feature = "client")]
+use crate::{AccountRegion, ApiResponse, ValorantClient};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "PascalCase")]
+pub struct CompetitiveUpdates {
+    subject: Puuid,
+    matches: Vec<CompetitiveUpdate>,
+}
+
+impl CompetitiveUpdates {
+    pub fn puuid(&self) -> &Puuid {
+        &self.subject
+    }
+
+    /// Newest first.
+    pub fn matches(&self) -> &[CompetitiveUpdate] {
+        &self.matches
+    }
+}
+
+impl ValorantAPIData for CompetitiveUpdates {
+    fn sanity_warnings(&self) -> Vec<SanityWarning> {
+        sanity::check_all(&self.matches)
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "PascalCase")]
+pub struct CompetitiveUpdate {
+    #[serde(rename = "MatchID")]
+    match_id: MatchId,
+    /// The map's asset path, e.g. `/Game/Maps/Ascent/Ascent`.
+    #[serde(rename = "MapID")]
+    map_id: String,
+    #[serde(rename = "SeasonID")]
+    season_id: String,
+    /// In milliseconds.
+    match_start_time: i64,
+    tier_before_update: u32,
+    tier_after_update: u32,
+    ranked_rating_before_update: u32,
+    ranked_rating_after_update: u32,
+    ranked_rating_earned: i32,
+    #[serde(default)]
+    ranked_rating_performance_bonus: i32,
+    competitive_movement: String,
+    #[serde(rename = "AFKPenalty", default)]
+    afk_penalty: i32,
+}
+
+impl CompetitiveUpdate {
+    pub fn match_id(&self) -> &MatchId {
+        &self.match_id
+    }
+
+    pub fn map_id(&self) -> &str {
+        &self.map_id
+    }
+
+    pub fn season_id(&self) -> &str {
+        &self.season_id
+    }
+
+    pub fn started_at(&self) -> DateTime<Utc> {
+        DateTime::from_timestamp_millis(self.match_start_time).unwrap_or_default()
+    }
+
+    pub fn tier_before(&self) -> u32 {
+        self.tier_before_update
+    }
+
+    pub fn tier_after(&self) -> u32 {
+        self.tier_after_update
+    }
+
+    pub fn rr_before(&self) -> u32 {
+        self.ranked_rating_before_update
+    }
+
+    pub fn rr_after(&self) -> u32 {
+        self.ranked_rating_after_update
+    }
+
+    /// Including the performance bonus and AFK penalty.
+    pub fn rr_earned(&self) -> i32 {
+        self.ranked_rating_earned
+    }
+
+    pub fn performance_bonus(&self) -> i32 {
+        self.ranked_rating_performance_bonus
+    }
+
+    pub fn afk_penalty(&self) -> i32 {
+        self.afk_penalty
+    }
+
+    /// Riot's own label, which is `MOVEMENT_UNKNOWN` since the RR system replaced it. Prefer
+    /// [`CompetitiveUpdate::rank_movement`].
+    pub fn competitive_movement(&self) -> &str {
+        &self.competitive_movement
+    }
+
+    /// Whether the game was ranked at all; unrated games are listed with tier 0 throughout.
+    pub fn is_ranked(&self) -> bool {
+        self.tier_before_update != 0 || self.tier_after_update != 0
+    }
+
+    /// Derived from the tiers, which unlike an RR history need no earlier entry to compare to.
+    pub fn rank_movement(&self) -> RankMovement {
+        let before = self.ranked_rating_before_update as i64;
+        if self.tier_after_update > self.tier_before_update {
+            RankMovement::Promoted
+        } else if self.tier_after_update < self.tier_before_update {
+            RankMovement::Demoted
+        } else if self.ranked_rating_earned < 0
+            && self.ranked_rating_after_update == 0
+            && before + (self.ranked_rating_earned as i64) < 0
+        {
+            RankMovement::Shielded
+        } else {
+            RankMovement::Stayed
+        }
+    }
+}
+
+impl ValorantAPIData for CompetitiveUpdate {
+    fn sanity_warnings(&self) -> Vec<SanityWarning> {
+        let mut warnings = Vec::new();
+        sanity::check_rank(
+            self.tier_after_update,
+            self.ranked_rating_after_update,
+            None,
+            &mut warnings,
+        );
+        warnings
+    }
+}
+
+#[cfg(feature = "client")]
+#[derive(Serialize)]
+struct RawRequest<'a> {
+    #[serde(rename = "type")]
+    kind: &'a str,
+    value: &'a str,
+    region: &'a str,
+    queries: &'a str,
+}
+
+#[cfg(feature = "client")]
+impl ValorantClient<'_> {
+    /// Fetches a player's last 20 competitive games through the API's raw passthrough. The
+    /// passthrough answers failures in the API's usual envelope, which become a
+    /// [`ValorantError`] like on every other endpoint.
+    pub async fn competitive_updates(
+        &self,
+        region: AccountRegion,
+        puuid: &Puuid,
+    ) -> Result<CompetitiveUpdates, ValorantError> {
+        self.wait_for_budget().await;
+        let request = self.post("v1/raw").json(&RawRequest {
+            kind: "competitiveupdates",
+            value: puuid.as_str(),
+            region: region.shard(),
+            queries: "?queue=competitive",
+        });
+        let response = self.send_timed("v1/raw", request).await?;
+        let status = response.status();
+        if !status.is_success() {
+            return error::into_data(response.json::<ApiResponse<CompetitiveUpdates>>().await?);
+        }
+        Ok(response.json().await?)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    #[cfg(feature = "client")]
+    use crate::fixtures::{COMPETITIVE_UPDATES, NOT_FOUND};
+    #[cfg(feature = "client")]
+    use crate::mock_server::{MockResponse, MockServer};
+    #[cfg(feature = "client")]
+    use crate::retry::RetryHint;
+
+    fn update(tiers: (u32, u32), rr: (u32, u32), earned: i32) -> CompetitiveUpdate {
+        serde_json::from_value(serde_json::json!({
+            "MatchID": "0c6bd2a0-2c1f-4b87-9b1e-6f3f5d2a7e11",
+            "MapID": "/Game/Maps/Ascent/Ascent",
+            "SeasonID": "4c4b8cff-43eb-13d3-8f14-96b783c90cd2",
+            "MatchStartTime": 1676748001000i64,
+            "TierBeforeUpdate": tiers.0,
+            "TierAfterUpdate": tiers.1,
+            "RankedRatingBeforeUpdate": rr.0,
+            "RankedRatingAfterUpdate": rr.1,
+            "RankedRatingEarned": earned,
+            "CompetitiveMovement": "MOVEMENT_UNKNOWN",
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn movements_follow_the_tiers() {
+        assert_eq!(
+            update((15, 16), (86, 9), 23).rank_movement(),
+            RankMovement::Promoted
+        );
+        assert_eq!(
+            update((16, 15), (5, 85), -20).rank_movement(),
+            RankMovement::Demoted
+        );
+        assert_eq!(
+            update((16, 16), (8, 0), -17).rank_movement(),
+            RankMovement::Shielded
+        );
+        assert_eq!(
+            update((16, 16), (58, 47), -11).rank_movement(),
+            RankMovement::Stayed
+        );
+        assert!(!update((0, 0), (0, 0), 0).is_ranked());
+    }
+
+    #[cfg(feature = "client")]
+    #[tokio::test]
+    async fn fetches_through_the_raw_endpoint() {
+        let server = MockServer::routes(vec![(
+            "/v1/raw",
+            MockResponse::json(200, COMPETITIVE_UPDATES),
+        )])
+        .await;
+        let url = server.url();
+        let client = ValorantClient::new().change_api_endpoint(&url);
+        let puuid: Puuid = "b44adaae-ab83-5001-a296-89ea0de0bce3".parse().unwrap();
+
+        let data = client
+            .competitive_updates(AccountRegion::EU, &puuid)
+            .await
+            .unwrap();
+        assert_eq!(data.puuid(), &puuid);
+        assert_eq!(data.matches().len(), 2);
+        assert_eq!(data.matches()[0].rr_earned(), -11);
+        assert_eq!(data.matches()[1].rank_movement(), RankMovement::Promoted);
+        assert_eq!(
+            data.matches()[0].started_at().timestamp_millis(),
+            1_676_748_001_000
+        );
+        assert_eq!(data.sanity_warnings(), []);
+
+        let server = MockServer::start(vec![MockResponse::json(404, NOT_FOUND)]).await;
+        let url = server.url();
+        let client = ValorantClient::new().change_api_endpoint(&url);
+        let error = client
+            .competitive_updates(AccountRegion::EU, &puuid)
+            .await
+            .unwrap_err();
+        assert!(error.is_not_found());
+    }
+}
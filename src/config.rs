@@ -0,0 +1,320 @@
+//! Named client configurations, e.g. `dev`, `staging` and `prod`, kept in one TOML file.
+//!
+//! Each top-level table of the file is a profile. Every key is optional:
+//!
+//! ```toml
+//! [dev]
+//! base_url = "http://localhost:8080/valorant"
+//! api_key_env = "DEV_HENRIKDEV_KEY"
+//!
+//! [prod]
+//! api_key_env = "HENRIKDEV_API_KEY"
+//! player_cooldown_secs = 60
+//! max_attempts = 3
+//! throttle_on_rate_limit_headers = true
+//! rate_limit = { requests = 30, per_secs = 60 }
+//! ```
+//!
+//! Keys are better kept out of the file: `api_key_env` names the environment variable to read
+//! the key from, while `api_key` takes the key itself. Unknown keys are rejected, so a typo
+//! doesn't silently fall back to a default.
+
+use crate::rate_limit::RateBudget;
+use crate::retry::RetryPolicy;
+use crate::ValorantClientBuilder;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::Path;
+use std::time::Duration;
+
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum ConfigError {
+    Io(std::io::Error),
+    /// The file isn't valid TOML.
+    Parse(String),
+    UnknownProfile(String),
+    UnknownKey {
+        profile: String,
+        key: String,
+    },
+    InvalidValue {
+        profile: String,
+        key: String,
+        expected: &'static str,
+    },
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigError::Io(error) => write!(f, "Failed to read the profiles: {error}"),
+            ConfigError::Parse(message) => write!(f, "Invalid profiles file: {message}"),
+            ConfigError::UnknownProfile(name) => write!(f, "No profile named {name:?}"),
+            ConfigError::UnknownKey { profile, key } => {
+                write!(f, "Unknown key {key} in profile {profile:?}")
+            }
+            ConfigError::InvalidValue {
+                profile,
+                key,
+                expected,
+            } => write!(
+                f,
+                "Invalid {key} in profile {profile:?}, expected {expected}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ConfigError::Io(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for ConfigError {
+    fn from(error: std::io::Error) -> Self {
+        ConfigError::Io(error)
+    }
+}
+
+/// One environment's settings, applied on top of the client's defaults.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ConfigProfile {
+    base_url: Option<String>,
+    api_key: Option<String>,
+    api_key_env: Option<String>,
+    player_cooldown: Option<Duration>,
+    max_attempts: Option<u32>,
+    throttle_on_rate_limit_headers: Option<bool>,
+    rate_limit: Option<(u32, Duration)>,
+}
+
+impl ConfigProfile {
+    pub fn base_url(&self) -> Option<&str> {
+        self.base_url.as_deref()
+    }
+
+    pub fn player_cooldown(&self) -> Option<Duration> {
+        self.player_cooldown
+    }
+
+    /// Requests allowed per period, if the profile budgets them.
+    pub fn rate_limit(&self) -> Option<(u32, Duration)> {
+        self.rate_limit
+    }
+
+    /// The key to use: `api_key` if set, else the variable `api_key_env` names. `None` leaves
+    /// the client to read [`API_KEY_VAR`](crate::API_KEY_VAR) as usual.
+    pub fn api_key(&self) -> Option<String> {
+        self.api_key.clone().or_else(|| {
+            let key = std::env::var(self.api_key_env.as_ref()?).ok()?;
+            let key = key.trim();
+            (!key.is_empty()).then(|| key.to_string())
+        })
+    }
+
+    /// A client builder with the profile's settings, which can be adjusted further.
+    pub fn builder(&self) -> ValorantClientBuilder<'_> {
+        let mut builder = ValorantClientBuilder::new();
+        if let Some(url) = &self.base_url {
+            builder = builder.api_endpoint(url);
+        }
+        if let Some(key) = self.api_key() {
+            builder = builder.api_key(key);
+        }
+        if let Some(cooldown) = self.player_cooldown {
+            builder = builder.player_cooldown(cooldown);
+        }
+        if let Some(attempts) = self.max_attempts {
+            builder = builder.retry_policy(RetryPolicy::new(attempts));
+        }
+        if let Some(throttle) = self.throttle_on_rate_limit_headers {
+            builder = builder.throttle_on_rate_limit_headers(throttle);
+        }
+        if let Some((requests, period)) = self.rate_limit {
+            builder = builder.rate_budget(RateBudget::new(requests, period));
+        }
+        builder
+    }
+
+    fn parse(name: &str, table: &dyn toml_edit::TableLike) -> Result<Self, ConfigError> {
+        let invalid = |key: &str, expected| ConfigError::InvalidValue {
+            profile: name.to_string(),
+            key: key.to_string(),
+            expected,
+        };
+        let string = |key: &str, item: &toml_edit::Item| {
+            item.as_str()
+                .map(str::to_string)
+                .ok_or_else(|| invalid(key, "a string"))
+        };
+        let positive = |key: &str, item: &toml_edit::Item| {
+            item.as_integer()
+                .and_then(|value| u32::try_from(value).ok())
+                .filter(|value| *value > 0)
+                .ok_or_else(|| invalid(key, "a positive integer"))
+        };
+
+        let mut profile = ConfigProfile::default();
+        for (key, item) in table.iter() {
+            match key {
+                "base_url" => profile.base_url = Some(string(key, item)?),
+                "api_key" => profile.api_key = Some(string(key, item)?),
+                "api_key_env" => profile.api_key_env = Some(string(key, item)?),
+                "player_cooldown_secs" => {
+                    let seconds = positive(key, item)?;
+                    profile.player_cooldown = Some(Duration::from_secs(seconds.into()));
+                }
+                "max_attempts" => profile.max_attempts = Some(positive(key, item)?),
+                "throttle_on_rate_limit_headers" => {
+                    let throttle = item.as_bool().ok_or_else(|| invalid(key, "a boolean"))?;
+                    profile.throttle_on_rate_limit_headers = Some(throttle);
+                }
+                "rate_limit" => {
+                    let expected = "a table of requests and per_secs";
+                    let limit = item.as_table_like().ok_or_else(|| invalid(key, expected))?;
+                    let field = |field| {
+                        limit
+                            .get(field)
+                            .and_then(|item| positive(key, item).ok())
+                            .ok_or_else(|| invalid(key, expected))
+                    };
+                    let period = Duration::from_secs(field("per_secs")?.into());
+                    profile.rate_limit = Some((field("requests")?, period));
+                }
+                _ => {
+                    return Err(ConfigError::UnknownKey {
+                        profile: name.to_string(),
+                        key: key.to_string(),
+                    })
+                }
+            }
+        }
+        Ok(profile)
+    }
+}
+
+/// The profiles of a configuration file, by name.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Profiles {
+    profiles: BTreeMap<String, ConfigProfile>,
+}
+
+impl Profiles {
+    pub fn from_toml(text: &str) -> Result<Self, ConfigError> {
+        let document = text
+            .parse::<toml_edit::Document<String>>()
+            .map_err(|error| ConfigError::Parse(error.to_string()))?;
+        let mut profiles = BTreeMap::new();
+        for (name, item) in document.as_table().iter() {
+            let table = item
+                .as_table_like()
+                .ok_or_else(|| ConfigError::InvalidValue {
+                    profile: name.to_string(),
+                    key: name.to_string(),
+                    expected: "a table of settings",
+                })?;
+            profiles.insert(name.to_string(), ConfigProfile::parse(name, table)?);
+        }
+        Ok(Profiles { profiles })
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, ConfigError> {
+        Profiles::from_toml(&std::fs::read_to_string(path)?)
+    }
+
+    pub fn get(&self, name: &str) -> Result<&ConfigProfile, ConfigError> {
+        self.profiles
+            .get(name)
+            .ok_or_else(|| ConfigError::UnknownProfile(name.to_string()))
+    }
+
+    /// The profile names, in alphabetical order.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.profiles.keys().map(String::as_str)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::fixtures::ACCOUNT;
+    use crate::mock_server::{MockResponse, MockServer};
+    use crate::prelude::*;
+
+    const PROFILES: &str = r#"
+        [dev]
+        base_url = "http://localhost:8080/valorant"
+        api_key = "dev-key"
+
+        [prod]
+        api_key_env = "VALORUST_TEST_PROD_KEY"
+        player_cooldown_secs = 60
+        max_attempts = 3
+        rate_limit = { requests = 30, per_secs = 60 }
+    "#;
+
+    #[test]
+    fn profiles_are_read_by_name() {
+        let profiles = Profiles::from_toml(PROFILES).unwrap();
+        assert_eq!(profiles.names().collect::<Vec<_>>(), ["dev", "prod"]);
+
+        let dev = profiles.get("dev").unwrap();
+        assert_eq!(dev.base_url(), Some("http://localhost:8080/valorant"));
+        assert_eq!(dev.api_key().as_deref(), Some("dev-key"));
+        assert_eq!(dev.player_cooldown(), None);
+
+        let prod = profiles.get("prod").unwrap();
+        assert_eq!(prod.base_url(), None);
+        assert_eq!(prod.player_cooldown(), Some(Duration::from_secs(60)));
+        assert_eq!(prod.rate_limit(), Some((30, Duration::from_secs(60))));
+        assert!(matches!(
+            profiles.get("staging"),
+            Err(ConfigError::UnknownProfile(name)) if name == "staging"
+        ));
+    }
+
+    #[test]
+    fn mistakes_are_reported() {
+        let error = Profiles::from_toml("[dev]\nbase_urll = \"x\"").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Unknown key base_urll in profile \"dev\""
+        );
+        let error = Profiles::from_toml("[dev]\nmax_attempts = -1").unwrap_err();
+        assert!(matches!(error, ConfigError::InvalidValue { key, .. } if key == "max_attempts"));
+        let error = Profiles::from_toml("[dev]\nrate_limit = { requests = 30 }").unwrap_err();
+        assert!(matches!(error, ConfigError::InvalidValue { key, .. } if key == "rate_limit"));
+        assert!(matches!(
+            Profiles::from_toml("[dev"),
+            Err(ConfigError::Parse(_))
+        ));
+        assert!(matches!(
+            Profiles::load("/nonexistent/profiles.toml"),
+            Err(ConfigError::Io(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn clients_are_built_from_a_profile() {
+        let server = MockServer::start(vec![MockResponse::json(200, ACCOUNT)]).await;
+        let profiles = Profiles::from_toml(&format!(
+            "[local]\nbase_url = \"{}\"\napi_key = \"local\"",
+            server.url()
+        ))
+        .unwrap();
+        let client = profiles.get("local").unwrap().builder().build().unwrap();
+        client
+            .request::<AccountData>(ValorantApiType::AccountData {
+                name: "Anonymous",
+                tag: "0000",
+            })
+            .await
+            .unwrap();
+        assert_eq!(server.requests()[0].header("authorization"), Some("local"));
+    }
+}
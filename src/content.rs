@@ -0,0 +1,259 @@
+//! The game's content: agents, maps, skins and acts, for naming the IDs matches and stores
+//! report.
+//!
+//! Requested with a locale, e.g. `de-DE`, names come in that language. Without one the API
+//! sends every translation along, which [`ContentItem::localized_name`] picks from.
+
+use crate::calendar::{act_number, episode_number};
+use crate::mmr_data::EpisodeAndAct;
+use crate::ValorantAPIData;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Content {
+    version: String,
+    #[serde(default)]
+    characters: Vec<ContentItem>,
+    acts: Vec<ContentAct>,
+    #[serde(default)]
+    maps: Vec<ContentMap>,
+    #[serde(default)]
+    skins: Vec<ContentItem>,
+}
+
+/// An agent, skin or other item of the game.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ContentItem {
+    name: String,
+    id: String,
+    #[serde(default)]
+    asset_name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    localized_names: Option<BTreeMap<String, String>>,
+}
+
+impl ContentItem {
+    /// The name in the requested locale, or in English without one.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// The internal name, e.g. `Wushu_PrimaryAsset` for Jett.
+    pub fn asset_name(&self) -> &str {
+        &self.asset_name
+    }
+
+    /// The name in `locale`, e.g. `ja-JP`, out of the translations sent along when the
+    /// content was requested without a locale. Falls back to [`name`](Self::name).
+    pub fn localized_name(&self, locale: &str) -> &str {
+        self.localized_names
+            .as_ref()
+            .and_then(|names| names.get(locale))
+            .unwrap_or(&self.name)
+    }
+}
+
+/// An episode or act. Acts point at their episode through `parent_id`.
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ContentAct {
+    id: String,
+    #[serde(default)]
+    parent_id: String,
+    #[serde(rename = "type")]
+    act_type: String,
+    name: String,
+    is_active: bool,
+}
+
+impl ContentAct {
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// The episode's name, e.g. `EPISODE 6`, or the act's, e.g. `ACT I`.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn is_episode(&self) -> bool {
+        self.act_type == "episode"
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.is_active
+    }
+}
+
+/// Every map in the game, including the Range and the team deathmatch maps.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ContentMap {
+    name: String,
+    id: String,
+    asset_name: String,
+    asset_path: String,
+}
+
+impl ContentMap {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// The path matches report their map by, e.g. `/Game/Maps/Ascent/Ascent`.
+    pub fn asset_path(&self) -> &str {
+        &self.asset_path
+    }
+
+    /// Whether the map is a full spike map. Those live at `/Game/Maps/<Code>/<Code>`, while the
+    /// Range and the deathmatch and skirmish maps sit elsewhere.
+    pub fn is_standard(&self) -> bool {
+        let mut segments = self.asset_path.trim_start_matches("/Game/Maps/").split('/');
+        match (segments.next(), segments.next(), segments.next()) {
+            (Some(folder), Some(map), None) => {
+                self.asset_path.starts_with("/Game/Maps/") && folder == map
+            }
+            _ => false,
+        }
+    }
+}
+
+impl Content {
+    pub fn version(&self) -> &str {
+        &self.version
+    }
+
+    /// Every agent, including ones not released yet.
+    pub fn characters(&self) -> &[ContentItem] {
+        &self.characters
+    }
+
+    pub fn maps(&self) -> &[ContentMap] {
+        &self.maps
+    }
+
+    pub fn skins(&self) -> &[ContentItem] {
+        &self.skins
+    }
+
+    /// Episodes and acts, in the API's order.
+    pub fn acts(&self) -> &[ContentAct] {
+        &self.acts
+    }
+
+    /// The name of the agent, map, skin or act with `id`. Content IDs are uppercase while
+    /// matches report them in lowercase, so case is ignored.
+    pub fn name_of(&self, id: &str) -> Option<&str> {
+        let items = self.characters.iter().chain(&self.skins);
+        items
+            .map(|item| (item.id.as_str(), item.name.as_str()))
+            .chain(
+                self.maps
+                    .iter()
+                    .map(|map| (map.id.as_str(), map.name.as_str())),
+            )
+            .chain(
+                self.acts
+                    .iter()
+                    .map(|act| (act.id.as_str(), act.name.as_str())),
+            )
+            .find(|(item, _)| item.eq_ignore_ascii_case(id))
+            .map(|(_, name)| name)
+    }
+
+    /// Every act the content endpoint knows about, oldest episode first.
+    pub fn seasons(&self) -> Vec<EpisodeAndAct> {
+        let mut seasons = self
+            .acts
+            .iter()
+            .filter(|act| act.act_type == "act")
+            .filter_map(|act| self.season_of(act))
+            .collect::<Vec<_>>();
+        seasons.sort();
+        seasons.dedup();
+        seasons
+    }
+
+    /// The act that is live right now, according to the `isActive` flags.
+    pub fn active_act(&self) -> Option<EpisodeAndAct> {
+        let act = self
+            .acts
+            .iter()
+            .find(|act| act.is_active && act.act_type == "act")?;
+        self.season_of(act)
+    }
+
+    fn season_of(&self, act: &ContentAct) -> Option<EpisodeAndAct> {
+        let episode = self
+            .acts
+            .iter()
+            .find(|episode| episode.id == act.parent_id && episode.act_type == "episode")?;
+        Some(EpisodeAndAct::from_numbers(
+            episode_number(&episode.name)?,
+            act_number(&act.name)?,
+        ))
+    }
+}
+
+impl ValorantAPIData for Content {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::fixtures::{decode, CONTENT};
+
+    #[test]
+    fn active_act() {
+        let data: Content = decode(CONTENT);
+        assert_eq!(data.active_act(), Some(EpisodeAndAct::from_numbers(6, 1)));
+    }
+
+    #[test]
+    fn ids_resolve_to_names() {
+        let data: Content = decode(CONTENT);
+        assert_eq!(
+            data.name_of("add6443a-41bd-e414-f6ad-e58d267f4e95"),
+            Some("Jett")
+        );
+        assert_eq!(
+            data.name_of("7EAECC1B-4337-BBF6-6AB9-04B8F06B3319"),
+            Some("Ascent")
+        );
+        assert_eq!(
+            data.name_of("5F0AAF7A-4289-3998-D5FF-EB9A5CF7EF5C"),
+            Some("Prime Vandal")
+        );
+        assert_eq!(data.name_of("unknown"), None);
+
+        let jett = &data.characters()[0];
+        assert_eq!(jett.localized_name("ja-JP"), "ジェット");
+        assert_eq!(data.skins()[0].localized_name("pt-BR"), "Vandal Prime");
+        // Content requested in one locale carries no translations
+        assert_eq!(data.characters()[1].localized_name("ja-JP"), "Sova");
+        assert!(data
+            .acts()
+            .iter()
+            .any(|act| act.is_episode() && act.is_active()));
+    }
+
+    #[test]
+    fn seasons() {
+        let data: Content = decode(CONTENT);
+        let seasons = data
+            .seasons()
+            .iter()
+            .map(EpisodeAndAct::to_value)
+            .collect::<Vec<_>>();
+        assert_eq!(seasons, ["e5a2", "e5a3", "e6a1"]);
+    }
+}
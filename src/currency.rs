@@ -0,0 +1,96 @@
+//! Store currencies and price formatting.
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// A currency prices can be paid in. The store keys prices by these currencies' UUIDs.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Currency {
+    ValorantPoints,
+    RadianitePoints,
+    KingdomCredits,
+}
+
+impl Currency {
+    pub const ALL: [Currency; 3] = [
+        Currency::ValorantPoints,
+        Currency::RadianitePoints,
+        Currency::KingdomCredits,
+    ];
+
+    pub fn uuid(self) -> &'static str {
+        match self {
+            Currency::ValorantPoints => "85ad13f7-3d1b-5128-9eb2-7cd8ee0b5741",
+            Currency::RadianitePoints => "e59aa87c-4cbf-517a-5983-6e81511be9b7",
+            Currency::KingdomCredits => "85ca954a-41f2-ce94-9b45-8ca3dd39a00d",
+        }
+    }
+
+    pub fn from_uuid(uuid: &str) -> Option<Self> {
+        Currency::ALL
+            .into_iter()
+            .find(|currency| currency.uuid().eq_ignore_ascii_case(uuid))
+    }
+
+    /// The short name shown in game, e.g. `VP`.
+    pub fn symbol(self) -> &'static str {
+        match self {
+            Currency::ValorantPoints => "VP",
+            Currency::RadianitePoints => "RP",
+            Currency::KingdomCredits => "KC",
+        }
+    }
+
+    /// Formats an amount with thousands separators, e.g. `1,775 VP`.
+    pub fn format(self, amount: u32) -> String {
+        format!("{} {}", group_thousands(amount), self.symbol())
+    }
+}
+
+impl fmt::Display for Currency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Currency::ValorantPoints => "Valorant Points",
+            Currency::RadianitePoints => "Radianite Points",
+            Currency::KingdomCredits => "Kingdom Credits",
+        })
+    }
+}
+
+fn group_thousands(amount: u32) -> String {
+    let digits = amount.to_string();
+    let mut grouped = String::with_capacity(digits.len() + digits.len() / 3);
+    for (index, digit) in digits.chars().enumerate() {
+        if index > 0 && (digits.len() - index).is_multiple_of(3) {
+            grouped.push(',');
+        }
+        grouped.push(digit);
+    }
+    grouped
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn uuids_round_trip() {
+        for currency in Currency::ALL {
+            assert_eq!(Currency::from_uuid(currency.uuid()), Some(currency));
+        }
+        assert_eq!(
+            Currency::from_uuid("85AD13F7-3D1B-5128-9EB2-7CD8EE0B5741"),
+            Some(Currency::ValorantPoints)
+        );
+        assert_eq!(Currency::from_uuid("unknown"), None);
+    }
+
+    #[test]
+    fn format() {
+        assert_eq!(Currency::ValorantPoints.format(0), "0 VP");
+        assert_eq!(Currency::ValorantPoints.format(875), "875 VP");
+        assert_eq!(Currency::RadianitePoints.format(1775), "1,775 RP");
+        assert_eq!(Currency::KingdomCredits.format(1234567), "1,234,567 KC");
+    }
+}
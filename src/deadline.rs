@@ -0,0 +1,99 @@
+//! Time budgets for operations that make several requests.
+//!
+//! A per-request timeout can't bound an operation that makes many requests. A [`Deadline`]
+//! bounds the whole operation instead: each step gets an even share of the time that is left,
+//! and the remaining steps are skipped once the deadline passes. Operations that run out of time
+//! fail with [`ValorantError::DeadlineExceeded`](crate::error::ValorantError::DeadlineExceeded).
+
+use std::fmt;
+use std::future::Future;
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Deadline {
+    at: Instant,
+}
+
+impl Deadline {
+    pub fn after(budget: Duration) -> Self {
+        Deadline {
+            at: Instant::now() + budget,
+        }
+    }
+
+    pub fn at(at: Instant) -> Self {
+        Deadline { at }
+    }
+
+    pub fn remaining(&self) -> Duration {
+        self.at.saturating_duration_since(Instant::now())
+    }
+
+    pub fn is_expired(&self) -> bool {
+        self.remaining().is_zero()
+    }
+
+    /// The share of the remaining time for the next of `steps_left` steps. Steps that finish
+    /// early leave their unused time to the ones after them.
+    pub fn slice(&self, steps_left: u32) -> Duration {
+        self.remaining() / steps_left.max(1)
+    }
+
+    /// Runs the next of `steps_left` steps within its share of the remaining time.
+    pub async fn step<F: Future>(
+        &self,
+        steps_left: u32,
+        future: F,
+    ) -> Result<F::Output, DeadlineExceeded> {
+        if self.is_expired() {
+            return Err(DeadlineExceeded);
+        }
+        tokio::time::timeout(self.slice(steps_left), future)
+            .await
+            .map_err(|_| DeadlineExceeded)
+    }
+}
+
+// Runs a step under the deadline if there is one
+pub(crate) async fn step<F: Future>(
+    deadline: Option<&Deadline>,
+    steps_left: u32,
+    future: F,
+) -> Result<F::Output, DeadlineExceeded> {
+    match deadline {
+        Some(deadline) => deadline.step(steps_left, future).await,
+        None => Ok(future.await),
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DeadlineExceeded;
+
+impl fmt::Display for DeadlineExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("The operation's deadline passed")
+    }
+}
+
+impl std::error::Error for DeadlineExceeded {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[tokio::test]
+    async fn steps_share_the_remaining_time() {
+        let deadline = Deadline::after(Duration::from_millis(300));
+        assert!(deadline.slice(3) <= Duration::from_millis(100));
+
+        // The first step finishes early, so the last one gets everything that is left
+        deadline.step(2, async {}).await.unwrap();
+        let slow = tokio::time::sleep(Duration::from_millis(200));
+        assert!(deadline.step(1, slow).await.is_ok());
+
+        let slow = tokio::time::sleep(Duration::from_millis(200));
+        assert_eq!(deadline.step(1, slow).await, Err(DeadlineExceeded));
+        assert!(deadline.is_expired());
+        assert_eq!(deadline.step(1, async {}).await, Err(DeadlineExceeded));
+    }
+}
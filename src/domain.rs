@@ -0,0 +1,189 @@
+//! Stable types for application code, decoupled from the wire models.
+//!
+//! The wire models follow the API's payloads field for field and change whenever the upstream
+//! API does. The types here only change with this crate's major version, so code built on them
+//! keeps compiling when a payload is reshaped; only the conversions below are updated.
+
+use crate::account_data::AccountData;
+use crate::match_data::{MatchData, MatchMode, MatchPlayer, RoundSummary};
+use crate::mmr_data::MMRData;
+use crate::AccountRegion;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Player {
+    pub puuid: String,
+    pub name: String,
+    pub tag: String,
+    /// Only known from account lookups.
+    pub region: Option<AccountRegion>,
+    pub account_level: Option<u32>,
+}
+
+impl From<&AccountData> for Player {
+    fn from(account: &AccountData) -> Self {
+        Player {
+            puuid: account.puuid().to_string(),
+            name: account.name().to_string(),
+            tag: account.tag().to_string(),
+            region: Some(account.region()),
+            account_level: Some(account.account_level()),
+        }
+    }
+}
+
+impl From<&MMRData> for Player {
+    fn from(mmr: &MMRData) -> Self {
+        Player {
+            puuid: mmr.puuid().to_string(),
+            name: mmr.name().to_string(),
+            tag: mmr.tag().to_string(),
+            region: None,
+            account_level: None,
+        }
+    }
+}
+
+impl From<&MatchPlayer> for Player {
+    fn from(player: &MatchPlayer) -> Self {
+        Player {
+            puuid: player.puuid().to_string(),
+            name: player.name().to_string(),
+            tag: player.tag().to_string(),
+            region: None,
+            account_level: Some(player.level()),
+        }
+    }
+}
+
+/// A competitive rank, e.g. tier 16 for `Platinum 2`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Rank {
+    pub tier: u32,
+    pub name: String,
+    /// RR within the rank, when the source reports it.
+    pub rr: Option<u32>,
+}
+
+impl From<&MMRData> for Rank {
+    fn from(mmr: &MMRData) -> Self {
+        Rank {
+            tier: mmr.current_tier().id(),
+            name: mmr.current_tier_patched().to_string(),
+            rr: Some(mmr.ranking_in_tier()),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Match {
+    pub id: String,
+    pub map: String,
+    pub mode: MatchMode,
+    pub started_at: DateTime<Utc>,
+    pub participants: Vec<Participant>,
+    pub rounds: Vec<Round>,
+}
+
+impl From<&MatchData> for Match {
+    fn from(game: &MatchData) -> Self {
+        Match {
+            id: game.match_id().to_string(),
+            map: game.map().to_string(),
+            mode: game.mode().clone(),
+            started_at: game.started_at(),
+            participants: game.players().iter().map(Participant::from).collect(),
+            rounds: game
+                .rounds()
+                .iter()
+                .zip(1..)
+                .map(|(round, number)| Round::new(number, round))
+                .collect(),
+        }
+    }
+}
+
+/// A player's part in one match.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Participant {
+    pub player: Player,
+    pub team: String,
+    pub agent: String,
+    /// `None` in unranked modes.
+    pub rank: Option<Rank>,
+    pub score: u32,
+    pub kills: u32,
+    pub deaths: u32,
+    pub assists: u32,
+}
+
+impl From<&MatchPlayer> for Participant {
+    fn from(player: &MatchPlayer) -> Self {
+        let stats = player.stats();
+        Participant {
+            player: Player::from(player),
+            team: player.team().to_string(),
+            agent: player.agent().to_string(),
+            rank: (player.current_tier() > 0).then(|| Rank {
+                tier: player.current_tier(),
+                name: player.current_tier_patched().to_string(),
+                rr: None,
+            }),
+            score: stats.score(),
+            kills: stats.kills(),
+            deaths: stats.deaths(),
+            assists: stats.assists(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Round {
+    /// Starts at 1.
+    pub number: u32,
+    pub winning_team: String,
+    /// How the round ended, e.g. `Eliminated` or `Bomb defused`.
+    pub end_type: String,
+}
+
+impl Round {
+    fn new(number: u32, round: &RoundSummary) -> Self {
+        Round {
+            number,
+            winning_team: round.winning_team().to_string(),
+            end_type: round.end_type().to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::fixtures::{decode, ACCOUNT, MATCH_CUSTOM, MMR};
+
+    #[test]
+    fn converts_wire_models() {
+        let account: AccountData = decode(ACCOUNT);
+        let mmr: MMRData = decode(MMR);
+        assert_eq!(Player::from(&account).puuid, Player::from(&mmr).puuid);
+        assert_eq!(
+            Rank::from(&mmr),
+            Rank {
+                tier: 16,
+                name: "Platinum 2".to_string(),
+                rr: Some(47)
+            }
+        );
+
+        let matches: Vec<MatchData> = decode(MATCH_CUSTOM);
+        let game = &matches[0];
+        let converted = Match::from(game);
+        assert_eq!(converted.participants.len(), game.players().len());
+        // Custom games are unranked
+        assert!(converted.participants.iter().all(|p| p.rank.is_none()));
+        assert_eq!(converted.rounds.len(), 20);
+        assert_eq!(converted.rounds.last().unwrap().number, 20);
+        assert_eq!(converted.rounds.last().unwrap().end_type, "Surrendered");
+    }
+}
@@ -0,0 +1,206 @@
+//! The one error type of the client.
+//!
+//! Every request of [`ValorantClient`](crate::ValorantClient) unwraps the API's envelope, so an
+//! `Ok` always holds data and every way a request can fail is a [`ValorantError`]: the network,
+//! a payload that doesn't fit the model, a rate limit, any other failure the API reports, an
+//! input that was never sent, or the deadline of an operation making several requests.
+
+use crate::deadline::DeadlineExceeded;
+use crate::retry::RetryHint;
+use crate::validation::ValidationError;
+use crate::{ApiError, ApiResponse, ValorantAPIData};
+use std::fmt;
+use std::time::Duration;
+
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum ValorantError {
+    /// The request never got an answer, e.g. a timeout or a refused connection.
+    Transport(reqwest::Error),
+    /// The API answered with a payload that doesn't fit the model.
+    Decode(reqwest::Error),
+    /// The API answered 429.
+    RateLimited { retry_after: Option<Duration> },
+    /// The API answered with any other failure, e.g. a 404 for an unknown player.
+    ApiError { status: u32, errors: Vec<ApiError> },
+    /// The request was rejected before it was sent.
+    InvalidInput(ValidationError),
+    /// The deadline of an operation passed before all of its requests were answered.
+    DeadlineExceeded,
+}
+
+impl ValorantError {
+    pub(crate) fn from_failure(status: u32, errors: Vec<ApiError>) -> Self {
+        match status {
+            429 => ValorantError::RateLimited {
+                retry_after: errors.iter().find_map(|error| error.retry_after),
+            },
+            _ => ValorantError::ApiError { status, errors },
+        }
+    }
+
+    /// The status the API answered with, `None` when it didn't answer.
+    pub fn status(&self) -> Option<u32> {
+        match self {
+            ValorantError::RateLimited { .. } => Some(429),
+            ValorantError::ApiError { status, .. } => Some(*status),
+            ValorantError::Decode(error) => error.status().map(|status| status.as_u16().into()),
+            ValorantError::Transport(_)
+            | ValorantError::InvalidInput(_)
+            | ValorantError::DeadlineExceeded => None,
+        }
+    }
+}
+
+impl fmt::Display for ValorantError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ValorantError::Transport(error) => write!(f, "Request failed: {error}"),
+            ValorantError::Decode(error) => write!(f, "Unexpected response payload: {error}"),
+            ValorantError::RateLimited {
+                retry_after: Some(wait),
+            } => write!(f, "Rate limited, retry in {}s", wait.as_secs()),
+            ValorantError::RateLimited { retry_after: None } => write!(f, "Rate limited"),
+            ValorantError::ApiError { status, errors } => match errors.first() {
+                Some(error) => write!(f, "The API answered {status}: {}", error.message),
+                None => write!(f, "The API answered {status}"),
+            },
+            ValorantError::InvalidInput(error) => write!(f, "Invalid request: {error}"),
+            ValorantError::DeadlineExceeded => DeadlineExceeded.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for ValorantError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ValorantError::Transport(error) | ValorantError::Decode(error) => Some(error),
+            ValorantError::InvalidInput(error) => Some(error),
+            ValorantError::DeadlineExceeded => Some(&DeadlineExceeded),
+            ValorantError::RateLimited { .. } | ValorantError::ApiError { .. } => None,
+        }
+    }
+}
+
+impl From<reqwest::Error> for ValorantError {
+    fn from(error: reqwest::Error) -> Self {
+        if error.is_decode() {
+            ValorantError::Decode(error)
+        } else {
+            ValorantError::Transport(error)
+        }
+    }
+}
+
+impl From<ValidationError> for ValorantError {
+    fn from(error: ValidationError) -> Self {
+        ValorantError::InvalidInput(error)
+    }
+}
+
+impl From<DeadlineExceeded> for ValorantError {
+    fn from(_: DeadlineExceeded) -> Self {
+        ValorantError::DeadlineExceeded
+    }
+}
+
+impl RetryHint for ValorantError {
+    fn is_retryable(&self) -> bool {
+        match self {
+            ValorantError::Transport(error) => error.is_retryable(),
+            ValorantError::RateLimited { .. } => true,
+            ValorantError::ApiError { status, .. } => {
+                matches!(status, 408 | 500..=599)
+            }
+            ValorantError::Decode(_)
+            | ValorantError::InvalidInput(_)
+            | ValorantError::DeadlineExceeded => false,
+        }
+    }
+
+    fn retry_after(&self) -> Option<Duration> {
+        match self {
+            ValorantError::RateLimited { retry_after } => *retry_after,
+            _ => None,
+        }
+    }
+
+    fn is_not_found(&self) -> bool {
+        self.status() == Some(404)
+    }
+}
+
+// Unwraps a response of the API into its data
+pub(crate) fn into_data<T: ValorantAPIData>(response: ApiResponse<T>) -> Result<T, ValorantError> {
+    match response {
+        ApiResponse::Success { data, .. } => Ok(data),
+        ApiResponse::Failure { status, errors } => Err(ValorantError::from_failure(status, errors)),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::fixtures::{ACCOUNT, NOT_FOUND};
+    use crate::mock_server::{MockResponse, MockServer};
+    use crate::prelude::*;
+
+    const RATE_LIMITED: &str =
+        r#"{"status":429,"errors":[{"message":"Rate Limited","code":0,"details":"null"}]}"#;
+
+    fn account(tag: &str) -> ValorantApiType<'_> {
+        ValorantApiType::AccountData {
+            name: "Anonymous",
+            tag,
+        }
+    }
+
+    #[tokio::test]
+    async fn failures_are_sorted_by_cause() {
+        let server = MockServer::start(vec![
+            MockResponse::json(200, ACCOUNT),
+            MockResponse::json(404, NOT_FOUND),
+            MockResponse::json(429, RATE_LIMITED).header("retry-after", "30"),
+            MockResponse::json(200, r#"{"status":200,"data":{"name":42}}"#),
+        ])
+        .await;
+        let url = server.url();
+        let client = ValorantClient::new().change_api_endpoint(&url);
+        let request = |tag| client.request::<AccountData>(account(tag));
+
+        let data = request("0000").await.unwrap();
+        assert_eq!(data.name(), "Anonymous");
+
+        let error = request("0000").await.unwrap_err();
+        assert!(matches!(error, ValorantError::ApiError { status: 404, .. }));
+        assert!(error.is_not_found());
+
+        let error = request("0000").await.unwrap_err();
+        assert!(matches!(
+            error,
+            ValorantError::RateLimited {
+                retry_after: Some(wait)
+            } if wait == Duration::from_secs(30)
+        ));
+
+        let error = request("0000").await.unwrap_err();
+        assert!(matches!(error, ValorantError::Decode(_)));
+        assert!(!error.is_retryable());
+
+        let error = request("0").await.unwrap_err();
+        assert!(matches!(error, ValorantError::InvalidInput(_)));
+        assert_eq!(server.requests().len(), 4);
+    }
+
+    #[tokio::test]
+    async fn unreachable_apis_are_transport_errors() {
+        let client = ValorantClient::new().change_api_endpoint("http://127.0.0.1:1");
+        let error = client
+            .request::<AccountData>(account("0000"))
+            .await
+            .unwrap_err();
+        assert!(matches!(error, ValorantError::Transport(_)));
+        assert!(error.is_retryable());
+        assert_eq!(error.status(), None);
+    }
+}
@@ -0,0 +1,319 @@
+//! The professional schedule: upcoming, live and finished matches of every league.
+//!
+//! With the `ics` feature, `to_ics` turns a schedule into an iCalendar file that calendar
+//! apps can subscribe to.
+
+#[cfg(feature = "client")]
+use crate::error::ValorantError;
+use crate::ValorantAPIData;
+#[cfg(feature = "client")]
+use crate::{ValorantApiType, ValorantClient};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub enum EventState {
+    Unstarted,
+    InProgress,
+    Completed,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct EsportsEvent {
+    date: DateTime<Utc>,
+    state: EventState,
+    #[serde(default)]
+    vod: Option<String>,
+    league: League,
+    tournament: Tournament,
+    #[serde(rename = "match")]
+    details: EsportsMatch,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct League {
+    name: String,
+    identifier: String,
+    icon: String,
+    region: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+struct Tournament {
+    name: String,
+    season: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+struct EsportsMatch {
+    id: String,
+    game_type: GameType,
+    teams: Vec<EsportsTeam>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+struct GameType {
+    #[serde(rename = "type")]
+    kind: String,
+    count: u32,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct EsportsTeam {
+    name: String,
+    code: String,
+    #[serde(default)]
+    icon: String,
+    has_won: bool,
+    game_wins: u32,
+    #[serde(default)]
+    record: Option<TeamRecord>,
+}
+
+/// A team's wins and losses in the tournament so far.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TeamRecord {
+    pub wins: u32,
+    pub losses: u32,
+}
+
+impl EsportsEvent {
+    /// The scheduled start.
+    pub fn date(&self) -> DateTime<Utc> {
+        self.date
+    }
+
+    pub fn state(&self) -> EventState {
+        self.state
+    }
+
+    pub fn vod(&self) -> Option<&str> {
+        self.vod.as_deref()
+    }
+
+    pub fn league(&self) -> &League {
+        &self.league
+    }
+
+    pub fn tournament(&self) -> &str {
+        &self.tournament.name
+    }
+
+    pub fn season(&self) -> &str {
+        &self.tournament.season
+    }
+
+    /// The match's ID on the esports site, which is not a [`MatchId`](crate::ids::MatchId).
+    pub fn match_id(&self) -> &str {
+        &self.details.id
+    }
+
+    /// The most games the match can take, e.g. 3 for a best of three.
+    pub fn games(&self) -> u32 {
+        self.details.game_type.count
+    }
+
+    /// Whether the match is a best of [`EsportsEvent::games`] rather than all games played.
+    pub fn is_best_of(&self) -> bool {
+        self.details.game_type.kind == "bestOf"
+    }
+
+    pub fn teams(&self) -> &[EsportsTeam] {
+        &self.details.teams
+    }
+
+    /// `Team A vs Team B`, with `TBD` for teams that aren't known yet.
+    pub fn title(&self) -> String {
+        self.details
+            .teams
+            .iter()
+            .map(|team| team.name.as_str())
+            .collect::<Vec<_>>()
+            .join(" vs ")
+    }
+}
+
+impl League {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The league's short name for filtering the schedule, e.g. `vct_americas`.
+    pub fn identifier(&self) -> &str {
+        &self.identifier
+    }
+
+    pub fn icon(&self) -> &str {
+        &self.icon
+    }
+
+    pub fn region(&self) -> &str {
+        &self.region
+    }
+}
+
+impl EsportsTeam {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn code(&self) -> &str {
+        &self.code
+    }
+
+    pub fn icon(&self) -> &str {
+        &self.icon
+    }
+
+    pub fn has_won(&self) -> bool {
+        self.has_won
+    }
+
+    pub fn game_wins(&self) -> u32 {
+        self.game_wins
+    }
+
+    pub fn record(&self) -> Option<TeamRecord> {
+        self.record
+    }
+}
+
+impl ValorantAPIData for EsportsEvent {}
+
+#[cfg(feature = "client")]
+impl ValorantClient<'_> {
+    /// The schedule of every league, or of one league by its identifier, e.g. `vct_emea`.
+    pub async fn esports_schedule(
+        &self,
+        league: Option<&str>,
+    ) -> Result<Vec<EsportsEvent>, ValorantError> {
+        self.request::<Vec<EsportsEvent>>(ValorantApiType::EsportsSchedule { league })
+            .await
+    }
+}
+
+/// Renders the events as an iCalendar file named `name`, one event per match.
+///
+/// Matches are scheduled for an hour per game they can take, since the schedule has no end
+/// times. Each event keeps the match's ID as its UID, so calendar apps update an event in
+/// place when the file is fetched again.
+#[cfg(feature = "ics")]
+pub fn to_ics(name: &str, events: &[EsportsEvent]) -> String {
+    const FORMAT: &str = "%Y%m%dT%H%M%SZ";
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        "PRODID:-//valorant-api//esports schedule//EN".to_string(),
+        "CALSCALE:GREGORIAN".to_string(),
+        format!("X-WR-CALNAME:{}", ics_text(name)),
+    ];
+    let stamp = Utc::now().format(FORMAT);
+    for event in events {
+        let end = event.date + chrono::Duration::hours(i64::from(event.games().max(1)));
+        let mut description = format!("{} ({})", event.tournament(), event.league.region);
+        if let Some(vod) = event.vod() {
+            description.push_str(&format!("\nVOD: {vod}"));
+        }
+        lines.extend([
+            "BEGIN:VEVENT".to_string(),
+            format!("UID:{}@esports.valorant", ics_text(event.match_id())),
+            format!("DTSTAMP:{stamp}"),
+            format!("DTSTART:{}", event.date.format(FORMAT)),
+            format!("DTEND:{}", end.format(FORMAT)),
+            format!(
+                "SUMMARY:{}",
+                ics_text(&format!("{} · {}", event.title(), event.league.name))
+            ),
+            format!("DESCRIPTION:{}", ics_text(&description)),
+        ]);
+        if let Some(vod) = event.vod() {
+            lines.push(format!("URL:{vod}"));
+        }
+        lines.push("END:VEVENT".to_string());
+    }
+    lines.push("END:VCALENDAR".to_string());
+
+    let mut ics = String::new();
+    for line in lines {
+        fold(&line, &mut ics);
+    }
+    ics
+}
+
+// Escapes the characters iCalendar text values reserve
+#[cfg(feature = "ics")]
+fn ics_text(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace('\n', "\\n")
+}
+
+// Lines are at most 75 bytes, continued on lines starting with a space, and end in CRLF
+#[cfg(feature = "ics")]
+fn fold(line: &str, ics: &mut String) {
+    let mut width = 0;
+    for character in line.chars() {
+        if width + character.len_utf8() > 75 {
+            ics.push_str("\r\n ");
+            width = 1;
+        }
+        ics.push(character);
+        width += character.len_utf8();
+    }
+    ics.push_str("\r\n");
+}
+
+#[cfg(all(test, any(feature = "client", feature = "ics")))]
+mod test {
+    use super::*;
+    use crate::fixtures::ESPORTS_SCHEDULE;
+    #[cfg(feature = "client")]
+    use crate::mock_server::{MockResponse, MockServer};
+
+    #[cfg(feature = "client")]
+    #[tokio::test]
+    async fn fetches_a_league_schedule() {
+        let server = MockServer::routes(vec![(
+            "/v1/esports/schedule?league=vct_lock_in",
+            MockResponse::json(200, ESPORTS_SCHEDULE),
+        )])
+        .await;
+        let url = server.url();
+        let client = ValorantClient::new().change_api_endpoint(&url);
+
+        let data = client.esports_schedule(Some("vct_lock_in")).await.unwrap();
+        assert_eq!(data.len(), 2);
+        assert_eq!(data[0].title(), "FNATIC vs NRG Esports");
+        assert_eq!(
+            data[0].teams()[0].record().map(|record| record.wins),
+            Some(3)
+        );
+        assert_eq!(data[1].state(), EventState::Unstarted);
+        assert!(data[1].is_best_of());
+    }
+
+    #[cfg(feature = "ics")]
+    #[test]
+    fn exports_icalendar() {
+        let data: Vec<EsportsEvent> = crate::fixtures::decode(ESPORTS_SCHEDULE);
+        let ics = to_ics("VCT, LOCK//IN", &data);
+
+        assert!(ics.starts_with("BEGIN:VCALENDAR\r\nVERSION:2.0\r\n"));
+        assert!(ics.ends_with("END:VCALENDAR\r\n"));
+        assert!(ics.contains("X-WR-CALNAME:VCT\\, LOCK//IN\r\n"));
+        assert_eq!(ics.matches("BEGIN:VEVENT").count(), 2);
+        assert!(ics.contains("UID:109625682604324140@esports.valorant\r\n"));
+        assert!(ics.contains("DTSTART:20230225T193000Z\r\nDTEND:20230226T003000Z\r\n"));
+        assert!(ics.contains("URL:https://www.youtube.com/watch?v=lockin-fnc-nrg\r\n"));
+        assert!(ics
+            .split("\r\n")
+            .all(|line| line.len() <= 75 && !line.contains('\n')));
+        // Folded lines unfold back into the original
+        let unfolded = ics.replace("\r\n ", "");
+        assert!(unfolded.contains("SUMMARY:FNATIC vs NRG Esports · LOCK//IN São Paulo\r\n"));
+    }
+}
@@ -0,0 +1,153 @@
+//! Deterministic fake data for tests and offline demos.
+//!
+//! Every model implements [`Fake`]; the same seed always produces the same value. Tests that
+//! need a particular match or leaderboard page build it with [`FakeMatch`] or
+//! [`FakeLeaderboard`] instead.
+
+pub use crate::leaderboard::fake::FakeLeaderboard;
+pub use crate::match_data::fake::FakeMatch;
+use crate::tier::Tier;
+use serde::Serialize;
+
+/// A small SplitMix64 generator, so fakes don't pull in a random number crate.
+#[derive(Debug, Clone)]
+pub struct FakeRng {
+    state: u64,
+}
+
+impl FakeRng {
+    pub fn seeded(seed: u64) -> Self {
+        FakeRng { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// A number in `low..=high`.
+    pub fn between(&mut self, low: u32, high: u32) -> u32 {
+        low + (self.next_u64() % u64::from(high - low + 1)) as u32
+    }
+
+    pub fn chance(&mut self, percent: u32) -> bool {
+        self.between(1, 100) <= percent
+    }
+
+    pub fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.next_u64() as usize % items.len()]
+    }
+
+    pub fn uuid(&mut self) -> String {
+        let high = self.next_u64();
+        let low = self.next_u64();
+        format!(
+            "{:08x}-{:04x}-4{:03x}-{:04x}-{:012x}",
+            high >> 32,
+            (high >> 16) & 0xffff,
+            high & 0x0fff,
+            ((low >> 48) & 0x3fff) | 0x8000,
+            low & 0xffff_ffff_ffff
+        )
+    }
+
+    pub fn name(&mut self) -> String {
+        const FIRST: &[&str] = &[
+            "Nitro", "Silent", "Baby", "Frosty", "Neon", "Rusty", "Lucky",
+        ];
+        const SECOND: &[&str] = &["Sniper", "Fox", "Viper", "Sage", "Tapper", "Lurker", "Duck"];
+        format!("{}{}", self.pick(FIRST), self.pick(SECOND))
+    }
+
+    pub fn tag(&mut self) -> String {
+        const CHARS: &[u8] = b"ABCDEFGHJKLMNPQRSTUVWXYZ0123456789";
+        let length = self.between(3, 5);
+        (0..length).map(|_| *self.pick(CHARS) as char).collect()
+    }
+}
+
+/// Implemented by every model, and open for an application's own types.
+pub trait Fake: Sized {
+    fn fake_with(rng: &mut FakeRng) -> Self;
+
+    fn fake(seed: u64) -> Self {
+        Self::fake_with(&mut FakeRng::seeded(seed))
+    }
+}
+
+pub(crate) fn tier_name(tier: u32) -> String {
+    Tier::from_id(tier).unwrap_or_default().to_string()
+}
+
+// Wraps `data` the way the API answers a successful request
+pub(crate) fn success<T: Serialize>(data: &T) -> String {
+    serde_json::json!({ "status": 200, "data": data }).to_string()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::fixtures::decode;
+    use crate::prelude::*;
+
+    #[test]
+    fn same_seed_same_data() {
+        let first = serde_json::to_string(&MMRData::fake(7)).unwrap();
+        let second = serde_json::to_string(&MMRData::fake(7)).unwrap();
+        assert_eq!(first, second);
+
+        let other = serde_json::to_string(&MMRData::fake(8)).unwrap();
+        assert_ne!(first, other);
+    }
+
+    #[test]
+    fn fakes_round_trip() {
+        let mmr = serde_json::to_string(&MMRData::fake(1)).unwrap();
+        serde_json::from_str::<MMRData>(&mmr).unwrap();
+
+        let account = serde_json::to_string(&AccountData::fake(1)).unwrap();
+        serde_json::from_str::<AccountData>(&account).unwrap();
+    }
+
+    #[test]
+    fn match_builder() {
+        let game = FakeMatch::custom().won_by("Blue");
+        let data: MatchData = decode(&game.response());
+        assert_eq!(data.winning_team(), Some("Blue"));
+        assert_eq!(data.rounds_won("Blue"), Some(11));
+        assert_eq!(data.rounds()[0].winning_team(), "Blue");
+
+        let swapped = FakeMatch::custom().swap_colours().build();
+        assert_eq!(swapped.winning_team(), Some("Blue"));
+        assert_eq!(swapped.players()[0].team(), "Blue");
+        assert_eq!(swapped.rounds()[0].winning_team(), "Blue");
+
+        let first = MatchData::fake(4);
+        assert_eq!(first.match_id(), MatchData::fake(4).match_id());
+        assert_ne!(first.match_id(), MatchData::fake(5).match_id());
+    }
+
+    #[test]
+    fn uuid_and_tag_shape() {
+        let mut rng = FakeRng::seeded(3);
+        let uuid = rng.uuid();
+        assert_eq!(uuid.len(), 36);
+        assert_eq!(uuid.matches('-').count(), 4);
+
+        for _ in 0..50 {
+            assert!((3..=5).contains(&rng.tag().len()));
+        }
+    }
+
+    #[test]
+    fn tier_names() {
+        assert_eq!(tier_name(0), "Unrated");
+        assert_eq!(tier_name(3), "Iron 1");
+        assert_eq!(tier_name(16), "Platinum 2");
+        assert_eq!(tier_name(26), "Immortal 3");
+        assert_eq!(tier_name(27), "Radiant");
+    }
+}
@@ -0,0 +1,147 @@
+//! Anonymized responses captured from every supported endpoint.
+//!
+//! The snapshot tests below parse each fixture into its model, so refreshing a fixture from the
+//! live API shows any schema change as a snapshot diff.
+
+use crate::{ApiResponse, ValorantAPIData};
+use serde::de::DeserializeOwned;
+
+pub const ACCOUNT: &str = include_str!("../fixtures/account.json");
+pub const MMR: &str = include_str!("../fixtures/mmr.json");
+pub const MMR_HISTORY: &str = include_str!("../fixtures/mmr_history.json");
+pub const MATCH_HISTORY: &str = include_str!("../fixtures/match_history.json");
+pub const MATCH_DEATHMATCH: &str = include_str!("../fixtures/match_deathmatch.json");
+pub const MATCH_CUSTOM: &str = include_str!("../fixtures/match_custom.json");
+pub const STORED_MATCHES: &str = include_str!("../fixtures/stored_matches.json");
+pub const CONTENT: &str = include_str!("../fixtures/content.json");
+pub const LEADERBOARD: &str = include_str!("../fixtures/leaderboard.json");
+pub const SEASONS: &str = include_str!("../fixtures/seasons.json");
+pub const STORE_FEATURED: &str = include_str!("../fixtures/store_featured.json");
+pub const NIGHT_MARKET: &str = include_str!("../fixtures/night_market.json");
+pub const VERSION: &str = include_str!("../fixtures/version.json");
+pub const COMPETITIVE_UPDATES: &str = include_str!("../fixtures/competitive_updates.json");
+pub const ESPORTS_SCHEDULE: &str = include_str!("../fixtures/esports_schedule.json");
+pub const WEBSITE: &str = include_str!("../fixtures/website.json");
+pub const NOT_FOUND: &str = include_str!("../fixtures/not_found.json");
+pub const RATE_LIMITED: &str = include_str!("../fixtures/rate_limited.json");
+
+/// Decodes a response that has to be a success into its data.
+pub(crate) fn decode<T: ValorantAPIData + DeserializeOwned>(response: &str) -> T {
+    serde_json::from_str::<ApiResponse<T>>(response)
+        .unwrap()
+        .into_result()
+        .expect("the response is a success")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::prelude::*;
+
+    #[test]
+    fn account() {
+        let result: ApiResponse<AccountData> = serde_json::from_str(ACCOUNT).unwrap();
+        insta::assert_debug_snapshot!(result);
+    }
+
+    #[test]
+    fn mmr() {
+        let result: ApiResponse<MMRData> = serde_json::from_str(MMR).unwrap();
+        insta::assert_debug_snapshot!(result);
+    }
+
+    #[test]
+    fn mmr_history() {
+        let result: ApiResponse<Vec<MMRHistoryEntry>> = serde_json::from_str(MMR_HISTORY).unwrap();
+        insta::assert_debug_snapshot!(result);
+    }
+
+    #[test]
+    fn match_history() {
+        let result: ApiResponse<Vec<MatchData>> = serde_json::from_str(MATCH_HISTORY).unwrap();
+        insta::assert_debug_snapshot!(result);
+    }
+
+    #[test]
+    fn match_deathmatch() {
+        let result: ApiResponse<Vec<MatchData>> = serde_json::from_str(MATCH_DEATHMATCH).unwrap();
+        insta::assert_debug_snapshot!(result);
+    }
+
+    #[test]
+    fn match_custom() {
+        let result: ApiResponse<Vec<MatchData>> = serde_json::from_str(MATCH_CUSTOM).unwrap();
+        insta::assert_debug_snapshot!(result);
+    }
+
+    #[test]
+    fn stored_matches() {
+        let result: ApiResponse<Vec<StoredMatch>> = serde_json::from_str(STORED_MATCHES).unwrap();
+        insta::assert_debug_snapshot!(result);
+    }
+
+    #[test]
+    fn content() {
+        let result: ApiResponse<crate::content::Content> = serde_json::from_str(CONTENT).unwrap();
+        insta::assert_debug_snapshot!(result);
+    }
+
+    #[test]
+    fn leaderboard() {
+        let result: ApiResponse<Leaderboard> = serde_json::from_str(LEADERBOARD).unwrap();
+        insta::assert_debug_snapshot!(result);
+    }
+
+    #[test]
+    fn seasons() {
+        let result: ApiResponse<Vec<crate::assets::Season>> =
+            serde_json::from_str(SEASONS).unwrap();
+        insta::assert_debug_snapshot!(result);
+    }
+
+    #[test]
+    fn store_featured() {
+        let result: ApiResponse<Vec<crate::store::FeaturedBundle>> =
+            serde_json::from_str(STORE_FEATURED).unwrap();
+        insta::assert_debug_snapshot!(result);
+    }
+
+    #[test]
+    fn night_market() {
+        let result: ApiResponse<crate::store::NightMarket> =
+            serde_json::from_str(NIGHT_MARKET).unwrap();
+        insta::assert_debug_snapshot!(result);
+    }
+
+    #[test]
+    fn version() {
+        let result: ApiResponse<GameVersion> = serde_json::from_str(VERSION).unwrap();
+        insta::assert_debug_snapshot!(result);
+    }
+
+    #[test]
+    fn competitive_updates() {
+        // The raw endpoint passes Riot's payload through without the usual envelope
+        let result: CompetitiveUpdates = serde_json::from_str(COMPETITIVE_UPDATES).unwrap();
+        insta::assert_debug_snapshot!(result);
+    }
+
+    #[test]
+    fn esports_schedule() {
+        let result: ApiResponse<Vec<EsportsEvent>> =
+            serde_json::from_str(ESPORTS_SCHEDULE).unwrap();
+        insta::assert_debug_snapshot!(result);
+    }
+
+    #[test]
+    fn website() {
+        let result: ApiResponse<Vec<NewsArticle>> = serde_json::from_str(WEBSITE).unwrap();
+        insta::assert_debug_snapshot!(result);
+    }
+
+    #[test]
+    fn not_found() {
+        let result: ApiResponse<AccountData> = serde_json::from_str(NOT_FOUND).unwrap();
+        insta::assert_debug_snapshot!(result);
+    }
+}
@@ -0,0 +1,131 @@
+//! How well a player has been playing lately, weighting recent matches more than old ones.
+//!
+//! A rank says where a player got to, not how they play today. [`FormModel`] weighs every
+//! match of a player's history by its age, halving its weight every half-life, so a Diamond
+//! who stopped playing a month ago has little recent form while one on a streak this week has
+//! plenty.
+
+use crate::match_data::{MatchData, MatchResult};
+use chrono::{DateTime, Duration, Utc};
+
+/// A player's recent form, as of the time it was computed.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[non_exhaustive]
+pub struct Form {
+    /// The weighted average of the player's combat score against their lobby's average, so
+    /// 1.0 plays like the average player in their games.
+    pub score: f64,
+    /// The weighted share of matches won, counting draws as half a win.
+    pub win_rate: f64,
+    /// The sum of the weights, i.e. how many matches played right now the history is worth.
+    /// Low activity means the score says little about today.
+    pub activity: f64,
+    pub matches: usize,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct FormModel {
+    half_life: Duration,
+}
+
+impl Default for FormModel {
+    fn default() -> Self {
+        FormModel::new(Duration::days(14))
+    }
+}
+
+impl FormModel {
+    /// Weighs matches by `0.5 ^ (age / half_life)`.
+    pub fn new(half_life: Duration) -> Self {
+        FormModel { half_life }
+    }
+
+    pub fn half_life(&self) -> Duration {
+        self.half_life
+    }
+
+    fn weight(&self, started_at: DateTime<Utc>, now: DateTime<Utc>) -> f64 {
+        let half_life = self.half_life.num_seconds().max(1) as f64;
+        let age = (now - started_at).num_seconds().max(0) as f64;
+        0.5_f64.powf(age / half_life)
+    }
+
+    /// The form of the player with `puuid` over `matches` as of `now`. Matches the player
+    /// isn't in and matches without a result for them, like unfinished customs, are skipped;
+    /// `None` when none are left.
+    pub fn form(&self, matches: &[MatchData], puuid: &str, now: DateTime<Utc>) -> Option<Form> {
+        let (mut score, mut wins, mut activity, mut count) = (0.0, 0.0, 0.0, 0);
+        for game in matches {
+            let (Some(player), Some(outcome)) =
+                (game.find_player_by_puuid(puuid), game.result_for(puuid))
+            else {
+                continue;
+            };
+            let lobby = game.players();
+            let average = lobby.iter().map(|other| other.stats().score()).sum::<u32>() as f64
+                / lobby.len() as f64;
+            let weight = self.weight(game.started_at(), now);
+            if average > 0.0 {
+                score += weight * player.stats().score() as f64 / average;
+            }
+            wins += weight
+                * match outcome.result {
+                    MatchResult::Win => 1.0,
+                    MatchResult::Draw => 0.5,
+                    MatchResult::Loss => 0.0,
+                };
+            activity += weight;
+            count += 1;
+        }
+        (count > 0).then(|| Form {
+            score: score / activity,
+            win_rate: wins / activity,
+            activity,
+            matches: count,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::fake::FakeMatch;
+
+    const ANONYMOUS: &str = "81862fc9-634f-806f-abf4-a07c56600224";
+    const LURKER: &str = "3fc24ec0-9529-89c1-7d9c-649a8bd5bb71";
+
+    #[test]
+    fn recent_matches_weigh_more() {
+        let model = FormModel::new(Duration::days(7));
+        let game = [FakeMatch::competitive().build()];
+        let start = game[0].started_at();
+
+        // Lurker lost with the lowest combat score of the lobby
+        let form = model.form(&game, LURKER, start).unwrap();
+        assert_eq!(form.win_rate, 0.0);
+        assert!((form.score - 2860.0 / 4595.0).abs() < 1e-9);
+        assert_eq!(form.activity, 1.0);
+
+        // A month without games leaves the same form with little weight behind it
+        let rusty = model
+            .form(&game, LURKER, start + Duration::days(28))
+            .unwrap();
+        assert_eq!(rusty.score, form.score);
+        assert_eq!(rusty.activity, 0.0625);
+
+        // A win a week ago counts twice as much as the loss two weeks ago
+        let mut history = vec![
+            FakeMatch::competitive().won_by("Blue").build(),
+            FakeMatch::competitive().days_later(7).build(),
+        ];
+        let form = model
+            .form(&history, ANONYMOUS, start + Duration::days(14))
+            .unwrap();
+        assert_eq!(form.matches, 2);
+        assert!((form.win_rate - 2.0 / 3.0).abs() < 1e-9);
+        assert_eq!(form.activity, 0.75);
+
+        history.clear();
+        assert_eq!(model.form(&history, ANONYMOUS, start), None);
+    }
+}
@@ -0,0 +1,343 @@
+//! A GraphQL facade over the client, built with [async-graphql](https://docs.rs/async-graphql).
+//!
+//! [`schema`] wires a client into a ready to serve schema. The object types mirror the main
+//! models, limited to the fields most frontends need, and API failures are reported as GraphQL
+//! errors carrying the API's status code in their `status` extension.
+//!
+//! ```no_run
+//! # async fn run() {
+//! let schema = valorant_api::graphql::schema(valorant_api::ValorantClient::new());
+//! let response = schema
+//!     .execute(r#"{ account(name: "NitroSniper", tag: "NERD") { puuid accountLevel } }"#)
+//!     .await;
+//! # }
+//! ```
+
+use crate::account_data::{AccountData, ProfileBanner};
+use crate::error::ValorantError;
+use crate::leaderboard::{Leaderboard, LeaderboardEntry};
+use crate::match_data::{MatchData, MatchPlayer};
+use crate::mmr_data::{EpisodeAndAct, MMRData};
+use crate::{AccountRegion, ValorantAPIData, ValorantApiType, ValorantClient};
+use async_graphql::{
+    EmptyMutation, EmptySubscription, ErrorExtensions, Object, Schema, SimpleObject,
+};
+use std::sync::Arc;
+
+pub type ValorantSchema = Schema<QueryRoot, EmptyMutation, EmptySubscription>;
+
+/// Builds a schema answering queries with `client`.
+pub fn schema(client: ValorantClient<'static>) -> ValorantSchema {
+    Schema::new(
+        QueryRoot {
+            client: Arc::new(client),
+        },
+        EmptyMutation,
+        EmptySubscription,
+    )
+}
+
+pub struct QueryRoot {
+    client: Arc<ValorantClient<'static>>,
+}
+
+impl QueryRoot {
+    pub fn new(client: Arc<ValorantClient<'static>>) -> Self {
+        QueryRoot { client }
+    }
+
+    async fn fetch<T>(&self, api_type: ValorantApiType<'_>) -> async_graphql::Result<T>
+    where
+        T: serde::de::DeserializeOwned + ValorantAPIData,
+    {
+        self.client.request::<T>(api_type).await.map_err(|error| {
+            let message = match &error {
+                ValorantError::ApiError { errors, .. } => errors.first().map_or_else(
+                    || "The API request failed".to_string(),
+                    |error| error.message.clone(),
+                ),
+                error => error.to_string(),
+            };
+            let graphql_error = async_graphql::Error::new(message);
+            match error.status() {
+                Some(status) => graphql_error.extend_with(|_, e| e.set("status", status)),
+                None => graphql_error,
+            }
+        })
+    }
+}
+
+#[Object]
+impl QueryRoot {
+    async fn account(&self, name: String, tag: String) -> async_graphql::Result<Account> {
+        let account: AccountData = self
+            .fetch(ValorantApiType::AccountData {
+                name: &name,
+                tag: &tag,
+            })
+            .await?;
+        Ok(Account::from(&account))
+    }
+
+    /// The player's rank in the current act.
+    async fn mmr(
+        &self,
+        region: AccountRegion,
+        name: String,
+        tag: String,
+    ) -> async_graphql::Result<Mmr> {
+        let mmr: MMRData = self
+            .fetch(ValorantApiType::MMRData {
+                region,
+                name: &name,
+                tag: &tag,
+                filter: None,
+            })
+            .await?;
+        Ok(Mmr::from(&mmr))
+    }
+
+    /// The player's recent matches, newest first.
+    async fn matches(
+        &self,
+        region: AccountRegion,
+        name: String,
+        tag: String,
+    ) -> async_graphql::Result<Vec<Match>> {
+        let matches: Vec<MatchData> = self
+            .fetch(ValorantApiType::MatchHistory {
+                region,
+                name: &name,
+                tag: &tag,
+            })
+            .await?;
+        Ok(matches.iter().map(Match::from).collect())
+    }
+
+    /// The current act's leaderboard.
+    async fn leaderboard(
+        &self,
+        region: AccountRegion,
+    ) -> async_graphql::Result<Vec<LeaderboardPlayer>> {
+        let leaderboard: Leaderboard = self
+            .fetch(ValorantApiType::Leaderboard {
+                region,
+                season: None,
+                page: None,
+                size: None,
+            })
+            .await?;
+        Ok(leaderboard
+            .players()
+            .iter()
+            .map(LeaderboardPlayer::from)
+            .collect())
+    }
+}
+
+#[derive(SimpleObject, Debug, Clone)]
+pub struct Account {
+    pub puuid: String,
+    pub region: AccountRegion,
+    pub name: String,
+    pub tag: String,
+    pub account_level: u32,
+    pub card: Card,
+}
+
+impl From<&AccountData> for Account {
+    fn from(account: &AccountData) -> Self {
+        Account {
+            puuid: account.puuid().to_string(),
+            region: account.region(),
+            name: account.name().to_string(),
+            tag: account.tag().to_string(),
+            account_level: account.account_level(),
+            card: Card::from(account.card()),
+        }
+    }
+}
+
+/// The player card, with image URLs in each size.
+#[derive(SimpleObject, Debug, Clone)]
+pub struct Card {
+    pub id: String,
+    pub small: String,
+    pub large: String,
+    pub wide: String,
+}
+
+impl From<&ProfileBanner> for Card {
+    fn from(card: &ProfileBanner) -> Self {
+        Card {
+            id: card.id().to_string(),
+            small: card.small().to_string(),
+            large: card.large().to_string(),
+            wide: card.wide().to_string(),
+        }
+    }
+}
+
+#[derive(SimpleObject, Debug, Clone)]
+pub struct Mmr {
+    pub puuid: String,
+    pub name: String,
+    pub tag: String,
+    pub tier: u32,
+    pub tier_name: String,
+    /// RR within the current rank.
+    pub ranking_in_tier: u32,
+    pub elo: u32,
+    pub last_change: i32,
+    pub highest_tier_name: String,
+    /// The act the highest rank was reached in, e.g. `e6a1`, if the API names one.
+    pub highest_season: Option<String>,
+}
+
+impl From<&MMRData> for Mmr {
+    fn from(mmr: &MMRData) -> Self {
+        Mmr {
+            puuid: mmr.puuid().to_string(),
+            name: mmr.name().to_string(),
+            tag: mmr.tag().to_string(),
+            tier: mmr.current_tier().id(),
+            tier_name: mmr.current_tier_patched().to_string(),
+            ranking_in_tier: mmr.ranking_in_tier(),
+            elo: mmr.elo(),
+            last_change: mmr.mmr_change_to_last_game(),
+            highest_tier_name: mmr.highest_tier_patched().to_string(),
+            highest_season: mmr.highest_rank_season().map(EpisodeAndAct::to_value),
+        }
+    }
+}
+
+#[derive(SimpleObject, Debug, Clone)]
+pub struct Match {
+    pub id: String,
+    pub map: String,
+    /// The mode ID, e.g. `competitive`. Empty for custom games.
+    pub mode: String,
+    /// RFC 3339 start time.
+    pub started_at: String,
+    pub players: Vec<Player>,
+}
+
+impl From<&MatchData> for Match {
+    fn from(game: &MatchData) -> Self {
+        Match {
+            id: game.match_id().to_string(),
+            map: game.map().to_string(),
+            mode: game.mode().id().to_string(),
+            started_at: game.started_at().to_rfc3339(),
+            players: game
+                .players()
+                .iter()
+                .map(|player| Player::new(game, player))
+                .collect(),
+        }
+    }
+}
+
+#[derive(SimpleObject, Debug, Clone)]
+pub struct Player {
+    pub puuid: String,
+    pub name: String,
+    pub tag: String,
+    pub team: String,
+    pub agent: String,
+    pub tier: u32,
+    pub party_id: String,
+    pub score: u32,
+    pub kills: u32,
+    pub deaths: u32,
+    pub assists: u32,
+    /// `WIN`, `LOSS` or `DRAW`, if the result is known.
+    pub result: Option<String>,
+}
+
+impl Player {
+    fn new(game: &MatchData, player: &MatchPlayer) -> Self {
+        let stats = player.stats();
+        Player {
+            puuid: player.puuid().to_string(),
+            name: player.name().to_string(),
+            tag: player.tag().to_string(),
+            team: player.team().to_string(),
+            agent: player.agent().to_string(),
+            tier: player.current_tier(),
+            party_id: player.party_id().to_string(),
+            score: stats.score(),
+            kills: stats.kills(),
+            deaths: stats.deaths(),
+            assists: stats.assists(),
+            result: game
+                .result_for(player.puuid())
+                .map(|outcome| format!("{:?}", outcome.result).to_uppercase()),
+        }
+    }
+}
+
+#[derive(SimpleObject, Debug, Clone)]
+pub struct LeaderboardPlayer {
+    pub rank: u32,
+    /// Empty for anonymized players.
+    pub puuid: String,
+    pub name: Option<String>,
+    pub tag: Option<String>,
+    pub ranked_rating: u32,
+    pub tier: u32,
+}
+
+impl From<&LeaderboardEntry> for LeaderboardPlayer {
+    fn from(entry: &LeaderboardEntry) -> Self {
+        let (puuid, name, tag) = match entry.identity() {
+            crate::ids::PlayerIdentity::Known { puuid, name, tag } => {
+                (puuid, Some(name), Some(tag))
+            }
+            crate::ids::PlayerIdentity::Anonymous => (String::new(), None, None),
+        };
+        LeaderboardPlayer {
+            rank: entry.leaderboard_rank(),
+            puuid,
+            name,
+            tag,
+            ranked_rating: entry.ranked_rating(),
+            tier: entry.competitive_tier(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::fixtures::{ACCOUNT, NOT_FOUND};
+    use crate::mock_server::{MockResponse, MockServer};
+
+    #[tokio::test]
+    async fn resolves_against_the_client() {
+        let server = MockServer::routes(vec![
+            ("/NitroSniper/NERD", MockResponse::json(200, ACCOUNT)),
+            ("/Missing/NERD", MockResponse::json(404, NOT_FOUND)),
+        ])
+        .await;
+        let url: &'static str = Box::leak(server.url().into_boxed_str());
+        let schema = schema(ValorantClient::new().change_api_endpoint(url));
+
+        let response = schema
+            .execute(
+                r#"{ account(name: "NitroSniper", tag: "NERD") { name tag region card { id } } }"#,
+            )
+            .await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+        let data = response.data.into_json().unwrap();
+        assert_eq!(data["account"]["tag"], "0000");
+        assert_eq!(data["account"]["region"], "EU");
+
+        let response = schema
+            .execute(r#"{ account(name: "Missing", tag: "NERD") { puuid } }"#)
+            .await;
+        let error = &response.errors[0];
+        let status = error.extensions.as_ref().and_then(|e| e.get("status"));
+        assert_eq!(status, Some(&async_graphql::Value::from(404)));
+    }
+}
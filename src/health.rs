@@ -0,0 +1,121 @@
+//! Startup and dashboard checks of whether the API is reachable and how fast it answers.
+
+use crate::{AccountRegion, ValorantApiType, ValorantClient};
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct HealthReport {
+    pub probes: Vec<Probe>,
+}
+
+impl HealthReport {
+    /// Whether every probe got a successful answer.
+    pub fn is_healthy(&self) -> bool {
+        self.probes
+            .iter()
+            .all(|probe| matches!(probe.outcome, ProbeOutcome::Up { .. }))
+    }
+
+    /// The slowest probe's latency.
+    pub fn latency(&self) -> Duration {
+        self.probes
+            .iter()
+            .map(|probe| probe.latency)
+            .max()
+            .unwrap_or_default()
+    }
+}
+
+/// One request of a health check.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Probe {
+    /// The probed route, e.g. `v1/version/eu`.
+    pub endpoint: String,
+    /// Until the response headers arrived, or the request failed.
+    pub latency: Duration,
+    pub outcome: ProbeOutcome,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ProbeOutcome {
+    Up {
+        status: u16,
+    },
+    /// The API answered with an error status, e.g. 401 for a rejected key.
+    Down {
+        status: u16,
+    },
+    /// No answer at all, with the transport error's description.
+    Unreachable(String),
+}
+
+impl ValorantClient<'_> {
+    /// Probes the version and status endpoints of `region` concurrently. The probes skip the
+    /// player cooldown but not the rate budget, since they count against the key's limit.
+    pub async fn health_check(&self, region: AccountRegion) -> HealthReport {
+        let version = ValorantApiType::Version { region }.to_url();
+        let status = format!("v1/status/{}", region.to_value());
+        let (version, status) = futures::join!(self.probe(version), self.probe(status));
+        HealthReport {
+            probes: vec![version, status],
+        }
+    }
+
+    async fn probe(&self, endpoint: String) -> Probe {
+        self.wait_for_budget().await;
+        let started = Instant::now();
+        let response = self.send_timed(&endpoint, self.get(&endpoint)).await;
+        let latency = started.elapsed();
+        let outcome = match response {
+            Ok(response) if response.status().is_success() => ProbeOutcome::Up {
+                status: response.status().as_u16(),
+            },
+            Ok(response) => ProbeOutcome::Down {
+                status: response.status().as_u16(),
+            },
+            Err(error) => ProbeOutcome::Unreachable(error.to_string()),
+        };
+        Probe {
+            endpoint,
+            latency,
+            outcome,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::fixtures::VERSION;
+    use crate::mock_server::{MockResponse, MockServer};
+
+    #[tokio::test]
+    async fn reports_each_probe() {
+        let server = MockServer::routes(vec![(
+            "/v1/version/eu",
+            MockResponse::json(200, VERSION).delay(Duration::from_millis(50)),
+        )])
+        .await;
+        let url = server.url();
+        let client = ValorantClient::new().change_api_endpoint(&url);
+
+        let report = client.health_check(AccountRegion::EU).await;
+        assert_eq!(report.probes[0].endpoint, "v1/version/eu");
+        assert_eq!(report.probes[0].outcome, ProbeOutcome::Up { status: 200 });
+        assert!(report.probes[0].latency >= Duration::from_millis(50));
+        // The mock server doesn't know the status route
+        assert_eq!(report.probes[1].outcome, ProbeOutcome::Down { status: 404 });
+        assert!(!report.is_healthy());
+        assert!(report.latency() >= report.probes[0].latency);
+
+        let client = ValorantClient::new().change_api_endpoint("http://127.0.0.1:1");
+        let report = client.health_check(AccountRegion::EU).await;
+        assert!(matches!(
+            report.probes[0].outcome,
+            ProbeOutcome::Unreachable(_)
+        ));
+    }
+}
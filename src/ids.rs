@@ -0,0 +1,374 @@
+use crate::normalize::fold;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum IdParseError {
+    MissingSeparator,
+    InvalidName(String),
+    InvalidTag(String),
+    InvalidPuuid(String),
+    InvalidMatchId(String),
+}
+
+impl fmt::Display for IdParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IdParseError::MissingSeparator => write!(f, "Riot ID is missing the '#' separator"),
+            IdParseError::InvalidName(name) => write!(f, "Invalid Riot ID name: {name:?}"),
+            IdParseError::InvalidTag(tag) => write!(f, "Invalid Riot ID tag: {tag:?}"),
+            IdParseError::InvalidPuuid(puuid) => write!(f, "Invalid PUUID: {puuid:?}"),
+            IdParseError::InvalidMatchId(id) => write!(f, "Invalid match ID: {id:?}"),
+        }
+    }
+}
+
+impl std::error::Error for IdParseError {}
+
+/// A player's `name#tag`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct RiotId {
+    name: String,
+    tag: String,
+}
+
+impl RiotId {
+    pub fn new(name: &str, tag: &str) -> Result<Self, IdParseError> {
+        let name_length = name.chars().count();
+        if !(1..=16).contains(&name_length) || name.contains('#') {
+            return Err(IdParseError::InvalidName(name.to_string()));
+        }
+        let tag_length = tag.chars().count();
+        if !(3..=5).contains(&tag_length) || !tag.chars().all(char::is_alphanumeric) {
+            return Err(IdParseError::InvalidTag(tag.to_string()));
+        }
+        Ok(RiotId {
+            name: name.to_string(),
+            tag: tag.to_string(),
+        })
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn tag(&self) -> &str {
+        &self.tag
+    }
+
+    /// Whether a user typed this ID as `typed`, ignoring case, surrounding whitespace and how
+    /// accents were entered, see [`fold`](crate::normalize::fold).
+    pub fn matches(&self, typed: &str) -> bool {
+        typed.rsplit_once('#').is_some_and(|(name, tag)| {
+            fold(name) == fold(&self.name) && fold(tag) == fold(&self.tag)
+        })
+    }
+}
+
+impl FromStr for RiotId {
+    type Err = IdParseError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let (name, tag) = value
+            .split_once('#')
+            .ok_or(IdParseError::MissingSeparator)?;
+        RiotId::new(name, tag)
+    }
+}
+
+impl fmt::Display for RiotId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}#{}", self.name, self.tag)
+    }
+}
+
+impl TryFrom<String> for RiotId {
+    type Error = IdParseError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<RiotId> for String {
+    fn from(id: RiotId) -> Self {
+        id.to_string()
+    }
+}
+
+/// The stable, UUID-shaped identifier of a player account.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct Puuid(String);
+
+impl Puuid {
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+pub(crate) fn is_uuid(value: &str) -> bool {
+    let bytes = value.as_bytes();
+    bytes.len() == 36
+        && bytes.iter().enumerate().all(|(index, byte)| match index {
+            8 | 13 | 18 | 23 => *byte == b'-',
+            _ => byte.is_ascii_hexdigit(),
+        })
+}
+
+impl FromStr for Puuid {
+    type Err = IdParseError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        if is_uuid(value) {
+            Ok(Puuid(value.to_ascii_lowercase()))
+        } else {
+            Err(IdParseError::InvalidPuuid(value.to_string()))
+        }
+    }
+}
+
+impl fmt::Display for Puuid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl TryFrom<String> for Puuid {
+    type Error = IdParseError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<Puuid> for String {
+    fn from(puuid: Puuid) -> Self {
+        puuid.0
+    }
+}
+
+/// The UUID of a match. A separate type from [`Puuid`], so the two can't be swapped.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct MatchId(String);
+
+impl MatchId {
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// The match's page on tracker.gg.
+    pub fn tracker_url(&self) -> String {
+        format!("https://tracker.gg/valorant/match/{}", self.0)
+    }
+
+    /// The match's details on the public API endpoint.
+    pub fn api_url(&self) -> String {
+        format!("{}/v2/match/{}", crate::DEFAULT_API_END_POINT, self.0)
+    }
+}
+
+impl FromStr for MatchId {
+    type Err = IdParseError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        if is_uuid(value) {
+            Ok(MatchId(value.to_ascii_lowercase()))
+        } else {
+            Err(IdParseError::InvalidMatchId(value.to_string()))
+        }
+    }
+}
+
+impl fmt::Display for MatchId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl TryFrom<String> for MatchId {
+    type Error = IdParseError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<MatchId> for String {
+    fn from(id: MatchId) -> Self {
+        id.0
+    }
+}
+
+/// Who a player is, for payloads that may hide it. Leaderboards and some match data anonymize
+/// players by blanking their name, tag and PUUID.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum PlayerIdentity {
+    Known {
+        puuid: String,
+        name: String,
+        tag: String,
+    },
+    Anonymous,
+}
+
+impl PlayerIdentity {
+    /// Builds an identity from raw API fields, treating a missing name as anonymized.
+    pub fn from_fields(puuid: &str, name: &str, tag: &str) -> Self {
+        if name.is_empty() {
+            PlayerIdentity::Anonymous
+        } else {
+            PlayerIdentity::Known {
+                puuid: puuid.to_string(),
+                name: name.to_string(),
+                tag: tag.to_string(),
+            }
+        }
+    }
+
+    pub fn is_anonymous(&self) -> bool {
+        matches!(self, PlayerIdentity::Anonymous)
+    }
+}
+
+impl fmt::Display for PlayerIdentity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PlayerIdentity::Known { name, tag, .. } => write!(f, "{name}#{tag}"),
+            PlayerIdentity::Anonymous => f.write_str("Anonymous"),
+        }
+    }
+}
+
+#[cfg(any(test, feature = "proptest"))]
+mod strategy {
+    use super::*;
+    use proptest::prelude::*;
+
+    impl Arbitrary for RiotId {
+        type Parameters = ();
+        type Strategy = BoxedStrategy<Self>;
+
+        fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+            ("[^#]{1,16}", "[A-Za-z0-9]{3,5}")
+                .prop_map(|(name, tag)| RiotId { name, tag })
+                .boxed()
+        }
+    }
+
+    impl Arbitrary for Puuid {
+        type Parameters = ();
+        type Strategy = BoxedStrategy<Self>;
+
+        fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+            "[0-9a-f]{8}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{12}"
+                .prop_map(Puuid)
+                .boxed()
+        }
+    }
+
+    impl Arbitrary for MatchId {
+        type Parameters = ();
+        type Strategy = BoxedStrategy<Self>;
+
+        fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+            "[0-9a-f]{8}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{12}"
+                .prop_map(MatchId)
+                .boxed()
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn parse_riot_id() {
+        let id: RiotId = "NitroSniper#NERD".parse().unwrap();
+        assert_eq!(id.name(), "NitroSniper");
+        assert_eq!(id.tag(), "NERD");
+        assert_eq!(id.to_string(), "NitroSniper#NERD");
+
+        assert_eq!(
+            "NitroSniper".parse::<RiotId>(),
+            Err(IdParseError::MissingSeparator)
+        );
+        assert!("NitroSniper#NE".parse::<RiotId>().is_err());
+        assert!("#NERD".parse::<RiotId>().is_err());
+        assert!("a#b#NERD".parse::<RiotId>().is_err());
+    }
+
+    #[test]
+    fn riot_ids_match_what_players_type() {
+        let id = RiotId::new("José Núñez", "NERD").unwrap();
+        assert!(id.matches("josé núñez#nerd"));
+        assert!(id.matches("  JOSE\u{301}  Nu\u{301}n\u{303}ez # Nerd "));
+        assert!(!id.matches("Jose Nunez#NERD"));
+        assert!(!id.matches("José Núñez"));
+    }
+
+    #[test]
+    fn parse_puuid() {
+        let puuid: Puuid = "B44ADAAE-ab83-5001-a296-89ea0de0bce3".parse().unwrap();
+        assert_eq!(puuid.as_str(), "b44adaae-ab83-5001-a296-89ea0de0bce3");
+
+        assert!("b44adaae-ab83-5001-a296".parse::<Puuid>().is_err());
+        assert!("b44adaae_ab83_5001_a296_89ea0de0bce3"
+            .parse::<Puuid>()
+            .is_err());
+        assert!(serde_json::from_str::<Puuid>(r#""not-a-puuid""#).is_err());
+    }
+
+    #[test]
+    fn parse_match_id() {
+        let id: MatchId = "0C6BD2A0-2c1f-4b87-9b1e-6f3f5d2a7e11".parse().unwrap();
+        assert_eq!(
+            id.tracker_url(),
+            "https://tracker.gg/valorant/match/0c6bd2a0-2c1f-4b87-9b1e-6f3f5d2a7e11"
+        );
+        assert_eq!(
+            id.api_url(),
+            "https://api.henrikdev.xyz/valorant/v2/match/0c6bd2a0-2c1f-4b87-9b1e-6f3f5d2a7e11"
+        );
+        assert_eq!(
+            "0c6bd2a0".parse::<MatchId>(),
+            Err(IdParseError::InvalidMatchId("0c6bd2a0".to_string()))
+        );
+    }
+
+    #[test]
+    fn player_identity() {
+        let known =
+            PlayerIdentity::from_fields("b44adaae-ab83-5001-a296-89ea0de0bce3", "Nitro", "NERD");
+        assert_eq!(known.to_string(), "Nitro#NERD");
+        assert!(!known.is_anonymous());
+
+        let hidden = PlayerIdentity::from_fields("", "", "");
+        assert!(hidden.is_anonymous());
+        assert_eq!(hidden.to_string(), "Anonymous");
+    }
+
+    proptest! {
+        #[test]
+        fn riot_id_round_trips(id in any::<RiotId>()) {
+            prop_assert_eq!(id.to_string().parse::<RiotId>(), Ok(id));
+        }
+
+        #[test]
+        fn puuid_round_trips(puuid in any::<Puuid>()) {
+            prop_assert_eq!(puuid.to_string().parse::<Puuid>(), Ok(puuid));
+        }
+
+        #[test]
+        fn parsers_never_panic(input in any::<String>()) {
+            let _ = input.parse::<RiotId>();
+            let _ = input.parse::<Puuid>();
+        }
+    }
+}
@@ -0,0 +1,630 @@
+//! The ranked leaderboard, plus an in-memory index over a full download of it.
+//!
+//! Large regions have tens of thousands of entries. [`ValorantClient::leaderboard_pages`]
+//! downloads them page by page, handing out a [`LeaderboardCursor`] with every page; saving
+//! the latest cursor lets a download that crashed resume from the next page.
+
+#[cfg(feature = "client")]
+use crate::error::ValorantError;
+use crate::ids::PlayerIdentity;
+use crate::mmr_data::EpisodeAndAct;
+use crate::{AccountRegion, ValorantAPIData};
+#[cfg(feature = "client")]
+use crate::{ValorantApiType, ValorantClient};
+use chrono::{DateTime, Utc};
+#[cfg(feature = "client")]
+use futures::Stream;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::ops::RangeInclusive;
+use std::path::Path;
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Leaderboard {
+    last_update: u64,
+    next_update: u64,
+    total_players: u32,
+    players: Vec<LeaderboardEntry>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct LeaderboardEntry {
+    #[serde(rename = "PlayerCardID")]
+    player_card_id: String,
+    #[serde(rename = "TitleID")]
+    title_id: String,
+    #[serde(rename = "IsBanned")]
+    is_banned: bool,
+    #[serde(rename = "IsAnonymized")]
+    is_anonymized: bool,
+    #[serde(default)]
+    puuid: String,
+    #[serde(rename = "gameName", default)]
+    game_name: String,
+    #[serde(rename = "tagLine", default)]
+    tag_line: String,
+    #[serde(rename = "leaderboardRank")]
+    leaderboard_rank: u32,
+    #[serde(rename = "rankedRating")]
+    ranked_rating: u32,
+    #[serde(rename = "numberOfWins")]
+    number_of_wins: u32,
+    #[serde(rename = "competitiveTier")]
+    competitive_tier: u32,
+}
+
+impl Leaderboard {
+    pub fn last_update(&self) -> DateTime<Utc> {
+        DateTime::from_timestamp(self.last_update as i64, 0).unwrap_or_default()
+    }
+
+    /// When the API expects to refresh the leaderboard.
+    pub fn next_update(&self) -> DateTime<Utc> {
+        DateTime::from_timestamp(self.next_update as i64, 0).unwrap_or_default()
+    }
+
+    /// Players on the whole leaderboard, not only on this page.
+    pub fn total_players(&self) -> u32 {
+        self.total_players
+    }
+
+    pub fn players(&self) -> &[LeaderboardEntry] {
+        &self.players
+    }
+}
+
+impl LeaderboardEntry {
+    pub fn identity(&self) -> PlayerIdentity {
+        if self.is_anonymized {
+            PlayerIdentity::Anonymous
+        } else {
+            PlayerIdentity::from_fields(&self.puuid, &self.game_name, &self.tag_line)
+        }
+    }
+
+    /// `None` when the player's card is hidden.
+    pub fn player_card_id(&self) -> Option<&str> {
+        Some(self.player_card_id.as_str()).filter(|id| !id.is_empty())
+    }
+
+    /// `None` when the player's title is hidden.
+    pub fn title_id(&self) -> Option<&str> {
+        Some(self.title_id.as_str()).filter(|id| !id.is_empty())
+    }
+
+    pub fn leaderboard_rank(&self) -> u32 {
+        self.leaderboard_rank
+    }
+
+    pub fn ranked_rating(&self) -> u32 {
+        self.ranked_rating
+    }
+
+    pub fn competitive_tier(&self) -> u32 {
+        self.competitive_tier
+    }
+
+    pub fn number_of_wins(&self) -> u32 {
+        self.number_of_wins
+    }
+
+    /// Anonymized players keep their rank and rating but hide their identity.
+    pub fn is_anonymized(&self) -> bool {
+        self.is_anonymized
+    }
+}
+
+impl ValorantAPIData for Leaderboard {}
+
+/// A downloaded leaderboard indexed by PUUID and Riot ID, so repeated lookups don't re-fetch
+/// the whole board.
+#[derive(Debug, Clone, Default)]
+pub struct LeaderboardIndex {
+    entries: Vec<LeaderboardEntry>,
+    by_puuid: HashMap<String, usize>,
+    by_riot_id: HashMap<String, usize>,
+}
+
+impl LeaderboardIndex {
+    pub fn new(leaderboard: Leaderboard) -> Self {
+        let mut entries = leaderboard.players;
+        entries.sort_by_key(|entry| entry.leaderboard_rank);
+
+        let mut by_puuid = HashMap::new();
+        let mut by_riot_id = HashMap::new();
+        for (position, entry) in entries.iter().enumerate() {
+            // Anonymized players have no usable identity to look them up by
+            if let PlayerIdentity::Known { puuid, name, tag } = entry.identity() {
+                by_puuid.insert(puuid.to_ascii_lowercase(), position);
+                by_riot_id.insert(riot_id_key(&name, &tag), position);
+            }
+        }
+
+        LeaderboardIndex {
+            entries,
+            by_puuid,
+            by_riot_id,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn entries(&self) -> &[LeaderboardEntry] {
+        &self.entries
+    }
+
+    pub fn by_puuid(&self, puuid: &str) -> Option<&LeaderboardEntry> {
+        self.by_puuid
+            .get(&puuid.to_ascii_lowercase())
+            .map(|&position| &self.entries[position])
+    }
+
+    /// Case-insensitive lookup by Riot ID.
+    pub fn by_name(&self, name: &str, tag: &str) -> Option<&LeaderboardEntry> {
+        self.by_riot_id
+            .get(&riot_id_key(name, tag))
+            .map(|&position| &self.entries[position])
+    }
+
+    /// Entries whose leaderboard position falls inside `ranks`, e.g. `1..=500`.
+    pub fn rank_range(&self, ranks: RangeInclusive<u32>) -> &[LeaderboardEntry] {
+        let start = self
+            .entries
+            .partition_point(|entry| entry.leaderboard_rank < *ranks.start());
+        let end = self
+            .entries
+            .partition_point(|entry| entry.leaderboard_rank <= *ranks.end());
+        &self.entries[start..end.max(start)]
+    }
+
+    /// Entries with at least `threshold` RR.
+    pub fn rr_at_least(&self, threshold: u32) -> impl Iterator<Item = &LeaderboardEntry> {
+        self.entries
+            .iter()
+            .filter(move |entry| entry.ranked_rating >= threshold)
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> std::io::Result<()> {
+        let json = serde_json::to_vec(&self.entries)?;
+        std::fs::write(path, json)
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> std::io::Result<Self> {
+        let json = std::fs::read(path)?;
+        let players = serde_json::from_slice(&json)?;
+        Ok(LeaderboardIndex::new(Leaderboard {
+            last_update: 0,
+            next_update: 0,
+            total_players: 0,
+            players,
+        }))
+    }
+}
+
+impl ValorantAPIData for LeaderboardIndex {}
+
+/// The ranks whose entry is decided by leaderboard RR rather than by a fixed RR cutoff.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RankCutoff {
+    Immortal1,
+    Immortal2,
+    Immortal3,
+    Radiant,
+}
+
+impl RankCutoff {
+    pub const ALL: [RankCutoff; 4] = [
+        RankCutoff::Immortal1,
+        RankCutoff::Immortal2,
+        RankCutoff::Immortal3,
+        RankCutoff::Radiant,
+    ];
+
+    /// The `competitiveTier` number of this rank.
+    pub fn tier(self) -> u32 {
+        match self {
+            RankCutoff::Immortal1 => 24,
+            RankCutoff::Immortal2 => 25,
+            RankCutoff::Immortal3 => 26,
+            RankCutoff::Radiant => 27,
+        }
+    }
+}
+
+/// How far a player is from one cutoff.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CutoffDistance {
+    pub cutoff: RankCutoff,
+    /// The lowest RR currently holding the rank.
+    pub threshold: u32,
+    /// RR still needed to reach the threshold, zero if the player is already at or above it.
+    pub rr_needed: u32,
+}
+
+impl LeaderboardIndex {
+    /// The RR currently needed for a rank, taken as the lowest RR of anyone holding it or
+    /// better. `None` if nobody on the board has the rank.
+    pub fn threshold(&self, cutoff: RankCutoff) -> Option<u32> {
+        self.entries
+            .iter()
+            .filter(|entry| entry.competitive_tier >= cutoff.tier())
+            .map(|entry| entry.ranked_rating)
+            .min()
+    }
+
+    /// Distance from `rr` to every cutoff that currently has a threshold.
+    pub fn cutoff_distances(&self, rr: u32) -> Vec<CutoffDistance> {
+        RankCutoff::ALL
+            .into_iter()
+            .filter_map(|cutoff| {
+                let threshold = self.threshold(cutoff)?;
+                Some(CutoffDistance {
+                    cutoff,
+                    threshold,
+                    rr_needed: threshold.saturating_sub(rr),
+                })
+            })
+            .collect()
+    }
+}
+
+/// Entries per page of a paged download unless [`LeaderboardCursor::page_size`] says otherwise.
+pub const DEFAULT_PAGE_SIZE: u32 = 1000;
+
+/// How far a paged leaderboard download got.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct LeaderboardCursor {
+    region: AccountRegion,
+    season: Option<EpisodeAndAct>,
+    next_page: u32,
+    page_size: u32,
+    fetched: u32,
+    done: bool,
+}
+
+impl LeaderboardCursor {
+    /// A cursor at the first page of the region's leaderboard, of the current act unless a
+    /// season is given.
+    pub fn new(region: AccountRegion, season: Option<EpisodeAndAct>) -> Self {
+        LeaderboardCursor {
+            region,
+            season,
+            next_page: 1,
+            page_size: DEFAULT_PAGE_SIZE,
+            fetched: 0,
+            done: false,
+        }
+    }
+
+    /// Entries per page. A size of 0 is raised to 1, as an empty page would never finish the
+    /// download.
+    pub fn page_size(mut self, size: u32) -> Self {
+        self.page_size = size.max(1);
+        self
+    }
+
+    pub fn next_page(&self) -> u32 {
+        self.next_page
+    }
+
+    /// Entries downloaded so far.
+    pub fn fetched(&self) -> u32 {
+        self.fetched
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.done
+    }
+
+    #[cfg(feature = "client")]
+    fn advance(&mut self, leaderboard: &Leaderboard) {
+        let returned = leaderboard.players.len() as u32;
+        self.fetched += returned;
+        self.next_page += 1;
+        // An empty page ends the download even when the reported total says otherwise
+        self.done =
+            returned == 0 || returned < self.page_size || self.fetched >= leaderboard.total_players;
+    }
+}
+
+/// One page of a paged download, with the cursor to save once the entries are stored.
+#[derive(Debug, Clone)]
+pub struct LeaderboardPage {
+    pub entries: Vec<LeaderboardEntry>,
+    pub cursor: LeaderboardCursor,
+}
+
+#[cfg(feature = "client")]
+impl ValorantClient<'_> {
+    /// Fetches the page `cursor` points at and moves it to the next one. `None` once the
+    /// whole leaderboard was downloaded. On an error the cursor stays where it was.
+    pub async fn next_leaderboard_page(
+        &self,
+        cursor: &mut LeaderboardCursor,
+    ) -> Result<Option<Vec<LeaderboardEntry>>, ValorantError> {
+        if cursor.done {
+            return Ok(None);
+        }
+        let leaderboard = self
+            .request::<Leaderboard>(ValorantApiType::Leaderboard {
+                region: cursor.region,
+                season: cursor.season.clone(),
+                page: Some(cursor.next_page),
+                size: Some(cursor.page_size),
+            })
+            .await?;
+        cursor.advance(&leaderboard);
+        Ok(Some(leaderboard.players))
+    }
+
+    /// Streams the pages left after `cursor`, ending after the last page or the first error.
+    /// Passing the cursor of the last page that was stored resumes an interrupted download.
+    pub fn leaderboard_pages(
+        &self,
+        cursor: LeaderboardCursor,
+    ) -> impl Stream<Item = Result<LeaderboardPage, ValorantError>> + '_ {
+        futures::stream::try_unfold(cursor, move |mut cursor| async move {
+            let Some(entries) = self.next_leaderboard_page(&mut cursor).await? else {
+                return Ok(None);
+            };
+            let page = LeaderboardPage {
+                entries,
+                cursor: cursor.clone(),
+            };
+            Ok(Some((page, cursor)))
+        })
+    }
+}
+
+impl From<Leaderboard> for LeaderboardIndex {
+    fn from(leaderboard: Leaderboard) -> Self {
+        LeaderboardIndex::new(leaderboard)
+    }
+}
+
+fn riot_id_key(name: &str, tag: &str) -> String {
+    format!("{}#{}", name.to_lowercase(), tag.to_lowercase())
+}
+
+#[cfg(any(test, feature = "test-support"))]
+pub(crate) mod fake {
+    use super::*;
+    use crate::fixtures::{decode, LEADERBOARD};
+
+    /// Builds a leaderboard for tests from the recorded five player board.
+    #[derive(Debug)]
+    pub struct FakeLeaderboard {
+        data: Leaderboard,
+    }
+
+    impl FakeLeaderboard {
+        pub fn recorded() -> Self {
+            let data: Leaderboard = decode(LEADERBOARD);
+            FakeLeaderboard { data }
+        }
+
+        /// Keeps `len` players from `start`, as a page of the whole board.
+        pub fn page(mut self, start: usize, len: usize) -> Self {
+            self.data.players = self.data.players.drain(..).skip(start).take(len).collect();
+            self
+        }
+
+        pub fn build(self) -> Leaderboard {
+            self.data
+        }
+
+        /// The body the leaderboard endpoint answers with.
+        pub fn response(&self) -> String {
+            crate::fake::success(&self.data)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    #[cfg(feature = "client")]
+    use crate::fake::FakeLeaderboard;
+    use crate::fixtures::{decode, LEADERBOARD};
+    #[cfg(feature = "client")]
+    use crate::mock_server::{MockResponse, MockServer};
+    #[cfg(feature = "client")]
+    use crate::retry::RetryHint;
+    #[cfg(feature = "client")]
+    use crate::validation::ValidationError;
+    use crate::{AccountRegion, ValorantApiType};
+    #[cfg(feature = "client")]
+    use futures::StreamExt;
+
+    fn index() -> LeaderboardIndex {
+        LeaderboardIndex::new(decode::<Leaderboard>(LEADERBOARD))
+    }
+
+    #[test]
+    fn lookups() {
+        let index = index();
+        assert_eq!(index.len(), 5);
+
+        let entry = index.by_name("NITRO", "nerd").unwrap();
+        assert_eq!(entry.leaderboard_rank(), 2);
+        assert_eq!(
+            index
+                .by_puuid("5A4C1F0E-7d2b-5e93-a1c4-3f6b8e2d9c10")
+                .unwrap()
+                .leaderboard_rank(),
+            2
+        );
+        assert!(index.by_name("Nobody", "NONE").is_none());
+    }
+
+    #[test]
+    fn pages_are_part_of_the_url() {
+        let page = |season, page, size| {
+            ValorantApiType::Leaderboard {
+                region: AccountRegion::EU,
+                season,
+                page,
+                size,
+            }
+            .to_url()
+        };
+        assert_eq!(page(None, None, None), "v2/leaderboard/eu");
+        assert_eq!(
+            page(None, Some(2), Some(500)),
+            "v2/leaderboard/eu?page=2&size=500"
+        );
+        assert_eq!(
+            page(Some(EpisodeAndAct::from_numbers(6, 1)), Some(1), None),
+            "v2/leaderboard/eu?season=e6a1&page=1"
+        );
+    }
+
+    #[cfg(feature = "client")]
+    #[tokio::test]
+    async fn paged_downloads_resume_from_their_cursor() {
+        let server = MockServer::start(vec![
+            MockResponse::json(200, &FakeLeaderboard::recorded().page(0, 2).response()),
+            MockResponse::json(503, r#"{"status":503,"errors":[]}"#),
+            MockResponse::json(200, &FakeLeaderboard::recorded().page(2, 2).response()),
+            MockResponse::json(200, &FakeLeaderboard::recorded().page(4, 2).response()),
+        ])
+        .await;
+        let url = server.url();
+        let client = ValorantClient::new().change_api_endpoint(&url);
+        let cursor = LeaderboardCursor::new(AccountRegion::EU, None).page_size(2);
+
+        let mut pages = Box::pin(client.leaderboard_pages(cursor));
+        let first = pages.next().await.unwrap().unwrap();
+        assert_eq!(first.entries.len(), 2);
+        assert!(pages.next().await.unwrap().unwrap_err().is_retryable());
+        assert!(pages.next().await.is_none());
+        drop(pages);
+
+        // The checkpoint survives a restart
+        let saved = serde_json::to_string(&first.cursor).unwrap();
+        let cursor: LeaderboardCursor = serde_json::from_str(&saved).unwrap();
+        assert_eq!((cursor.next_page(), cursor.fetched()), (2, 2));
+        let rest: Vec<_> = client
+            .leaderboard_pages(cursor)
+            .map(|page| page.unwrap())
+            .collect()
+            .await;
+        let ranks: Vec<_> = rest
+            .iter()
+            .flat_map(|page| &page.entries)
+            .map(LeaderboardEntry::leaderboard_rank)
+            .collect();
+        assert_eq!(ranks, [3, 4, 5]);
+        assert!(rest[1].cursor.is_done());
+        assert_eq!(rest[1].cursor.fetched(), 5);
+
+        let paths: Vec<_> = server.requests().into_iter().map(|r| r.path).collect();
+        assert_eq!(
+            paths,
+            [
+                "/v2/leaderboard/eu?page=1&size=2",
+                "/v2/leaderboard/eu?page=2&size=2",
+                "/v2/leaderboard/eu?page=2&size=2",
+                "/v2/leaderboard/eu?page=3&size=2",
+            ]
+        );
+    }
+
+    #[cfg(feature = "client")]
+    #[tokio::test]
+    async fn empty_pages_end_the_download() {
+        let server = MockServer::start(vec![MockResponse::json(
+            200,
+            &FakeLeaderboard::recorded().page(5, 2).response(),
+        )])
+        .await;
+        let url = server.url();
+        let client = ValorantClient::new().change_api_endpoint(&url);
+
+        let cursor = LeaderboardCursor::new(AccountRegion::EU, None).page_size(2);
+        let pages: Vec<_> = client.leaderboard_pages(cursor).collect().await;
+        let [Ok(page)] = &pages[..] else {
+            panic!("expected a single page, got {pages:?}");
+        };
+        assert!(page.entries.is_empty() && page.cursor.is_done());
+
+        // Saved cursors can't ask for empty pages either
+        let mut saved =
+            serde_json::to_value(LeaderboardCursor::new(AccountRegion::EU, None)).unwrap();
+        saved["page_size"] = 0.into();
+        let mut cursor: LeaderboardCursor = serde_json::from_value(saved).unwrap();
+        let error = client.next_leaderboard_page(&mut cursor).await.unwrap_err();
+        assert!(matches!(
+            error,
+            ValorantError::InvalidInput(ValidationError::EmptyPageSize)
+        ));
+        assert_eq!(server.requests().len(), 1);
+    }
+
+    #[test]
+    fn ranges() {
+        let index = index();
+        let ranks = index
+            .rank_range(2..=3)
+            .iter()
+            .map(LeaderboardEntry::leaderboard_rank)
+            .collect::<Vec<_>>();
+        assert_eq!(ranks, [2, 3]);
+        assert!(index.rank_range(10..=20).is_empty());
+
+        assert_eq!(index.rr_at_least(500).count(), 2);
+    }
+
+    #[test]
+    fn anonymized_players() {
+        let index = index();
+        let hidden = &index.rank_range(3..=3)[0];
+        assert_eq!(hidden.identity(), PlayerIdentity::Anonymous);
+        assert_eq!(hidden.title_id(), None);
+        assert!(hidden.player_card_id().is_some());
+        assert!(hidden.is_anonymized());
+
+        assert!(!index.rank_range(1..=1)[0].identity().is_anonymous());
+    }
+
+    #[test]
+    fn thresholds() {
+        let index = index();
+        assert_eq!(index.threshold(RankCutoff::Radiant), Some(562));
+        assert_eq!(index.threshold(RankCutoff::Immortal3), Some(431));
+        assert_eq!(index.threshold(RankCutoff::Immortal1), Some(97));
+
+        let entry = index.by_name("Climber", "EUW").unwrap();
+        let distances = index.cutoff_distances(entry.ranked_rating());
+        assert_eq!(
+            distances
+                .iter()
+                .map(|distance| (distance.cutoff, distance.rr_needed))
+                .collect::<Vec<_>>(),
+            [
+                (RankCutoff::Immortal1, 0),
+                (RankCutoff::Immortal2, 0),
+                (RankCutoff::Immortal3, 217),
+                (RankCutoff::Radiant, 348),
+            ]
+        );
+    }
+
+    #[test]
+    fn persistence() {
+        let index = index();
+        let path = std::env::temp_dir().join(format!("leaderboard-{}.json", std::process::id()));
+        index.save(&path).unwrap();
+        let loaded = LeaderboardIndex::load(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded.len(), index.len());
+        assert!(loaded.by_name("Nitro", "NERD").is_some());
+    }
+}
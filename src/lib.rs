@@ -1,6 +1,56 @@
-use prelude::EpisodeAndAct;
+#[cfg(feature = "client")]
+use api_version::ApiVersions;
+#[cfg(feature = "client")]
+use cache::{CachedResponse, MemoryCache, ResponseCache};
+#[cfg(feature = "client")]
+use content::Content;
+#[cfg(feature = "client")]
+use deadline::Deadline;
+#[cfg(feature = "client")]
+use error::ValorantError;
+#[cfg(feature = "client")]
+use futures::stream::{self, StreamExt};
+#[cfg(feature = "client")]
+use leaderboard::{Leaderboard, LeaderboardIndex};
+#[cfg(feature = "client")]
+use match_data::MatchData;
+#[cfg(feature = "client")]
+use mmr_history::{MMRChange, MMRHistoryEntry};
+#[cfg(feature = "client")]
+use mmr_data::{ActLeg, ActRankStats};
+use mmr_data::{EpisodeAndAct, SeasonFilter};
+#[cfg(feature = "client")]
+use outcome::{LegFailure, PartialOutcome};
+#[cfg(feature = "client")]
+use rate_limit::{KeyLimits, RateBudget, ResponseMeta};
+use routing::Platform;
+#[cfg(feature = "client")]
+use retry::{RetryHint, RetryPolicy};
+#[cfg(feature = "client")]
+use sanity::SanityHook;
+use sanity::SanityWarning;
+#[cfg(feature = "client")]
+use signing::RequestSigner;
+#[cfg(feature = "client")]
+use stats::StatsRecorder;
+#[cfg(feature = "client")]
+use store::NightMarket;
+#[cfg(feature = "client")]
+use telemetry::{RequestSpan, Telemetry, TraceContext};
 //#![warn(missing_docs)]
-use serde::{de::DeserializeOwned, Deserialize, Serialize};
+#[cfg(feature = "client")]
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "client")]
+use std::collections::HashMap;
+#[cfg(feature = "client")]
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+#[cfg(feature = "client")]
+use std::time::Instant;
+
+#[cfg(feature = "client")]
+pub use reqwest::tls::{Certificate, Identity, Version as TlsVersion};
 
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(untagged)]
@@ -11,249 +61,2668 @@ pub enum ApiResponse<T: ValorantAPIData> {
 
 #[derive(Serialize, Deserialize, Debug)]
 pub struct ApiError {
-    message: String,
-    code: u32,
-    details: String,
+    pub(crate) message: String,
+    pub(crate) code: u32,
+    pub(crate) details: String,
+    // From the response's Retry-After header, which the body doesn't repeat
+    #[serde(skip)]
+    #[cfg_attr(not(feature = "client"), allow(dead_code))]
+    pub(crate) retry_after: Option<Duration>,
 }
 
-pub trait ValorantAPIData {}
+impl<T: ValorantAPIData> ApiResponse<T> {
+    /// The data of a success, or the errors of a failure.
+    pub fn into_result(self) -> Result<T, Vec<ApiError>> {
+        match self {
+            ApiResponse::Success { data, .. } => Ok(data),
+            ApiResponse::Failure { errors, .. } => Err(errors),
+        }
+    }
 
-#[derive(Serialize, Deserialize, Debug)]
+    pub fn data(&self) -> Option<&T> {
+        match self {
+            ApiResponse::Success { data, .. } => Some(data),
+            ApiResponse::Failure { .. } => None,
+        }
+    }
+
+    /// The errors of a failure, empty for a success.
+    pub fn errors(&self) -> &[ApiError] {
+        match self {
+            ApiResponse::Success { .. } => &[],
+            ApiResponse::Failure { errors, .. } => errors,
+        }
+    }
+
+    pub fn status(&self) -> u32 {
+        match self {
+            ApiResponse::Success { status, .. } | ApiResponse::Failure { status, .. } => *status,
+        }
+    }
+}
+
+impl ApiError {
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    pub fn code(&self) -> u32 {
+        self.code
+    }
+
+    pub fn details(&self) -> &str {
+        &self.details
+    }
+}
+
+/// A payload the client knows how to decode. The trait is sealed: only the crate's models
+/// implement it, so it can gain methods without a breaking release.
+pub trait ValorantAPIData: sealed::Sealed {
+    /// Broken invariants in the payload that hint at upstream data corruption. Models without
+    /// invariants to check report none.
+    fn sanity_warnings(&self) -> Vec<SanityWarning> {
+        Vec::new()
+    }
+}
+
+impl<T: ValorantAPIData> ValorantAPIData for Vec<T> {
+    fn sanity_warnings(&self) -> Vec<SanityWarning> {
+        sanity::check_all(self)
+    }
+}
+
+impl<T: ValorantAPIData> ValorantAPIData for Option<T> {
+    fn sanity_warnings(&self) -> Vec<SanityWarning> {
+        self.as_ref().map_or_else(Vec::new, T::sanity_warnings)
+    }
+}
+
+mod sealed {
+    use crate::ValorantAPIData;
+
+    pub trait Sealed {}
+
+    macro_rules! seal {
+        ($($model:ty),* $(,)?) => {
+            $(impl Sealed for $model {})*
+        };
+    }
+
+    // Every model the client can decode, in one place so a new one is a deliberate addition
+    seal!(
+        crate::account_data::AccountData,
+        crate::account_data::AccountDataV2,
+        crate::assets::PlayerCard,
+        crate::assets::PlayerTitle,
+        crate::assets::Season,
+        crate::calendar::ActCalendar,
+        crate::competitive_updates::CompetitiveUpdate,
+        crate::competitive_updates::CompetitiveUpdates,
+        crate::content::Content,
+        crate::esports::EsportsEvent,
+        crate::leaderboard::Leaderboard,
+        crate::leaderboard::LeaderboardIndex,
+        crate::match_data::MatchData,
+        crate::match_kind::MatchKind,
+        crate::mmr_data::ActRankStats,
+        crate::mmr_data::MMRData,
+        crate::mmr_data::MMRDataV3,
+        crate::mmr_history::MMRChange,
+        crate::mmr_history::MMRHistoryEntry,
+        crate::mmr_history::MMRHistoryEntryV2,
+        crate::news::NewsArticle,
+        crate::store::FeaturedBundle,
+        crate::store::NightMarket,
+        crate::stored_matches::StoredMatch,
+        crate::stored_matches::StoredMatchesPage,
+        crate::version::GameVersion,
+    );
+
+    #[cfg(feature = "client")]
+    impl Sealed for crate::mmr_data::ActLeg {}
+    #[cfg(feature = "client")]
+    impl Sealed for crate::sync::PlayerSync {}
+
+    impl<T: ValorantAPIData> Sealed for Vec<T> {}
+    impl<T: ValorantAPIData> Sealed for Option<T> {}
+    impl<T: ValorantAPIData> Sealed for crate::outcome::PartialOutcome<T> {}
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "graphql", derive(async_graphql::Enum))]
 #[serde(rename_all = "lowercase")]
+#[non_exhaustive]
 pub enum AccountRegion {
     EU,
     NA,
+    /// Asia-Pacific. Named `as` by earlier versions of the API, which is still accepted.
+    #[serde(alias = "as")]
+    AP,
     KR,
-    AS,
+    /// Brazil.
+    BR,
+    /// Latin America.
+    LATAM,
 }
-impl AccountRegion {
-    fn to_value(&self) -> String {
-        match self {
-            AccountRegion::EU => "eu",
-            AccountRegion::NA => "na",
-            AccountRegion::KR => "kr",
-            AccountRegion::AS => "as",
-        }
-        .to_string()
-    }
+const DEFAULT_API_END_POINT: &str = "https://api.henrikdev.xyz/valorant";
+
+/// The environment variable the API key is read from when no key is set explicitly.
+#[cfg(feature = "client")]
+pub const API_KEY_VAR: &str = "HENRIKDEV_API_KEY";
+
+#[cfg(feature = "client")]
+fn env_api_key() -> Option<String> {
+    let key = std::env::var(API_KEY_VAR).ok()?;
+    let key = key.trim();
+    (!key.is_empty()).then(|| key.to_string())
 }
 
+#[cfg(feature = "client")]
 pub struct ValorantClient<'a> {
     api_end_point: &'a str,
+    http: reqwest::Client,
+    profile: DeploymentProfile,
+    api_key: RwLock<Option<String>>,
+    on_auth_error: Option<AuthErrorHook>,
+    current_act: RwLock<Option<(Instant, EpisodeAndAct)>>,
+    game_version: RwLock<Option<String>>,
+    rate_budget: Option<RateBudget>,
+    key_limits: KeyLimits,
+    retry_policy: Option<RetryPolicy>,
+    player_cooldown: Option<Duration>,
+    response_cache: Arc<dyn ResponseCache>,
+    on_suspicious_data: Option<SanityHook>,
+    versions: ApiVersions,
+    stats: StatsRecorder,
+    stored_page_limit: RwLock<u32>,
+    telemetry: Telemetry,
+    request_signer: Option<Arc<dyn RequestSigner>>,
+    #[cfg(any(test, feature = "test-support"))]
+    failure_injector: Option<chaos::FailureInjector>,
 }
 
+#[cfg(feature = "client")]
+fn replay_response(status: reqwest::StatusCode, body: Vec<u8>) -> reqwest::Response {
+    let mut response = http::Response::new(body);
+    *response.status_mut() = status;
+    response.into()
+}
+
+// Acts change every couple of months, so the live act is only looked up once an hour
+#[cfg(feature = "client")]
+const CURRENT_ACT_TTL: Duration = Duration::from_secs(60 * 60);
+
+/// Called when the API rejects the configured key. Returning a new key replaces the current one
+/// and replays the failed request once.
+#[cfg(feature = "client")]
+pub type AuthErrorHook = Arc<dyn Fn(&AuthErrorContext<'_>) -> Option<String> + Send + Sync>;
+
+#[cfg(feature = "client")]
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct AuthErrorContext<'a> {
+    pub status: reqwest::StatusCode,
+    pub rejected_key: Option<&'a str>,
+}
+
+#[cfg(feature = "client")]
 impl<'a> ValorantClient<'a> {
+    /// A client for the public API, authenticated with the key in [`API_KEY_VAR`] if it is set.
     pub fn new() -> Self {
         ValorantClient::default()
     }
 
+    pub fn builder() -> ValorantClientBuilder<'a> {
+        ValorantClientBuilder::new()
+    }
+
     pub fn change_api_endpoint(mut self, endpoint: &'a str) -> Self {
         self.api_end_point = endpoint;
         self
     }
 
-    pub async fn request<T>(
+    /// Sends `key` with every request, in place of the one from [`API_KEY_VAR`].
+    pub fn with_api_key(self, key: impl Into<String>) -> Self {
+        *self.api_key.write().unwrap() = Some(key.into());
+        self
+    }
+
+    /// Sends every request through `http`, e.g. a client shared with the rest of the
+    /// application so they pool connections together. Clones of a `reqwest::Client` share one
+    /// pool.
+    pub fn with_http_client(mut self, http: reqwest::Client) -> Self {
+        self.http = http;
+        self
+    }
+
+    /// Sends a request and unwraps its data, telling network, payload, rate limit, API and
+    /// input failures apart. Requests failing [`ValorantApiType::validate`] fail with
+    /// [`ValorantError::InvalidInput`] without being sent.
+    pub async fn request<T>(&self, api_type: ValorantApiType<'_>) -> Result<T, ValorantError>
+    where
+        T: DeserializeOwned + ValorantAPIData,
+    {
+        api_type.validate()?;
+        let api_type = self.resolve_season_filter(api_type).await?;
+        self.send(&api_type).await
+    }
+
+    /// Sends a request and returns the status and body without decoding them, for callers that
+    /// archive or forward responses. Failures the API answers with are returned like any
+    /// other answer.
+    pub async fn request_raw(
         &self,
         api_type: ValorantApiType<'_>,
+    ) -> Result<(u16, Vec<u8>), ValorantError> {
+        api_type.validate()?;
+        let api_type = self.resolve_season_filter(api_type).await?;
+        let response = self.send_authorized(&api_type).await?;
+        let status = response.status().as_u16();
+        Ok((status, response.bytes().await?.to_vec()))
+    }
+
+    async fn send<T>(&self, api_type: &ValorantApiType<'_>) -> Result<T, ValorantError>
+    where
+        T: DeserializeOwned + ValorantAPIData,
+    {
+        error::into_data(self.send_enveloped(api_type).await?)
+    }
+
+    // Keeps the API's envelope, for composite helpers that report failed legs next to the data
+    async fn send_enveloped<T>(
+        &self,
+        api_type: &ValorantApiType<'_>,
+    ) -> Result<ApiResponse<T>, reqwest::Error>
+    where
+        T: DeserializeOwned + ValorantAPIData,
+    {
+        let response = self.send_cached::<T>(api_type).await?;
+        let hook = self.on_suspicious_data.as_ref();
+        if let (Some(hook), ApiResponse::Success { data, .. }) = (hook, &response) {
+            let warnings = data.sanity_warnings();
+            if !warnings.is_empty() {
+                hook(&self.url(&self.versions.route(api_type).0), &warnings);
+            }
+        }
+        Ok(response)
+    }
+
+    async fn send_cached<T>(
+        &self,
+        api_type: &ValorantApiType<'_>,
+    ) -> Result<ApiResponse<T>, reqwest::Error>
+    where
+        T: DeserializeOwned + ValorantAPIData,
+    {
+        let cooldown_key = self.cooldown_key(api_type);
+        if let Some(key) = &cooldown_key {
+            if let Some(recent) = self.recent_response(key).await {
+                return self.decode(api_type, recent).await;
+            }
+        }
+
+        let response = self.send_authorized(api_type).await?;
+        let status = response.status();
+        match cooldown_key {
+            Some(key)
+                if !status.is_server_error() && status != reqwest::StatusCode::TOO_MANY_REQUESTS =>
+            {
+                let body = response.bytes().await?.to_vec();
+                let replay = replay_response(status, body.clone());
+                self.remember_response(key, status, body).await;
+                self.decode(api_type, replay).await
+            }
+            _ => self.decode(api_type, response).await,
+        }
+    }
+
+    // Payloads of pinned versions are converted to the shape of the default version first
+    async fn decode<T>(
+        &self,
+        api_type: &ValorantApiType<'_>,
+        response: reqwest::Response,
     ) -> Result<ApiResponse<T>, reqwest::Error>
     where
         T: DeserializeOwned + ValorantAPIData,
     {
-        reqwest::get(format!("{}/{}", self.api_end_point, api_type.to_url()))
+        let retry_after = response
+            .headers()
+            .get(reqwest::header::RETRY_AFTER)
+            .and_then(|value| value.to_str().ok())
+            .and_then(retry::parse_retry_after);
+        let mut decoded = if self.versions.route(api_type).1.is_none() {
+            response.json().await?
+        } else {
+            let status = response.status();
+            let body = api_version::normalize(api_type, response.bytes().await?.to_vec());
+            replay_response(status, body).json().await?
+        };
+        if let ApiResponse::Failure { errors, .. } = &mut decoded {
+            for error in errors {
+                error.retry_after = retry_after;
+            }
+        }
+        Ok(decoded)
+    }
+
+    // Retries as the retry policy allows, waiting at least as long as a Retry-After asked and
+    // giving up when it asked for longer than the policy's maximum delay
+    async fn send_authorized(
+        &self,
+        api_type: &ValorantApiType<'_>,
+    ) -> Result<reqwest::Response, reqwest::Error> {
+        let mut attempt = 1;
+        loop {
+            let outcome = self.send_authorized_once(api_type).await;
+            let policy = match &self.retry_policy {
+                Some(policy) if attempt < policy.max_attempts() => policy,
+                _ => return outcome,
+            };
+            let wait = match &outcome {
+                Ok(response) if retry::retryable_status(response.status().as_u16().into()) => {
+                    let retry_after = response
+                        .headers()
+                        .get(reqwest::header::RETRY_AFTER)
+                        .and_then(|value| value.to_str().ok())
+                        .and_then(retry::parse_retry_after);
+                    match policy.delay_after(attempt, retry_after) {
+                        Some(wait) => wait,
+                        None => return outcome,
+                    }
+                }
+                Err(error) if error.is_retryable() => policy.delay(attempt),
+                _ => return outcome,
+            };
+            tokio::time::sleep(wait).await;
+            attempt += 1;
+        }
+    }
+
+    async fn send_authorized_once(
+        &self,
+        api_type: &ValorantApiType<'_>,
+    ) -> Result<reqwest::Response, reqwest::Error> {
+        let path = self.versions.route(api_type).0;
+        self.wait_for_budget().await;
+        let response = self.send_timed(&path, self.build_request(api_type)).await?;
+        let status = response.status();
+        if status != reqwest::StatusCode::UNAUTHORIZED && status != reqwest::StatusCode::FORBIDDEN {
+            return Ok(response);
+        }
+
+        let rejected_key = self.api_key();
+        let new_key = self.on_auth_error.as_ref().and_then(|hook| {
+            hook(&AuthErrorContext {
+                status,
+                rejected_key: rejected_key.as_deref(),
+            })
+        });
+        match new_key {
+            Some(key) => {
+                *self.api_key.write().unwrap() = Some(key);
+                self.wait_for_budget().await;
+                self.send_timed(&path, self.build_request(api_type)).await
+            }
+            None => Ok(response),
+        }
+    }
+
+    // Requests about one player are keyed by their URL, which identifies both the player and
+    // the endpoint
+    fn cooldown_key(&self, api_type: &ValorantApiType<'_>) -> Option<String> {
+        self.player_cooldown?;
+        api_type
+            .is_player_scoped()
+            .then(|| self.versions.route(api_type).0.to_lowercase())
+    }
+
+    async fn recent_response(&self, key: &str) -> Option<reqwest::Response> {
+        // Errors count as misses, the request is simply sent again
+        let cached = self.response_cache.get(key).await.ok()??;
+        let status = reqwest::StatusCode::from_u16(cached.status).ok()?;
+        Some(replay_response(status, cached.body))
+    }
+
+    async fn remember_response(&self, key: String, status: reqwest::StatusCode, body: Vec<u8>) {
+        let Some(ttl) = self.player_cooldown else {
+            return;
+        };
+        let response = CachedResponse {
+            status: status.as_u16(),
+            body,
+        };
+        let _ = self.response_cache.put(&key, response, ttl).await;
+    }
+
+    /// Drops the cached response for a request, so the next one during the player cooldown is
+    /// sent to the API, e.g. right after the player finished a match.
+    pub async fn invalidate(&self, api_type: &ValorantApiType<'_>) -> std::io::Result<()> {
+        match self.cooldown_key(api_type) {
+            Some(key) => self.response_cache.invalidate(&key).await,
+            None => Ok(()),
+        }
+    }
+
+    async fn wait_for_budget(&self) {
+        if let Some(budget) = &self.rate_budget {
+            budget.acquire().await;
+        }
+        self.key_limits.wait().await;
+    }
+
+    /// The rate limit headers of the latest answer that carried any, on any endpoint. The API
+    /// counts one limit per key, not per route.
+    pub fn rate_limit(&self) -> Option<ResponseMeta> {
+        self.key_limits.latest()
+    }
+
+    async fn resolve_season_filter<'b>(
+        &self,
+        api_type: ValorantApiType<'b>,
+    ) -> Result<ValorantApiType<'b>, ValorantError> {
+        match api_type {
+            ValorantApiType::MMRData {
+                region,
+                name,
+                tag,
+                filter: Some(SeasonFilter::Current),
+            } => Ok(ValorantApiType::MMRData {
+                region,
+                name,
+                tag,
+                filter: self.current_act().await?.map(SeasonFilter::Act),
+            }),
+            ValorantApiType::StoredMatches {
+                region,
+                name,
+                tag,
+                filter: Some(SeasonFilter::Current),
+                page,
+                size,
+            } => Ok(ValorantApiType::StoredMatches {
+                region,
+                name,
+                tag,
+                filter: self.current_act().await?.map(SeasonFilter::Act),
+                page,
+                size,
+            }),
+            api_type => Ok(api_type),
+        }
+    }
+
+    /// The live act according to the content endpoint, cached for an hour.
+    pub async fn current_act(&self) -> Result<Option<EpisodeAndAct>, ValorantError> {
+        if let Some((fetched_at, season)) = &*self.current_act.read().unwrap() {
+            if fetched_at.elapsed() < CURRENT_ACT_TTL {
+                return Ok(Some(season.clone()));
+            }
+        }
+        self.fetch_current_act().await
+    }
+
+    // Replaces the cached act, if the content endpoint names one
+    async fn fetch_current_act(&self) -> Result<Option<EpisodeAndAct>, ValorantError> {
+        let season = self
+            .send::<Content>(&ValorantApiType::Content { locale: None })
+            .await?
+            .active_act();
+        if let Some(season) = &season {
+            *self.current_act.write().unwrap() = Some((Instant::now(), season.clone()));
+        }
+        Ok(season)
+    }
+
+    fn api_key(&self) -> Option<String> {
+        self.api_key.read().unwrap().clone()
+    }
+
+    fn build_request(&self, api_type: &ValorantApiType<'_>) -> reqwest::RequestBuilder {
+        self.get(&self.versions.route(api_type).0)
+    }
+
+    fn get(&self, path: &str) -> reqwest::RequestBuilder {
+        self.authorize(self.http.get(self.url(path)))
+    }
+
+    fn post(&self, path: &str) -> reqwest::RequestBuilder {
+        self.authorize(self.http.post(self.url(path)))
+    }
+
+    fn authorize(&self, request: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
+        match self.api_key() {
+            Some(key) => request.header(self.profile.auth_header.as_str(), key),
+            None => request,
+        }
+    }
+
+    fn url(&self, path: &str) -> String {
+        let end_point = self.api_end_point.trim_end_matches('/');
+        match self.profile.path_prefix.trim_matches('/') {
+            "" => format!("{end_point}/{path}"),
+            prefix => format!("{end_point}/{prefix}/{path}"),
+        }
+    }
+
+    /// Fetches a player's MMR history together with their match history and links each RR
+    /// change to the match it came from. Matches that fall outside the match history are
+    /// returned without a result. Fails if either of the two requests fails.
+    pub async fn mmr_changes(
+        &self,
+        region: AccountRegion,
+        name: &str,
+        tag: &str,
+    ) -> Result<Vec<MMRChange>, ValorantError> {
+        let (history, matches) = tokio::join!(
+            self.request::<Vec<MMRHistoryEntry>>(ValorantApiType::MMRHistory { region, name, tag }),
+            self.request::<Vec<MatchData>>(ValorantApiType::MatchHistory { region, name, tag }),
+        );
+        let (history, matches) = (history?, matches?);
+        Ok(mmr_history::annotate(&history, &matches, name, tag))
+    }
+
+    /// Fetches the player's rank stats for every act listed by the content endpoint. Acts the
+    /// player has no games in are left out of the map, and any act the API fails to answer
+    /// fails the whole lookup.
+    pub async fn mmr_all_acts(
+        &self,
+        region: AccountRegion,
+        name: &str,
+        tag: &str,
+    ) -> Result<HashMap<EpisodeAndAct, ActRankStats>, ValorantError> {
+        self.fetch_all_acts(region, name, tag, None)
+            .await?
+            .into_complete()
+    }
+
+    /// Like [`mmr_all_acts`](Self::mmr_all_acts), but keeps the acts that were answered and
+    /// reports the ones the API failed to answer next to them.
+    pub async fn mmr_all_acts_partial(
+        &self,
+        region: AccountRegion,
+        name: &str,
+        tag: &str,
+    ) -> Result<PartialOutcome<HashMap<EpisodeAndAct, ActRankStats>>, ValorantError> {
+        self.fetch_all_acts(region, name, tag, None).await
+    }
+
+    /// Like [`mmr_all_acts`](Self::mmr_all_acts), but gives up once `deadline` passes. The
+    /// content lookup gets half the budget and the acts, fetched in parallel, the rest.
+    pub async fn mmr_all_acts_within(
+        &self,
+        region: AccountRegion,
+        name: &str,
+        tag: &str,
+        deadline: Deadline,
+    ) -> Result<HashMap<EpisodeAndAct, ActRankStats>, ValorantError> {
+        self.fetch_all_acts(region, name, tag, Some(&deadline))
             .await?
-            .json()
+            .into_complete()
+    }
+
+    async fn fetch_all_acts(
+        &self,
+        region: AccountRegion,
+        name: &str,
+        tag: &str,
+        deadline: Option<&Deadline>,
+    ) -> Result<PartialOutcome<HashMap<EpisodeAndAct, ActRankStats>>, ValorantError> {
+        let content = self.send_enveloped::<Content>(&ValorantApiType::Content { locale: None });
+        let seasons = error::into_data(deadline::step(deadline, 2, content).await??)?.seasons();
+
+        let requests = seasons.into_iter().map(|season| async move {
+            let result = self
+                .send_enveloped::<ActLeg>(&ValorantApiType::MMRData {
+                    region,
+                    name,
+                    tag,
+                    filter: Some(SeasonFilter::Act(season.clone())),
+                })
+                .await;
+            (season, result)
+        });
+        let mut requests = stream::iter(requests).buffer_unordered(bulk::MAX_IN_FLIGHT);
+
+        let collect = async {
+            let mut acts = PartialOutcome::new(HashMap::new());
+            while let Some((season, result)) = requests.next().await {
+                match result {
+                    Ok(ApiResponse::Success {
+                        data: ActLeg::Played(data),
+                        ..
+                    }) if data.number_of_games() > 0 => {
+                        acts.value.insert(season, data);
+                    }
+                    Ok(ApiResponse::Success { .. }) => {}
+                    Ok(ApiResponse::Failure { status, errors }) => {
+                        acts.failures.push(LegFailure {
+                            leg: season.to_value(),
+                            status,
+                            errors,
+                        });
+                    }
+                    // Returning drops the requests that are still in flight
+                    Err(error) => return Err(error),
+                }
+            }
+            Ok(acts)
+        };
+        Ok(deadline::step(deadline, 1, collect).await??)
+    }
+
+    /// Downloads a region's whole leaderboard and indexes it for local lookups.
+    pub async fn leaderboard_index(
+        &self,
+        region: AccountRegion,
+        season: Option<EpisodeAndAct>,
+    ) -> Result<LeaderboardIndex, ValorantError> {
+        self.request::<Leaderboard>(ValorantApiType::Leaderboard {
+            region,
+            season,
+            page: None,
+            size: None,
+        })
+        .await
+        .map(LeaderboardIndex::new)
+    }
+
+    /// Fetches the player's night market, or `None` while it isn't open. The API answers with
+    /// a 404 outside of night market windows, which is not treated as a failure here.
+    pub async fn night_market(
+        &self,
+        region: AccountRegion,
+        name: &str,
+        tag: &str,
+    ) -> Result<Option<NightMarket>, ValorantError> {
+        match self
+            .request::<NightMarket>(ValorantApiType::NightMarket { region, name, tag })
             .await
+        {
+            Ok(market) if !market.offers().is_empty() => Ok(Some(market)),
+            Ok(_) => Ok(None),
+            Err(error) if error.status() == Some(404) => Ok(None),
+            Err(error) => Err(error),
+        }
+    }
+
+    /// Resolves DNS and opens a connection to the endpoint ahead of time so the first real
+    /// request can reuse it from the pool. The request goes to the profile's path prefix with
+    /// the key, like every other, so gateways in front of the API let it through.
+    pub async fn warmup(&self) -> Result<(), ValorantError> {
+        self.authorize(self.http.head(self.url(""))).send().await?;
+        Ok(())
     }
 }
 
+#[cfg(feature = "client")]
 impl Default for ValorantClient<'_> {
     fn default() -> Self {
         ValorantClient {
-            api_end_point: "https://api.henrikdev.xyz/valorant",
+            api_end_point: DEFAULT_API_END_POINT,
+            http: reqwest::Client::new(),
+            profile: DeploymentProfile::default(),
+            api_key: RwLock::new(env_api_key()),
+            on_auth_error: None,
+            current_act: RwLock::new(None),
+            game_version: RwLock::new(None),
+            rate_budget: None,
+            key_limits: KeyLimits::default(),
+            retry_policy: None,
+            player_cooldown: None,
+            response_cache: Arc::new(MemoryCache::new()),
+            on_suspicious_data: None,
+            versions: ApiVersions::default(),
+            stats: StatsRecorder::default(),
+            stored_page_limit: RwLock::new(validation::MAX_PAGE_SIZE),
+            telemetry: Telemetry::default(),
+            request_signer: None,
+            #[cfg(any(test, feature = "test-support"))]
+            failure_injector: None,
         }
     }
 }
 
-pub enum ValorantApiType<'a> {
-    MMRData {
-        region: AccountRegion,
-        name: &'a str,
-        tag: &'a str,
-        filter: Option<EpisodeAndAct>
-    },
-    AccountData {
-        name: &'a str,
-        tag: &'a str,
-    },
+/// Describes how a deployment of the API is laid out, so the client also works against
+/// self-hosted caching proxies that mount the routes under a prefix or expect the key in a
+/// different header.
+#[cfg(feature = "client")]
+#[derive(Debug, Clone)]
+pub struct DeploymentProfile {
+    path_prefix: String,
+    auth_header: String,
 }
 
-impl<'a> ValorantApiType<'a> {
-    pub fn to_url(&self) -> String {
-        match self {
-            Self::MMRData { region, name, tag, filter} => {
-                format!("v2/mmr/{}/{}/{}", region.to_value(), name, tag)
-            }
-            Self::AccountData { name, tag } => {
-                format!("v1/account/{}/{}", name, tag)
-            }
+#[cfg(feature = "client")]
+impl DeploymentProfile {
+    /// The layout of the public HenrikDev API.
+    pub fn henrikdev() -> Self {
+        DeploymentProfile {
+            path_prefix: String::new(),
+            auth_header: "Authorization".to_string(),
         }
     }
+
+    /// Path segments inserted between the endpoint and every route, e.g. `"henrik/valorant"`.
+    pub fn path_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.path_prefix = prefix.into();
+        self
+    }
+
+    /// Name of the header the API key is sent in.
+    pub fn auth_header(mut self, header: impl Into<String>) -> Self {
+        self.auth_header = header.into();
+        self
+    }
 }
 
-pub mod prelude {
-    pub use crate::account_data::AccountData;
-    pub use crate::mmr_data::MMRData;
-    pub use crate::AccountRegion;
-    pub use crate::ApiResponse;
-    pub use crate::ValorantApiType;
-    pub use crate::ValorantClient;
-    pub use crate::mmr_data::EpisodeAndAct;
+#[cfg(feature = "client")]
+impl Default for DeploymentProfile {
+    fn default() -> Self {
+        DeploymentProfile::henrikdev()
+    }
 }
 
-#[cfg(test)]
-mod test {
-    use crate::prelude::*;
-    #[test]
-    fn get_account_data_404() {
-        let response_404 = r#"{
-            "status": 404,
-            "errors": [
-            {
-                "message": "Not found",
-                "code": 0,
-                "details": "null"
-            }
-            ]
-        }"#;
+/// Builds a [`ValorantClient`] with custom transport settings such as extra root certificates
+/// or a client identity for mutual TLS.
+#[cfg(feature = "client")]
+pub struct ValorantClientBuilder<'a> {
+    api_end_point: &'a str,
+    http: reqwest::ClientBuilder,
+    profile: DeploymentProfile,
+    api_key: Option<String>,
+    on_auth_error: Option<AuthErrorHook>,
+    rate_budget: Option<RateBudget>,
+    throttle_on_headers: bool,
+    retry_policy: Option<RetryPolicy>,
+    player_cooldown: Option<Duration>,
+    response_cache: Option<Arc<dyn ResponseCache>>,
+    on_suspicious_data: Option<SanityHook>,
+    versions: ApiVersions,
+    telemetry: Telemetry,
+    request_signer: Option<Arc<dyn RequestSigner>>,
+    #[cfg(any(test, feature = "test-support"))]
+    failure_injector: Option<chaos::FailureInjector>,
+}
 
-        let result: ApiResponse<MMRData> = serde_json::from_str(response_404).unwrap();
-        dbg!(result);
+#[cfg(feature = "client")]
+impl<'a> ValorantClientBuilder<'a> {
+    pub fn new() -> Self {
+        ValorantClientBuilder::default()
+    }
+
+    pub fn api_endpoint(mut self, endpoint: &'a str) -> Self {
+        self.api_end_point = endpoint;
+        self
+    }
+
+    pub fn profile(mut self, profile: DeploymentProfile) -> Self {
+        self.profile = profile;
+        self
+    }
+
+    /// Without a key, the one in [`API_KEY_VAR`] is used if it is set.
+    pub fn api_key(mut self, key: impl Into<String>) -> Self {
+        self.api_key = Some(key.into());
+        self
+    }
+
+    /// Registers a hook that can hand out a fresh API key when the current one is rejected, for
+    /// key rotation setups.
+    pub fn on_auth_error<F>(mut self, hook: F) -> Self
+    where
+        F: Fn(&AuthErrorContext<'_>) -> Option<String> + Send + Sync + 'static,
+    {
+        self.on_auth_error = Some(Arc::new(hook));
+        self
+    }
+
+    /// Makes every request wait for a token from the budget. Attach clones of one budget to
+    /// several clients to have them share a single key's limit.
+    pub fn rate_budget(mut self, budget: RateBudget) -> Self {
+        self.rate_budget = Some(budget);
+        self
+    }
+
+    /// Makes requests wait when the rate limit headers of the last answer say the next one
+    /// would be rejected, until the window resets or for as long as a 429 asked.
+    pub fn throttle_on_rate_limit_headers(mut self, throttle: bool) -> Self {
+        self.throttle_on_headers = throttle;
+        self
+    }
+
+    /// Resends requests that failed in a way worth retrying, as often as the policy allows.
+    pub fn retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.retry_policy = Some(policy);
+        self
+    }
+
+    /// Answers repeated requests about the same player from the last response until the
+    /// cooldown has passed, e.g. so a spammed `!rank` command fetches the MMR only once per 30
+    /// seconds.
+    pub fn player_cooldown(mut self, cooldown: Duration) -> Self {
+        self.player_cooldown = Some(cooldown);
+        self
+    }
+
+    /// Keeps the responses reused during the player cooldown in `cache` instead of a
+    /// [`MemoryCache`]. Pass clones of an `Arc` to share one cache between clients.
+    pub fn response_cache(mut self, cache: impl ResponseCache + 'static) -> Self {
+        self.response_cache = Some(Arc::new(cache));
+        self
+    }
+
+    /// Checks every successful response for broken invariants, like RR above 100 below
+    /// Immortal, and calls `hook` with the URL and warnings of suspicious ones. The responses are
+    /// still returned as they are.
+    pub fn on_suspicious_data<F>(mut self, hook: F) -> Self
+    where
+        F: Fn(&str, &[SanityWarning]) + Send + Sync + 'static,
+    {
+        self.on_suspicious_data = Some(Arc::new(hook));
+        self
+    }
+
+    /// Requests the endpoint families pinned in `versions` from their pinned version, still
+    /// decoding each into the family's usual model.
+    pub fn api_versions(mut self, versions: ApiVersions) -> Self {
+        self.versions = versions;
+        self
+    }
+
+    /// Sends the trace context `hook` returns with every request, so the calls join the
+    /// caller's distributed trace.
+    pub fn trace_context<F>(mut self, hook: F) -> Self
+    where
+        F: Fn() -> Option<TraceContext> + Send + Sync + 'static,
+    {
+        self.telemetry.context = Some(Arc::new(hook));
+        self
+    }
+
+    /// Calls `hook` with every finished request, attributed after the OpenTelemetry HTTP client
+    /// conventions.
+    pub fn on_request_span<F>(mut self, hook: F) -> Self
+    where
+        F: Fn(&RequestSpan) + Send + Sync + 'static,
+    {
+        self.telemetry.on_span = Some(Arc::new(hook));
+        self
+    }
+
+    /// Lets `signer` sign every request right before it is sent, for gateways that require
+    /// signed requests. See [`signing`].
+    pub fn request_signer(mut self, signer: impl RequestSigner + 'static) -> Self {
+        self.request_signer = Some(Arc::new(signer));
+        self
+    }
+
+    /// Trusts an additional root CA, e.g. a corporate proxy or a private CA of a self-hosted
+    /// instance.
+    pub fn add_root_certificate(mut self, certificate: Certificate) -> Self {
+        self.http = self.http.add_root_certificate(certificate);
+        self
+    }
+
+    /// Controls whether the system's built-in root certificates are trusted as well.
+    pub fn tls_built_in_root_certs(mut self, enabled: bool) -> Self {
+        self.http = self.http.tls_built_in_root_certs(enabled);
+        self
+    }
+
+    /// Presents a client certificate during the TLS handshake.
+    pub fn identity(mut self, identity: Identity) -> Self {
+        self.http = self.http.identity(identity);
+        self
+    }
+
+    pub fn min_tls_version(mut self, version: TlsVersion) -> Self {
+        self.http = self.http.min_tls_version(version);
+        self
+    }
+
+    /// Disables certificate validation entirely. Only meant for local testing.
+    pub fn danger_accept_invalid_certs(mut self, accept: bool) -> Self {
+        self.http = self.http.danger_accept_invalid_certs(accept);
+        self
+    }
+
+    /// Injects timeouts, truncated bodies and server errors into requests, to test how an app
+    /// copes with them. See [`chaos`].
+    #[cfg(any(test, feature = "test-support"))]
+    pub fn failure_injector(mut self, injector: chaos::FailureInjector) -> Self {
+        self.failure_injector = Some(injector);
+        self
+    }
+
+    pub fn build(self) -> Result<ValorantClient<'a>, reqwest::Error> {
+        Ok(ValorantClient {
+            api_end_point: self.api_end_point,
+            http: self.http.build()?,
+            profile: self.profile,
+            api_key: RwLock::new(self.api_key.or_else(env_api_key)),
+            on_auth_error: self.on_auth_error,
+            current_act: RwLock::new(None),
+            game_version: RwLock::new(None),
+            rate_budget: self.rate_budget,
+            key_limits: KeyLimits::new(self.throttle_on_headers),
+            retry_policy: self.retry_policy,
+            player_cooldown: self.player_cooldown,
+            response_cache: self
+                .response_cache
+                .unwrap_or_else(|| Arc::new(MemoryCache::new())),
+            on_suspicious_data: self.on_suspicious_data,
+            versions: self.versions,
+            stats: StatsRecorder::default(),
+            stored_page_limit: RwLock::new(validation::MAX_PAGE_SIZE),
+            telemetry: self.telemetry,
+            request_signer: self.request_signer,
+            #[cfg(any(test, feature = "test-support"))]
+            failure_injector: self.failure_injector,
+        })
+    }
+}
+
+#[cfg(feature = "client")]
+impl Default for ValorantClientBuilder<'_> {
+    fn default() -> Self {
+        ValorantClientBuilder {
+            api_end_point: DEFAULT_API_END_POINT,
+            http: reqwest::Client::builder(),
+            profile: DeploymentProfile::default(),
+            api_key: None,
+            on_auth_error: None,
+            rate_budget: None,
+            throttle_on_headers: false,
+            retry_policy: None,
+            player_cooldown: None,
+            response_cache: None,
+            on_suspicious_data: None,
+            versions: ApiVersions::default(),
+            telemetry: Telemetry::default(),
+            request_signer: None,
+            #[cfg(any(test, feature = "test-support"))]
+            failure_injector: None,
+        }
+    }
+}
+#[non_exhaustive]
+pub enum ValorantApiType<'a> {
+    MMRData {
+        region: AccountRegion,
+        name: &'a str,
+        tag: &'a str,
+        filter: Option<SeasonFilter>,
+    },
+    /// Decodes as [`MMRDataV3`](mmr_data::MMRDataV3).
+    MMRDataV3 {
+        region: AccountRegion,
+        platform: Platform,
+        name: &'a str,
+        tag: &'a str,
+    },
+    AccountData {
+        name: &'a str,
+        tag: &'a str,
+    },
+    MMRHistory {
+        region: AccountRegion,
+        name: &'a str,
+        tag: &'a str,
+    },
+    MatchHistory {
+        region: AccountRegion,
+        name: &'a str,
+        tag: &'a str,
+    },
+    AccountDataByPuuid {
+        puuid: &'a str,
+    },
+    MMRDataByPuuid {
+        region: AccountRegion,
+        puuid: &'a str,
+    },
+    MMRHistoryByPuuid {
+        region: AccountRegion,
+        puuid: &'a str,
+    },
+    MatchHistoryByPuuid {
+        region: AccountRegion,
+        puuid: &'a str,
+    },
+    /// A single match, decoded as [`MatchData`].
+    Match {
+        id: &'a str,
+    },
+    Content {
+        locale: Option<&'a str>,
+    },
+    /// Pages start at 1. Without a page the whole leaderboard is returned.
+    Leaderboard {
+        region: AccountRegion,
+        season: Option<EpisodeAndAct>,
+        page: Option<u32>,
+        size: Option<u32>,
+    },
+    StoreFeatured,
+    Version {
+        region: AccountRegion,
+    },
+    /// Pages start at 1. Without a filter matches from every act are returned.
+    StoredMatches {
+        region: AccountRegion,
+        name: &'a str,
+        tag: &'a str,
+        filter: Option<SeasonFilter>,
+        page: Option<u32>,
+        size: Option<u32>,
+    },
+    NightMarket {
+        region: AccountRegion,
+        name: &'a str,
+        tag: &'a str,
+    },
+    /// Every league's schedule unless `league` names one, e.g. `vct_emea`.
+    EsportsSchedule {
+        league: Option<&'a str>,
+    },
+    /// The website's articles, e.g. for `en-us`.
+    Website {
+        country_code: &'a str,
+    },
+}
+
+impl<'a> ValorantApiType<'a> {
+    /// Whether the request is about a single player.
+    pub fn is_player_scoped(&self) -> bool {
+        match self {
+            Self::MMRData { .. }
+            | Self::MMRDataV3 { .. }
+            | Self::AccountData { .. }
+            | Self::MMRHistory { .. }
+            | Self::MatchHistory { .. }
+            | Self::AccountDataByPuuid { .. }
+            | Self::MMRDataByPuuid { .. }
+            | Self::MMRHistoryByPuuid { .. }
+            | Self::MatchHistoryByPuuid { .. }
+            | Self::StoredMatches { .. }
+            | Self::NightMarket { .. } => true,
+            Self::Match { .. }
+            | Self::Content { .. }
+            | Self::Leaderboard { .. }
+            | Self::StoreFeatured
+            | Self::Version { .. }
+            | Self::EsportsSchedule { .. }
+            | Self::Website { .. } => false,
+        }
+    }
+
+    pub fn to_url(&self) -> String {
+        match self {
+            // An unresolved `SeasonFilter::Current` is left out, which the API treats as the
+            // current act as well
+            Self::MMRData { region, name, tag, filter } => match filter {
+                Some(SeasonFilter::Act(season)) => format!(
+                    "v2/mmr/{}/{}/{}?filter={}",
+                    region.to_value(),
+                    name,
+                    tag,
+                    season.to_value()
+                ),
+                _ => format!("v2/mmr/{}/{}/{}", region.to_value(), name, tag),
+            },
+            Self::MMRDataV3 {
+                region,
+                platform,
+                name,
+                tag,
+            } => format!(
+                "v3/mmr/{}/{}/{}/{}",
+                region.segment(),
+                platform.segment(),
+                name,
+                tag
+            ),
+            Self::AccountData { name, tag } => {
+                format!("v1/account/{}/{}", name, tag)
+            }
+            Self::MMRHistory { region, name, tag } => {
+                format!("v1/mmr-history/{}/{}/{}", region.to_value(), name, tag)
+            }
+            Self::MatchHistory { region, name, tag } => {
+                format!("v3/matches/{}/{}/{}", region.to_value(), name, tag)
+            }
+            Self::AccountDataByPuuid { puuid } => format!("v1/by-puuid/account/{}", puuid),
+            Self::MMRDataByPuuid { region, puuid } => {
+                format!("v2/by-puuid/mmr/{}/{}", region.to_value(), puuid)
+            }
+            Self::MMRHistoryByPuuid { region, puuid } => {
+                format!("v1/by-puuid/mmr-history/{}/{}", region.to_value(), puuid)
+            }
+            Self::MatchHistoryByPuuid { region, puuid } => {
+                format!("v3/by-puuid/matches/{}/{}", region.to_value(), puuid)
+            }
+            Self::Match { id } => format!("v2/match/{}", id),
+            Self::Leaderboard {
+                region,
+                season,
+                page,
+                size,
+            } => {
+                let url = format!("v2/leaderboard/{}", region.to_value());
+                let query = [
+                    ("season", season.as_ref().map(|season| season.to_value())),
+                    ("page", page.map(|page| page.to_string())),
+                    ("size", size.map(|size| size.to_string())),
+                ]
+                .into_iter()
+                .filter_map(|(key, value)| value.map(|value| format!("{key}={value}")))
+                .collect::<Vec<_>>();
+                match query.is_empty() {
+                    true => url,
+                    false => format!("{}?{}", url, query.join("&")),
+                }
+            }
+            Self::Content { locale } => match locale {
+                Some(locale) => format!("v1/content?locale={}", locale),
+                None => "v1/content".to_string(),
+            },
+            Self::StoreFeatured => "v2/store-featured".to_string(),
+            Self::Version { region } => format!("v1/version/{}", region.to_value()),
+            Self::StoredMatches {
+                region,
+                name,
+                tag,
+                filter,
+                page,
+                size,
+            } => {
+                let url = format!("v1/stored-matches/{}/{}/{}", region.to_value(), name, tag);
+                let season = match filter {
+                    Some(SeasonFilter::Act(season)) => Some(season.to_value()),
+                    _ => None,
+                };
+                let query = [
+                    ("season", season),
+                    ("page", page.map(|page| page.to_string())),
+                    ("size", size.map(|size| size.to_string())),
+                ]
+                .into_iter()
+                .filter_map(|(key, value)| value.map(|value| format!("{key}={value}")))
+                .collect::<Vec<_>>();
+                match query.is_empty() {
+                    true => url,
+                    false => format!("{}?{}", url, query.join("&")),
+                }
+            }
+            Self::NightMarket { region, name, tag } => {
+                format!("v1/night-market/{}/{}/{}", region.to_value(), name, tag)
+            }
+            Self::EsportsSchedule { league } => match league {
+                Some(league) => format!("v1/esports/schedule?league={}", league),
+                None => "v1/esports/schedule".to_string(),
+            },
+            Self::Website { country_code } => format!("v1/website/{}", country_code),
+        }
+    }
+}
+
+pub mod act_rank;
+#[cfg(feature = "storage")]
+pub mod alias_history;
+pub mod api_version;
+#[cfg(feature = "analytics")]
+pub mod analytics;
+#[cfg(feature = "storage")]
+pub mod archive;
+pub mod assets;
+#[cfg(feature = "storage")]
+pub mod backfill;
+#[cfg(feature = "client")]
+pub mod bulk;
+#[cfg(feature = "storage")]
+pub mod bundle_history;
+#[cfg(feature = "client")]
+pub mod cache;
+pub mod calendar;
+#[cfg(any(all(test, feature = "client"), feature = "test-support"))]
+pub mod chaos;
+pub mod competitive_updates;
+#[cfg(feature = "profiles")]
+pub mod config;
+pub mod content;
+pub mod currency;
+#[cfg(feature = "client")]
+pub mod deadline;
+pub mod domain;
+#[cfg(feature = "client")]
+pub mod error;
+pub mod esports;
+pub mod form;
+#[cfg(any(test, feature = "test-support"))]
+pub mod fake;
+// Support for the crate's own tests, not part of the public API
+#[cfg(any(test, feature = "test-support"))]
+#[doc(hidden)]
+pub mod fixtures;
+#[cfg(feature = "graphql")]
+pub mod graphql;
+#[cfg(feature = "client")]
+pub mod health;
+pub mod ids;
+pub mod leaderboard;
+pub mod locale;
+pub mod map_pool;
+pub mod match_data;
+pub mod match_kind;
+pub mod mmr_history;
+pub mod news;
+pub mod normalize;
+pub mod outcome;
+pub mod percentile;
+pub mod periods;
+#[cfg(any(all(test, feature = "client"), feature = "test-support"))]
+pub mod mock_server;
+#[cfg(feature = "client")]
+pub mod prefetch;
+#[cfg(feature = "storage")]
+pub mod queue;
+#[cfg(feature = "client")]
+pub mod rate_limit;
+#[cfg(feature = "markdown")]
+pub mod report;
+#[cfg(feature = "cache-redis")]
+pub mod redis_cache;
+#[cfg(feature = "client")]
+pub mod retry;
+pub mod roster;
+pub mod routing;
+pub mod sanity;
+#[cfg(feature = "image")]
+pub mod scorecard;
+pub mod series;
+#[cfg(feature = "client")]
+pub mod signing;
+#[cfg(feature = "client")]
+pub mod stats;
+#[cfg(feature = "storage")]
+pub mod storage;
+pub mod store;
+pub mod stored_matches;
+#[cfg(feature = "client")]
+pub mod sync;
+#[cfg(feature = "client")]
+pub mod telemetry;
+pub mod template;
+pub mod tier;
+pub mod time_ago;
+pub mod validation;
+pub mod version;
+#[cfg(feature = "web")]
+pub mod web;
+#[cfg(feature = "client")]
+pub mod webhook;
+
+/// The client, the request types and the models most applications use. Everything else is
+/// reached through its module; the prelude only grows, so a glob import of it stays safe.
+pub mod prelude {
+    // The client and its requests
+    pub use crate::api_version::{ApiVersion, ApiVersions, EndpointFamily};
+    #[cfg(feature = "client")]
+    pub use crate::bulk::{BulkResult, SkipReason};
+    #[cfg(feature = "client")]
+    pub use crate::deadline::Deadline;
+    #[cfg(feature = "client")]
+    pub use crate::error::ValorantError;
+    #[cfg(feature = "client")]
+    pub use crate::health::HealthReport;
+    pub use crate::outcome::{LegFailure, PartialOutcome};
+    #[cfg(feature = "client")]
+    pub use crate::rate_limit::{RateBudget, ResponseMeta};
+    #[cfg(feature = "client")]
+    pub use crate::retry::{RetryHint, RetryPolicy};
+    pub use crate::routing::Platform;
+    pub use crate::sanity::SanityWarning;
+    #[cfg(feature = "client")]
+    pub use crate::signing::RequestSigner;
+    #[cfg(feature = "client")]
+    pub use crate::stats::EndpointStats;
+    #[cfg(feature = "client")]
+    pub use crate::telemetry::{RequestSpan, TraceContext};
+    pub use crate::{AccountRegion, ApiResponse, ValorantApiType};
+    #[cfg(feature = "client")]
+    pub use crate::{DeploymentProfile, ValorantClient, ValorantClientBuilder};
+
+    // Identifiers
+    pub use crate::ids::{MatchId, PlayerIdentity, Puuid, RiotId};
+
+    // Models
+    pub use crate::account_data::{AccountData, AccountDataV2, ProfileBanner};
+    pub use crate::act_rank::ActRankTriangle;
+    #[cfg(feature = "client")]
+    pub use crate::assets::AssetClient;
+    pub use crate::assets::{PlayerCard, PlayerTitle};
+    pub use crate::calendar::{ActCalendar, ActWindow};
+    pub use crate::competitive_updates::{CompetitiveUpdate, CompetitiveUpdates};
+    pub use crate::content::Content;
+    pub use crate::currency::Currency;
+    pub use crate::esports::{EsportsEvent, EsportsTeam, EventState};
+    pub use crate::form::{Form, FormModel};
+    pub use crate::leaderboard::{
+        CutoffDistance, Leaderboard, LeaderboardCursor, LeaderboardEntry, LeaderboardIndex,
+        LeaderboardPage, RankCutoff,
+    };
+    pub use crate::map_pool::{MapPool, Veto, VetoFormat, VetoTeam};
+    pub use crate::match_data::{
+        MatchData, MatchMode, MatchOutcome, MatchResult, Party, PenaltyFlags, PlayerCustomization,
+        PremierInfo, QueueSize, TeamRoster,
+    };
+    pub use crate::match_kind::{
+        DeathmatchMatch, Economy, EscalationMatch, MatchKind, PlayerEconomy, SpikeRounds,
+        StandardMatch, SwiftplayMatch,
+    };
+    pub use crate::mmr_data::{
+        ActRankStats, EpisodeAndAct, MMRData, MmrDelta, PlacementProgress, SeasonFilter,
+    };
+    pub use crate::mmr_history::{MMRChange, MMRHistoryEntry, RankMovement};
+    pub use crate::news::{NewsArticle, NewsFeed};
+    pub use crate::percentile::TierDistribution;
+    pub use crate::periods::PeriodSummary;
+    pub use crate::roster::{Roster, RosterMember, RosterSnapshot};
+    pub use crate::series::SeriesReport;
+    pub use crate::store::{BundleItem, FeaturedBundle, NightMarket, NightMarketOffer};
+    pub use crate::stored_matches::{PageInfo, StoredMatch, StoredMatchesPage};
+    #[cfg(feature = "client")]
+    pub use crate::sync::PlayerSync;
+    pub use crate::tier::Tier;
+    pub use crate::version::GameVersion;
+
+    // Presentation
+    pub use crate::locale::{Catalog, Localize};
+    pub use crate::template::{Template, TemplateData};
+    pub use crate::time_ago::TimeAgo;
+}
+
+#[cfg(test)]
+mod test {
+    use crate::prelude::*;
+    #[test]
+    fn get_account_data_404() {
+        let response_404 = r#"{
+            "status": 404,
+            "errors": [
+            {
+                "message": "Not found",
+                "code": 0,
+                "details": "null"
+            }
+            ]
+        }"#;
+
+        let result: ApiResponse<MMRData> = serde_json::from_str(response_404).unwrap();
+        dbg!(result);
+    }
+
+    #[test]
+    fn responses_convert_into_results() {
+        let found: ApiResponse<AccountData> =
+            serde_json::from_str(crate::fixtures::ACCOUNT).unwrap();
+        assert_eq!(found.status(), 200);
+        assert!(found.errors().is_empty());
+        assert_eq!(found.data().map(AccountData::name), Some("Anonymous"));
+        assert_eq!(found.into_result().unwrap().tag(), "0000");
+
+        let missing: ApiResponse<AccountData> =
+            serde_json::from_str(crate::fixtures::NOT_FOUND).unwrap();
+        assert_eq!(missing.status(), 404);
+        assert!(missing.data().is_none());
+        let errors = missing.into_result().unwrap_err();
+        assert_eq!(errors[0].message(), "Not found");
+    }
+
+}
+
+#[cfg(all(test, feature = "client"))]
+mod client_test {
+    use crate::prelude::*;
+
+    #[test]
+    fn builder_with_custom_tls() {
+        let client = ValorantClient::builder()
+            .api_endpoint("https://henrik.internal/valorant")
+            .tls_built_in_root_certs(false)
+            .min_tls_version(crate::TlsVersion::TLS_1_2)
+            .build()
+            .unwrap();
+        assert_eq!(client.api_end_point, "https://henrik.internal/valorant");
+
+        assert!(crate::Certificate::from_pem(b"not a certificate").is_err());
+    }
+
+    #[test]
+    fn self_hosted_profile() {
+        let client = ValorantClient::builder()
+            .api_endpoint("https://cache.example.com/")
+            .profile(
+                DeploymentProfile::henrikdev()
+                    .path_prefix("/henrik/valorant/")
+                    .auth_header("X-Proxy-Key"),
+            )
+            .api_key("secret")
+            .build()
+            .unwrap();
+        let request = client
+            .build_request(&ValorantApiType::AccountData {
+                name: "NitroSniper",
+                tag: "NERD",
+            })
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            request.url().as_str(),
+            "https://cache.example.com/henrik/valorant/v1/account/NitroSniper/NERD"
+        );
+        assert_eq!(request.headers()["X-Proxy-Key"], "secret");
+
+        let request = ValorantClient::new()
+            .build_request(&ValorantApiType::AccountData {
+                name: "NitroSniper",
+                tag: "NERD",
+            })
+            .build()
+            .unwrap();
+        assert_eq!(
+            request.url().as_str(),
+            "https://api.henrikdev.xyz/valorant/v1/account/NitroSniper/NERD"
+        );
+        assert!(request.headers().get("Authorization").is_none());
+
+        let request = ValorantClient::new()
+            .with_api_key("HDEV-secret")
+            .build_request(&ValorantApiType::AccountData {
+                name: "NitroSniper",
+                tag: "NERD",
+            })
+            .build()
+            .unwrap();
+        assert_eq!(request.headers()["Authorization"], "HDEV-secret");
+    }
+
+    #[tokio::test]
+    async fn warmup_goes_through_the_profile() {
+        use crate::mock_server::{MockResponse, MockServer};
+
+        let server = MockServer::start(vec![MockResponse::json(200, "{}")]).await;
+        let url = server.url();
+        let client = ValorantClient::builder()
+            .api_endpoint(&url)
+            .profile(
+                DeploymentProfile::henrikdev()
+                    .path_prefix("/henrik/valorant/")
+                    .auth_header("X-Proxy-Key"),
+            )
+            .api_key("secret")
+            .build()
+            .unwrap();
+        client.warmup().await.unwrap();
+
+        let requests = server.requests();
+        assert_eq!(requests[0].method, "HEAD");
+        assert_eq!(requests[0].path, "/henrik/valorant/");
+        assert_eq!(requests[0].header("x-proxy-key"), Some("secret"));
+    }
+
+    #[tokio::test]
+    async fn auth_error_replays_with_new_key() {
+        use crate::mock_server::{MockResponse, MockServer};
+
+        let server = MockServer::start(vec![
+            MockResponse::json(401, r#"{"status": 401, "errors": []}"#),
+            MockResponse::json(200, crate::account_data::test::RESPONSE_200),
+        ])
+        .await;
+        let url = server.url();
+        let client = ValorantClient::builder()
+            .api_endpoint(&url)
+            .api_key("expired")
+            .on_auth_error(|context| {
+                assert_eq!(context.status, reqwest::StatusCode::UNAUTHORIZED);
+                assert_eq!(context.rejected_key, Some("expired"));
+                Some("rotated".to_string())
+            })
+            .build()
+            .unwrap();
+
+        client
+            .request::<AccountData>(ValorantApiType::AccountData {
+                name: "NitroSniper",
+                tag: "NERD",
+            })
+            .await
+            .unwrap();
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 2);
+        assert_eq!(requests[1].method, "GET");
+        assert_eq!(requests[1].path, "/v1/account/NitroSniper/NERD");
+        assert_eq!(requests[0].header("authorization"), Some("expired"));
+        assert_eq!(requests[1].header("authorization"), Some("rotated"));
+    }
+
+    #[tokio::test]
+    async fn auth_error_without_new_key_is_not_replayed() {
+        use crate::mock_server::{MockResponse, MockServer};
+
+        let server = MockServer::start(vec![MockResponse::json(
+            403,
+            r#"{"status": 403, "errors": [{"message": "Forbidden", "code": 0, "details": "null"}]}"#,
+        )])
+        .await;
+        let url = server.url();
+        let client = ValorantClient::builder()
+            .api_endpoint(&url)
+            .on_auth_error(|_| None)
+            .build()
+            .unwrap();
+
+        let result = client
+            .request::<AccountData>(ValorantApiType::AccountData {
+                name: "NitroSniper",
+                tag: "NERD",
+            })
+            .await
+            .unwrap_err();
+        assert!(matches!(result, ValorantError::ApiError { status: 403, .. }));
+        assert_eq!(server.requests().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn shared_http_clients_are_used() {
+        use crate::mock_server::{MockResponse, MockServer};
+
+        let server =
+            MockServer::start(vec![MockResponse::json(200, crate::fixtures::ACCOUNT)]).await;
+        let url = server.url();
+        let mut headers = reqwest::header::HeaderMap::new();
+        headers.insert("user-agent", "my-bot/1.0".parse().unwrap());
+        let http = reqwest::Client::builder()
+            .default_headers(headers)
+            .build()
+            .unwrap();
+        let client = ValorantClient::new()
+            .change_api_endpoint(&url)
+            .with_http_client(http);
+
+        for _ in 0..2 {
+            client
+                .request::<AccountData>(ValorantApiType::AccountData {
+                    name: "Anonymous",
+                    tag: "0000",
+                })
+                .await
+                .unwrap();
+        }
+        let requests = server.requests();
+        assert_eq!(requests.len(), 2);
+        assert!(requests
+            .iter()
+            .all(|request| request.header("user-agent") == Some("my-bot/1.0")));
+    }
+
+    #[tokio::test]
+    async fn current_season_filter_is_resolved_once() {
+        use crate::mmr_data::test::ACT_RANK_STATS;
+        use crate::mock_server::{MockResponse, MockServer};
+
+        let server = MockServer::start(vec![
+            MockResponse::json(200, crate::fixtures::CONTENT),
+            MockResponse::json(200, ACT_RANK_STATS),
+        ])
+        .await;
+        let url = server.url();
+        let client = ValorantClient::new().change_api_endpoint(&url);
+
+        for _ in 0..2 {
+            client
+                .request::<ActRankStats>(ValorantApiType::MMRData {
+                    region: AccountRegion::EU,
+                    name: "NitroSniper",
+                    tag: "NERD",
+                    filter: Some(SeasonFilter::Current),
+                })
+                .await
+                .unwrap();
+        }
+
+        let paths = server
+            .requests()
+            .into_iter()
+            .map(|request| request.path)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            paths,
+            [
+                "/v1/content",
+                "/v2/mmr/eu/NitroSniper/NERD?filter=e6a1",
+                "/v2/mmr/eu/NitroSniper/NERD?filter=e6a1",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn mmr_all_acts_skips_unplayed_acts() {
+        use crate::mmr_data::test::ACT_RANK_STATS;
+        use crate::mock_server::{MockResponse, MockServer};
+
+        let server = MockServer::routes(vec![
+            ("/v1/content", MockResponse::json(200, crate::fixtures::CONTENT)),
+            ("?filter=e5a2", MockResponse::json(200, ACT_RANK_STATS)),
+            (
+                "?filter=e5a3",
+                MockResponse::json(200, r#"{"status": 200, "data": {"error": "No data Available"}}"#),
+            ),
+            ("?filter=e6a1", MockResponse::json(200, ACT_RANK_STATS)),
+        ])
+        .await;
+        let url = server.url();
+        let client = ValorantClient::new().change_api_endpoint(&url);
+
+        let acts = client
+            .mmr_all_acts(AccountRegion::EU, "NitroSniper", "NERD")
+            .await
+            .unwrap();
+        let mut seasons = acts.keys().map(EpisodeAndAct::to_value).collect::<Vec<_>>();
+        seasons.sort();
+        assert_eq!(seasons, ["e5a2", "e6a1"]);
+        assert_eq!(server.requests().len(), 4);
+    }
+
+    #[tokio::test]
+    async fn mmr_all_acts_partial_reports_failed_acts() {
+        use crate::mmr_data::test::ACT_RANK_STATS;
+        use crate::mock_server::{MockResponse, MockServer};
+
+        let unavailable = r#"{
+            "status": 503,
+            "errors": [{"message": "Riot API unavailable", "code": 0, "details": "null"}]
+        }"#;
+        let server = MockServer::routes(vec![
+            ("/v1/content", MockResponse::json(200, crate::fixtures::CONTENT)),
+            ("?filter=e5a2", MockResponse::json(200, ACT_RANK_STATS)),
+            ("?filter=e5a3", MockResponse::json(503, unavailable)),
+            ("?filter=e6a1", MockResponse::json(200, ACT_RANK_STATS)),
+        ])
+        .await;
+        let url = server.url();
+        let client = ValorantClient::new().change_api_endpoint(&url);
+
+        let outcome = client
+            .mmr_all_acts_partial(AccountRegion::EU, "NitroSniper", "NERD")
+            .await
+            .unwrap();
+        assert!(!outcome.is_complete());
+        assert_eq!(outcome.value.len(), 2);
+        assert_eq!(outcome.failures.len(), 1);
+        assert_eq!(
+            (outcome.failures[0].leg.as_str(), outcome.failures[0].status),
+            ("e5a3", 503)
+        );
+
+        let error = client
+            .mmr_all_acts(AccountRegion::EU, "NitroSniper", "NERD")
+            .await
+            .unwrap_err();
+        assert!(matches!(error, ValorantError::ApiError { status: 503, .. }));
+    }
+
+    #[tokio::test]
+    async fn mmr_all_acts_fails_on_unexpected_payloads() {
+        use crate::mmr_data::test::ACT_RANK_STATS;
+        use crate::mock_server::{MockResponse, MockServer};
+
+        let server = MockServer::routes(vec![
+            ("/v1/content", MockResponse::json(200, crate::fixtures::CONTENT)),
+            ("?filter=e5a2", MockResponse::json(200, ACT_RANK_STATS)),
+            (
+                "?filter=e5a3",
+                MockResponse::json(200, r#"{"status": 200, "data": {"wins": 3}}"#),
+            ),
+            ("?filter=e6a1", MockResponse::json(200, ACT_RANK_STATS)),
+        ])
+        .await;
+        let url = server.url();
+        let client = ValorantClient::new().change_api_endpoint(&url);
+        let error = client
+            .mmr_all_acts_partial(AccountRegion::EU, "NitroSniper", "NERD")
+            .await
+            .unwrap_err();
+        assert!(matches!(error, ValorantError::Decode(_)));
+
+        // Without the list of acts there is nothing to report as unplayed
+        let server = MockServer::routes(vec![(
+            "/v1/content",
+            MockResponse::json(429, crate::fixtures::RATE_LIMITED),
+        )])
+        .await;
+        let url = server.url();
+        let client = ValorantClient::new().change_api_endpoint(&url);
+        let error = client
+            .mmr_all_acts_partial(AccountRegion::EU, "NitroSniper", "NERD")
+            .await
+            .unwrap_err();
+        assert!(matches!(error, ValorantError::RateLimited { .. }));
+    }
+
+    #[tokio::test]
+    async fn mmr_changes_fail_with_the_match_history() {
+        use crate::mock_server::{MockResponse, MockServer};
+
+        let server = MockServer::routes(vec![
+            (
+                "/v1/mmr-history/eu/NitroSniper/NERD",
+                MockResponse::json(200, crate::fixtures::MMR_HISTORY),
+            ),
+            (
+                "/v3/matches/eu/NitroSniper/NERD",
+                MockResponse::json(429, crate::fixtures::RATE_LIMITED),
+            ),
+        ])
+        .await;
+        let url = server.url();
+        let client = ValorantClient::new().change_api_endpoint(&url);
+
+        let error = client
+            .mmr_changes(AccountRegion::EU, "NitroSniper", "NERD")
+            .await
+            .unwrap_err();
+        assert!(matches!(error, ValorantError::RateLimited { .. }));
+    }
+
+    #[tokio::test]
+    async fn night_market_outside_its_window_is_none() {
+        use crate::mock_server::{MockResponse, MockServer};
+
+        let server = MockServer::routes(vec![
+            (
+                "/v1/night-market/eu/NitroSniper/NERD",
+                MockResponse::json(200, crate::fixtures::NIGHT_MARKET),
+            ),
+            (
+                "/v1/night-market/eu/Closed/NERD",
+                MockResponse::json(404, crate::fixtures::NOT_FOUND),
+            ),
+        ])
+        .await;
+        let url = server.url();
+        let client = ValorantClient::new().change_api_endpoint(&url);
+
+        let open = client
+            .night_market(AccountRegion::EU, "NitroSniper", "NERD")
+            .await
+            .unwrap();
+        let Some(market) = open else {
+            panic!("expected an open night market");
+        };
+        assert_eq!(market.offers().len(), 2);
+
+        let closed = client
+            .night_market(AccountRegion::EU, "Closed", "NERD")
+            .await
+            .unwrap();
+        assert!(closed.is_none());
+    }
+
+    #[tokio::test]
+    async fn mmr_all_acts_gives_up_at_the_deadline() {
+        use crate::mock_server::{MockResponse, MockServer};
+        use std::time::Duration;
+
+        let server = MockServer::start(vec![
+            MockResponse::json(200, crate::fixtures::CONTENT).delay(Duration::from_millis(300))
+        ])
+        .await;
+        let url = server.url();
+        let client = ValorantClient::new().change_api_endpoint(&url);
+
+        let result = client
+            .mmr_all_acts_within(
+                AccountRegion::EU,
+                "NitroSniper",
+                "NERD",
+                Deadline::after(Duration::from_millis(100)),
+            )
+            .await;
+        assert!(matches!(result, Err(ValorantError::DeadlineExceeded)));
+        // The acts were never requested
+        assert_eq!(server.requests().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn clients_share_a_rate_budget() {
+        use crate::mock_server::{MockResponse, MockServer};
+
+        let server = MockServer::start(vec![MockResponse::json(
+            200,
+            crate::account_data::test::RESPONSE_200,
+        )]).await;
+        let url = server.url();
+        let budget = RateBudget::new(2, std::time::Duration::from_millis(200));
+        let clients = (0..2)
+            .map(|_| {
+                ValorantClient::builder()
+                    .api_endpoint(&url)
+                    .rate_budget(budget.clone())
+                    .build()
+                    .unwrap()
+            })
+            .collect::<Vec<_>>();
+
+        let start = std::time::Instant::now();
+        for client in clients.iter().chain(&clients) {
+            client
+                .request::<AccountData>(ValorantApiType::AccountData {
+                    name: "NitroSniper",
+                    tag: "NERD",
+                })
+                .await
+                .unwrap();
+        }
+        // Two requests fit the budget, the other two wait 100ms each for a refill
+        assert!(start.elapsed() >= std::time::Duration::from_millis(180));
+        assert_eq!(server.requests().len(), 4);
+    }
+
+    #[tokio::test]
+    async fn player_cooldown_reuses_recent_responses() {
+        use crate::mock_server::{MockResponse, MockServer};
+
+        let server = MockServer::start(vec![MockResponse::json(
+            200,
+            crate::account_data::test::RESPONSE_200,
+        )])
+        .await;
+        let url = server.url();
+        let client = ValorantClient::builder()
+            .api_endpoint(&url)
+            .player_cooldown(std::time::Duration::from_millis(200))
+            .build()
+            .unwrap();
+        let account = |name| {
+            client.request::<AccountData>(ValorantApiType::AccountData { name, tag: "NERD" })
+        };
+
+        for _ in 0..3 {
+            account("NitroSniper").await.unwrap();
+        }
+        // Riot IDs are case insensitive, so this is still the same player
+        account("nitrosniper").await.unwrap();
+        assert_eq!(server.requests().len(), 1);
+
+        account("SomeoneElse").await.unwrap();
+        assert_eq!(server.requests().len(), 2);
+
+        tokio::time::sleep(std::time::Duration::from_millis(250)).await;
+        account("NitroSniper").await.unwrap();
+        assert_eq!(server.requests().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn making_a_call() {
+        let api_user = ValorantClient::new();
+        let result = api_user
+            .request::<MMRData>(ValorantApiType::MMRData {
+                region: AccountRegion::EU,
+                name: "NitroSniper",
+                tag: "NERD",
+                filter: None,
+            })
+            .await
+            .unwrap();
+        dbg!(result);
+    }
+}
+
+pub mod mmr_data {
+    use crate::tier::Tier;
+    use crate::ValorantAPIData;
+    use serde::{Deserialize, Serialize};
+    use std::fmt;
+    use std::str::FromStr;
+
+    #[derive(Serialize, Deserialize, Debug)]
+    pub struct MMRData {
+        puuid: String,
+        name: String,
+        tag: String,
+        current_data: CurrentActData,
+        highest_rank: HighestRank,
+    }
+
+    #[derive(Serialize, Deserialize, Debug)]
+    struct CurrentActData {
+        #[serde(rename = "currenttier")]
+        current_tier: Tier,
+        #[serde(rename = "currenttierpatched")]
+        current_tier_patched: String,
+        images: RankImages,
+        ranking_in_tier: u32,
+        mmr_change_to_last_game: i32,
+        elo: u32,
+        games_needed_for_rating: u32,
+        old: bool,
+    }
+
+    #[derive(Serialize, Deserialize, Debug)]
+    pub struct RankImages {
+        small: String,
+        large: String,
+        triangle_down: String,
+        triangle_up: String,
+    }
+
+    impl RankImages {
+        pub(crate) fn for_tier(tier: u32) -> Self {
+            let icon = |file: &str| {
+                format!(
+                    "https://media.valorant-api.com/competitivetiers/03621f52-342b-cf4e-4f86-9350a49c6d04/{tier}/{file}.png"
+                )
+            };
+            RankImages {
+                small: icon("smallicon"),
+                large: icon("largeicon"),
+                triangle_down: icon("ranktriangledownicon"),
+                triangle_up: icon("ranktriangleupicon"),
+            }
+        }
+
+        pub fn small(&self) -> &str {
+            &self.small
+        }
+
+        pub fn large(&self) -> &str {
+            &self.large
+        }
+
+        pub fn triangle_down(&self) -> &str {
+            &self.triangle_down
+        }
+
+        pub fn triangle_up(&self) -> &str {
+            &self.triangle_up
+        }
+    }
+
+    #[derive(Serialize, Deserialize, Debug)]
+    struct HighestRank {
+        old: bool,
+        tier: Tier,
+        patched_tier: String,
+        #[serde(default)]
+        season: Option<EpisodeAndAct>,
+    }
+
+    #[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+    pub struct EpisodeAndAct {
+        episode: u32,
+        act: u32,
+    }
+
+    /// Why an [`EpisodeAndAct`] couldn't be made or parsed.
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    #[non_exhaustive]
+    pub enum SeasonParseError {
+        /// Not of the form `e{episode}a{act}`.
+        InvalidFormat(String),
+        /// Acts are numbered 1 to 3 within an episode.
+        InvalidAct(u32),
+    }
+
+    impl fmt::Display for SeasonParseError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match self {
+                SeasonParseError::InvalidFormat(value) => {
+                    write!(f, "Invalid season {value:?}, expected e.g. \"e10a1\"")
+                }
+                SeasonParseError::InvalidAct(act) => {
+                    write!(f, "Invalid act {act}, acts go from 1 to 3")
+                }
+            }
+        }
+    }
+
+    impl std::error::Error for SeasonParseError {}
+
+    impl EpisodeAndAct {
+        pub fn new(episode: u32, act: u32) -> Result<Self, SeasonParseError> {
+            match act {
+                1..=3 => Ok(Self { episode, act }),
+                _ => Err(SeasonParseError::InvalidAct(act)),
+            }
+        }
+
+        pub fn to_value(&self) -> String {
+            self.to_string()
+        }
+
+        pub fn episode(&self) -> u32 {
+            self.episode
+        }
+
+        pub fn act(&self) -> u32 {
+            self.act
+        }
+
+        pub(crate) fn from_numbers(episode: u32, act: u32) -> Self {
+            Self { episode, act }
+        }
+    }
+
+    impl fmt::Display for EpisodeAndAct {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "e{}a{}", self.episode, self.act)
+        }
+    }
+
+    /// Parses `e{episode}a{act}`, e.g. `e5a2` or `e10a1`.
+    impl FromStr for EpisodeAndAct {
+        type Err = SeasonParseError;
+
+        fn from_str(value: &str) -> Result<Self, Self::Err> {
+            let invalid = || SeasonParseError::InvalidFormat(value.to_string());
+            // Only plain digits, `u32::from_str` would also take a sign
+            let number = |digits: &str| match digits.bytes().all(|byte| byte.is_ascii_digit()) {
+                true => digits.parse::<u32>().map_err(|_| invalid()),
+                false => Err(invalid()),
+            };
+            let (episode, act) = value
+                .strip_prefix('e')
+                .and_then(|rest| rest.split_once('a'))
+                .ok_or_else(invalid)?;
+            Self::new(number(episode)?, number(act)?)
+        }
+    }
+
+    impl Serialize for EpisodeAndAct {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: serde::Serializer,
+        {
+            serializer.collect_str(self)
+        }
+    }
+
+    impl<'de> Deserialize<'de> for EpisodeAndAct {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: serde::Deserializer<'de>,
+        {
+            let string = String::deserialize(deserializer)?;
+            string.parse().map_err(serde::de::Error::custom)
+        }
+    }
+
+    impl MMRData {
+        pub fn puuid(&self) -> &str {
+            &self.puuid
+        }
+
+        pub fn name(&self) -> &str {
+            &self.name
+        }
+
+        pub fn tag(&self) -> &str {
+            &self.tag
+        }
+
+        pub fn current_tier(&self) -> Tier {
+            self.current_data.current_tier
+        }
+
+        /// The current rank's name, e.g. `Gold 2`.
+        pub fn current_tier_patched(&self) -> &str {
+            &self.current_data.current_tier_patched
+        }
+
+        /// RR within the current rank.
+        pub fn ranking_in_tier(&self) -> u32 {
+            self.current_data.ranking_in_tier
+        }
+
+        pub fn elo(&self) -> u32 {
+            self.current_data.elo
+        }
+
+        pub fn mmr_change_to_last_game(&self) -> i32 {
+            self.current_data.mmr_change_to_last_game
+        }
+
+        /// The icons of the current rank.
+        pub fn rank_images(&self) -> &RankImages {
+            &self.current_data.images
+        }
+
+        /// Placement games left before the current act's rank is shown.
+        pub fn games_needed_for_rating(&self) -> u32 {
+            self.current_data.games_needed_for_rating
+        }
+
+        /// Whether the current rank was carried over from an earlier act.
+        pub fn is_old(&self) -> bool {
+            self.current_data.old
+        }
+
+        pub fn highest_tier(&self) -> Tier {
+            self.highest_rank.tier
+        }
+
+        pub fn highest_tier_patched(&self) -> &str {
+            &self.highest_rank.patched_tier
+        }
+
+        /// The act the highest rank was reached in. `None` when the API doesn't say, like for
+        /// `v3` players without a peak.
+        pub fn highest_rank_season(&self) -> Option<&EpisodeAndAct> {
+            self.highest_rank.season.as_ref()
+        }
+
+        /// `None` once the player has finished the current act's placement games.
+        pub fn placement(&self) -> Option<PlacementProgress> {
+            let current = &self.current_data;
+            if current.games_needed_for_rating == 0 {
+                return None;
+            }
+            // A rank carried over from an earlier act isn't a provisional rank for this one
+            let provisional = current.current_tier.is_ranked() && !current.old;
+            Some(PlacementProgress {
+                games_left: current.games_needed_for_rating,
+                provisional_tier: provisional.then_some(current.current_tier),
+                provisional_tier_patched: provisional
+                    .then(|| current.current_tier_patched.clone()),
+            })
+        }
+
+        /// What changed between two snapshots of the same player's MMR.
+        pub fn diff(old: &MMRData, new: &MMRData) -> MmrDelta {
+            MmrDelta {
+                tier_before: old.current_tier(),
+                tier_after: new.current_tier(),
+                rr_delta: i64::from(new.ranking_in_tier()) - i64::from(old.ranking_in_tier()),
+                elo_delta: i64::from(new.elo()) - i64::from(old.elo()),
+                peak_before: old.highest_rank.tier,
+                peak_after: new.highest_rank.tier,
+            }
+        }
+    }
+
+    /// The difference between two [`MMRData`] snapshots, see [`MMRData::diff`].
+    #[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+    #[non_exhaustive]
+    pub struct MmrDelta {
+        pub tier_before: Tier,
+        pub tier_after: Tier,
+        /// The change of RR within the tier, which wraps around at promotions and demotions.
+        pub rr_delta: i64,
+        /// The change of the total RR, which doesn't.
+        pub elo_delta: i64,
+        pub peak_before: Tier,
+        pub peak_after: Tier,
+    }
+
+    impl MmrDelta {
+        pub fn promoted(&self) -> bool {
+            self.tier_after > self.tier_before
+        }
+
+        pub fn demoted(&self) -> bool {
+            self.tier_after < self.tier_before
+        }
+
+        /// Whether the player climbed above their previous peak.
+        pub fn reached_new_peak(&self) -> bool {
+            self.peak_after > self.peak_before
+        }
+
+        /// Whether nothing changed, e.g. when no game was played between the snapshots.
+        pub fn is_empty(&self) -> bool {
+            self.tier_before == self.tier_after
+                && self.rr_delta == 0
+                && self.elo_delta == 0
+                && self.peak_before == self.peak_after
+        }
+    }
+
+    impl ValorantAPIData for MMRData {
+        fn sanity_warnings(&self) -> Vec<crate::sanity::SanityWarning> {
+            let current = &self.current_data;
+            let mut warnings = Vec::new();
+            crate::sanity::check_rank(
+                current.current_tier.id(),
+                current.ranking_in_tier,
+                Some(current.elo),
+                &mut warnings,
+            );
+            warnings
+        }
+    }
+
+    /// How many placement games an act starts with.
+    pub const PLACEMENT_GAMES: u32 = 5;
+
+    /// How far a player is through the placement games of the current act.
+    #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+    #[non_exhaustive]
+    pub struct PlacementProgress {
+        pub games_left: u32,
+        /// The rank shown while placing, if the API reports one.
+        pub provisional_tier: Option<Tier>,
+        pub provisional_tier_patched: Option<String>,
+    }
+
+    impl PlacementProgress {
+        pub fn games_played(&self) -> u32 {
+            PLACEMENT_GAMES.saturating_sub(self.games_left)
+        }
     }
 
-    #[tokio::test]
-    async fn making_a_call() {
-        let api_user = ValorantClient::new();
-        let result = api_user
-            .request::<MMRData>(ValorantApiType::MMRData {
-                region: AccountRegion::EU,
-                name: "NitroSniper",
-                tag: "NERD",
-                filter: None
-            })
-            .await
-            .unwrap();
-        dbg!(result);
+    impl std::fmt::Display for PlacementProgress {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "{} of {PLACEMENT_GAMES} placement games played", self.games_played())?;
+            match &self.provisional_tier_patched {
+                Some(rank) => write!(f, ", provisionally {rank}"),
+                None => Ok(()),
+            }
+        }
     }
-}
 
-pub mod mmr_data {
-    use crate::ValorantAPIData;
-    use serde::{Deserialize, Serialize};
+    /// The `v3/mmr` payload, which groups the rank into `current` and `peak` objects. Convert it
+    /// into [`MMRData`] to share code with the `v2` endpoint.
+    #[derive(Serialize, Deserialize, Debug)]
+    pub struct MMRDataV3 {
+        account: V3Account,
+        peak: Option<V3Peak>,
+        current: V3Current,
+    }
 
     #[derive(Serialize, Deserialize, Debug)]
-    pub struct MMRData {
+    struct V3Account {
         puuid: String,
         name: String,
         tag: String,
-        current_data: CurrentActData,
-        highest_rank: HighestRank,
     }
 
     #[derive(Serialize, Deserialize, Debug)]
-    struct CurrentActData {
-        #[serde(rename = "currenttier")]
-        current_tier: u32,
-        #[serde(rename = "currenttierpatched")]
-        current_tier_patched: String,
-        images: RankImages,
-        ranking_in_tier: u32,
-        mmr_change_to_last_game: i32,
-        elo: u32,
-        games_needed_for_rating: u32,
-        old: bool,
+    struct V3Tier {
+        id: Tier,
+        name: String,
     }
 
     #[derive(Serialize, Deserialize, Debug)]
-    struct RankImages {
-        small: String,
-        large: String,
-        triangle_down: String,
-        triangle_up: String,
+    struct V3Season {
+        short: EpisodeAndAct,
     }
 
     #[derive(Serialize, Deserialize, Debug)]
-    struct HighestRank {
-        old: bool,
-        tier: u32,
-        patched_tier: String,
-        season: EpisodeAndAct
+    struct V3Peak {
+        season: V3Season,
+        tier: V3Tier,
     }
 
-    #[derive(Debug)]
-    pub struct EpisodeAndAct {
-        episode: u32,
-        act: u32,
+    #[derive(Serialize, Deserialize, Debug)]
+    struct V3Current {
+        tier: V3Tier,
+        rr: u32,
+        last_change: i32,
+        elo: u32,
+        games_needed_for_rating: u32,
     }
 
-    impl EpisodeAndAct {
-        pub fn to_value(&self) -> String {
-            format!("e{}a{}", self.episode, self.act)
+    impl ValorantAPIData for MMRDataV3 {}
+
+    impl From<MMRDataV3> for MMRData {
+        fn from(v3: MMRDataV3) -> Self {
+            let current = v3.current;
+            // A player without a peak hasn't finished placements in any act. The current rank
+            // stands in for it, in no act
+            let highest_rank = match v3.peak {
+                Some(peak) => HighestRank {
+                    old: false,
+                    tier: peak.tier.id,
+                    patched_tier: peak.tier.name,
+                    season: Some(peak.season.short),
+                },
+                None => HighestRank {
+                    old: false,
+                    tier: current.tier.id,
+                    patched_tier: current.tier.name.clone(),
+                    season: None,
+                },
+            };
+            MMRData {
+                puuid: v3.account.puuid,
+                name: v3.account.name,
+                tag: v3.account.tag,
+                current_data: CurrentActData {
+                    current_tier: current.tier.id,
+                    current_tier_patched: current.tier.name,
+                    images: RankImages::for_tier(current.tier.id.id()),
+                    ranking_in_tier: current.rr,
+                    mmr_change_to_last_game: current.last_change,
+                    elo: current.elo,
+                    games_needed_for_rating: current.games_needed_for_rating,
+                    old: false,
+                },
+                highest_rank,
+            }
         }
     }
 
-    // Create a Serialize and Deserialize implementation for SeasonAndActData that turn season and
-    // act into a string in the form of "s{season}a{act}"
-    impl Serialize for EpisodeAndAct {
-        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-        where
-            S: serde::Serializer,
-        {
-            let s = format!("e{}a{}", self.episode, self.act);
-            serializer.serialize_str(&s)
+    #[cfg(any(test, feature = "proptest"))]
+    mod strategy {
+        use super::*;
+        use proptest::prelude::*;
+
+        impl Arbitrary for EpisodeAndAct {
+            type Parameters = ();
+            type Strategy = BoxedStrategy<Self>;
+
+            fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+                (0u32..=20, 1u32..=3)
+                    .prop_map(|(episode, act)| EpisodeAndAct { episode, act })
+                    .boxed()
+            }
         }
     }
 
-    impl<'de> Deserialize<'de> for EpisodeAndAct {
-        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
-        where
-            D: serde::Deserializer<'de>,
-        {
-            let string = String::deserialize(deserializer)?;
+    #[cfg(any(test, feature = "test-support"))]
+    mod fake {
+        use super::*;
+        use crate::fake::{tier_name, Fake, FakeRng};
 
-            let chars = string.chars().collect::<Vec<_>>();
-            let (e, episode_number, a, act_number) = match chars.as_slice() {
-                [e, episode_number, a, act_number] => (*e, *episode_number, *a, *act_number),
-                _ => {
-                    return Err(serde::de::Error::custom("Invalid length"));
-                },
-            };
-            // write serde Errors where it checks them and return good error along with the values
-            if e != 'e' || a != 'a' {
-                return Err(serde::de::Error::custom(format!("Invalid format, format recieved: {string}")));
+        impl Fake for MMRData {
+            fn fake_with(rng: &mut FakeRng) -> Self {
+                let current_data = CurrentActData::fake_with(rng);
+                let highest_tier = rng.between(current_data.current_tier.id(), 27);
+                MMRData {
+                    puuid: rng.uuid(),
+                    name: rng.name(),
+                    tag: rng.tag(),
+                    current_data,
+                    highest_rank: HighestRank {
+                        old: false,
+                        tier: Tier::from_id(highest_tier).unwrap_or_default(),
+                        patched_tier: tier_name(highest_tier),
+                        season: Some(EpisodeAndAct::fake_with(rng)),
+                    },
+                }
             }
-            if !episode_number.is_numeric() || !act_number.is_numeric() {
-                return Err(serde::de::Error::custom(format!("Invalid format, format recieved: {string}")));
+        }
+
+        impl Fake for CurrentActData {
+            fn fake_with(rng: &mut FakeRng) -> Self {
+                let tier = rng.between(3, 27);
+                let ranking_in_tier = if tier >= 24 {
+                    rng.between(0, 450)
+                } else {
+                    rng.between(0, 99)
+                };
+                let mmr_change_to_last_game = rng.between(5, 30) as i32;
+                CurrentActData {
+                    current_tier: Tier::from_id(tier).unwrap_or_default(),
+                    current_tier_patched: tier_name(tier),
+                    images: RankImages::for_tier(tier),
+                    ranking_in_tier,
+                    mmr_change_to_last_game: if rng.chance(50) {
+                        mmr_change_to_last_game
+                    } else {
+                        -mmr_change_to_last_game
+                    },
+                    elo: (tier - 3) * 100 + ranking_in_tier,
+                    games_needed_for_rating: 0,
+                    old: false,
+                }
             }
+        }
 
-            // get the data
-            Ok(Self { episode: episode_number.into(), act: act_number.into() })
+        impl Fake for EpisodeAndAct {
+            fn fake_with(rng: &mut FakeRng) -> Self {
+                EpisodeAndAct {
+                    episode: rng.between(1, 9),
+                    act: rng.between(1, 3),
+                }
+            }
         }
     }
 
-    impl ValorantAPIData for MMRData {}
-
-    struct ActRankStats {
+    /// What the MMR endpoint returns when it is filtered to a single act.
+    #[derive(Serialize, Deserialize, Debug)]
+    pub struct ActRankStats {
         wins: u32,
         number_of_games: u32,
         final_rank: u32,
+        final_rank_patched: String,
+        // Older payloads don't list the wins
+        #[serde(default)]
+        act_rank_wins: Vec<ActRankWin>,
+    }
+
+    #[derive(Serialize, Deserialize, Debug)]
+    struct ActRankWin {
+        tier: u32,
+        patched_tier: String,
+    }
+
+    impl ActRankStats {
+        pub fn wins(&self) -> u32 {
+            self.wins
+        }
+
+        pub fn number_of_games(&self) -> u32 {
+            self.number_of_games
+        }
+
+        /// The rank the act ended at, or the current rank while it is live.
+        pub fn final_rank(&self) -> u32 {
+            self.final_rank
+        }
+
+        pub fn final_rank_patched(&self) -> &str {
+            &self.final_rank_patched
+        }
+
+        /// The act rank triangle, rebuilt from the ranks the act's wins were earned at.
+        pub fn triangle(&self) -> crate::act_rank::ActRankTriangle {
+            crate::act_rank::ActRankTriangle::from_wins(
+                self.act_rank_wins.iter().map(|win| win.tier),
+            )
+        }
+    }
+
+    impl ValorantAPIData for ActRankStats {}
+
+    // An act filtered request, which the API answers with an error object in place of the
+    // stats when the player has no games in the act. Anything else is a decoding error.
+    #[cfg(feature = "client")]
+    #[derive(Deserialize, Debug)]
+    #[serde(untagged)]
+    pub(crate) enum ActLeg {
+        Played(ActRankStats),
+        Unplayed(#[allow(dead_code)] NoActData),
+    }
+
+    #[cfg(feature = "client")]
+    #[derive(Deserialize, Debug)]
+    #[serde(deny_unknown_fields)]
+    pub(crate) struct NoActData {
+        // Only there so the error object matches; its message is never shown.
+        #[allow(dead_code)]
+        error: String,
+    }
+
+    #[cfg(feature = "client")]
+    impl ValorantAPIData for ActLeg {}
+
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub enum SeasonFilter {
+        /// The act that is live when the request is made, resolved by the client through the
+        /// content endpoint.
+        Current,
+        Act(EpisodeAndAct),
+    }
 
+    impl From<EpisodeAndAct> for SeasonFilter {
+        fn from(season: EpisodeAndAct) -> Self {
+            SeasonFilter::Act(season)
+        }
     }
-    
 
     #[cfg(test)]
-    mod test {
+    pub(crate) mod test {
         use super::*;
-        use crate::ApiResponse;
+        use crate::fixtures::decode;
+
+        pub(crate) const ACT_RANK_STATS: &str = r#"{
+            "status": 200,
+            "data": {
+                "wins": 12,
+                "number_of_games": 23,
+                "final_rank": 16,
+                "final_rank_patched": "Platinum 2",
+                "act_rank_wins": [
+                    {"patched_tier": "Platinum 3", "tier": 17},
+                    {"patched_tier": "Platinum 2", "tier": 16},
+                    {"patched_tier": "Gold 3", "tier": 14}
+                ]
+            }
+        }"#;
+
+        #[test]
+        fn diffs_snapshots() {
+            let mmr = |edit: fn(&mut serde_json::Value)| {
+                let mut response: serde_json::Value =
+                    serde_json::from_str(crate::fixtures::MMR).unwrap();
+                edit(&mut response["data"]);
+                serde_json::from_value::<MMRData>(response["data"].take()).unwrap()
+            };
+            let old = mmr(|_| {});
+            assert!(MMRData::diff(&old, &old).is_empty());
+
+            let promoted = mmr(|data| {
+                data["current_data"]["currenttier"] = 17.into();
+                data["current_data"]["ranking_in_tier"] = 12.into();
+                data["current_data"]["elo"] = 1412.into();
+            });
+            let delta = MMRData::diff(&old, &promoted);
+            assert!(delta.promoted() && !delta.demoted());
+            assert_eq!((delta.rr_delta, delta.elo_delta), (-35, 65));
+            assert!(!delta.reached_new_peak());
+
+            let peak = mmr(|data| data["highest_rank"]["tier"] = 19.into());
+            assert!(MMRData::diff(&old, &peak).reached_new_peak());
+        }
+
+        #[test]
+        fn v3_converts_to_v2() {
+            let v3: MMRDataV3 = serde_json::from_str(
+                r#"{
+                    "account": {
+                        "puuid": "5a4c1f0e-7d2b-5e93-a1c4-3f6b8e2d9c10",
+                        "name": "Anonymous",
+                        "tag": "0000"
+                    },
+                    "peak": {
+                        "season": {"id": "3e47230a-463c-a301-eb7d-67bb60357d4f", "short": "e5a3"},
+                        "ranked_schema": "ascendant",
+                        "tier": {"id": 18, "name": "Diamond 1"}
+                    },
+                    "current": {
+                        "tier": {"id": 16, "name": "Platinum 2"},
+                        "rr": 47,
+                        "last_change": -11,
+                        "elo": 1347,
+                        "games_needed_for_rating": 0,
+                        "rank_protection_shields": 0,
+                        "leaderboard_placement": null
+                    },
+                    "seasonal": []
+                }"#,
+            )
+            .unwrap();
+            let v2: MMRData = decode(crate::fixtures::MMR);
+            assert_eq!(
+                serde_json::to_value(MMRData::from(v3)).unwrap(),
+                serde_json::to_value(v2).unwrap()
+            );
+        }
+
+        #[test]
+        fn v3_without_a_peak_has_no_peak_act() {
+            let v3: MMRDataV3 = serde_json::from_str(
+                r#"{
+                    "account": {
+                        "puuid": "5a4c1f0e-7d2b-5e93-a1c4-3f6b8e2d9c10",
+                        "name": "Anonymous",
+                        "tag": "0000"
+                    },
+                    "peak": null,
+                    "current": {
+                        "tier": {"id": 0, "name": "Unrated"},
+                        "rr": 0,
+                        "last_change": 0,
+                        "elo": 0,
+                        "games_needed_for_rating": 5
+                    }
+                }"#,
+            )
+            .unwrap();
+            let mmr = MMRData::from(v3);
+            assert_eq!(mmr.highest_rank_season(), None);
+            assert_eq!(mmr.highest_tier_patched(), "Unrated");
+
+            let saved = serde_json::to_string(&mmr).unwrap();
+            let restored: MMRData = serde_json::from_str(&saved).unwrap();
+            assert_eq!(restored.highest_rank_season(), None);
+        }
+
+        #[test]
+        fn reports_placement_progress() {
+            let mmr = |games_needed: u32, old: bool| {
+                let response = crate::fixtures::MMR
+                    .replace(
+                        r#""games_needed_for_rating": 0"#,
+                        &format!(r#""games_needed_for_rating": {games_needed}"#),
+                    )
+                    .replacen(r#""old": false"#, &format!(r#""old": {old}"#), 1);
+                decode::<MMRData>(&response)
+            };
+            assert_eq!(mmr(0, false).placement(), None);
+
+            let placing = mmr(3, false).placement().unwrap();
+            assert_eq!(placing.games_played(), 2);
+            assert_eq!(
+                placing.to_string(),
+                "2 of 5 placement games played, provisionally Platinum 2"
+            );
+            let carried_over = mmr(5, true).placement().unwrap();
+            assert_eq!(carried_over.provisional_tier, None);
+            assert_eq!(carried_over.to_string(), "0 of 5 placement games played");
+        }
+
+        #[test]
+        fn deserialize_act_rank_stats() {
+            let data: ActRankStats = decode(ACT_RANK_STATS);
+            assert_eq!((data.number_of_games(), data.wins()), (23, 12));
+            assert_eq!(data.final_rank(), 16);
+        }
+
+        #[test]
+        fn rebuilds_the_act_rank_triangle() {
+            let data: ActRankStats = decode(ACT_RANK_STATS);
+            assert_eq!((data.final_rank(), data.final_rank_patched()), (16, "Platinum 2"));
+            let triangle = data.triangle();
+            assert_eq!(triangle.act_rank(), Some(17));
+            assert_eq!(triangle.rows()[1], [Some(16), Some(14), None]);
+        }
+
         #[test]
         fn deserialize_response() {
             let response = r#"{
@@ -285,12 +2754,16 @@ pub mod mmr_data {
                     }
                 }
             }"#;
-            let result = serde_json::from_str::<ApiResponse<MMRData>>(response).unwrap();
-            dbg!(result);
+            let data: MMRData = decode(response);
+            assert_eq!(data.elo(), 1347);
+            assert_eq!(data.games_needed_for_rating(), 0);
+            assert!(!data.is_old());
+            assert_eq!(data.highest_tier(), Tier::Diamond1);
+            assert!(data.rank_images().small().ends_with("/16/smallicon.png"));
         }
 
         // write edge cases for season and act
-        // 1. act can't be greater than 3, season must be length of 4 with value "s{season}a{act}"
+        // 1. act can't be greater than 3, season must be of the form "e{episode}a{act}"
         #[test]
         fn edge_cases() {
             let season_input = r#""e5a5""#;
@@ -319,12 +2792,48 @@ pub mod mmr_data {
             let season_input = r#""sdfa""#;
             let result = serde_json::from_str::<EpisodeAndAct>(season_input);
             assert!(result.is_err());
+
+            let season_input = r#""e5aé""#;
+            let result = serde_json::from_str::<EpisodeAndAct>(season_input);
+            assert!(result.is_err());
+        }
+
+        #[test]
+        fn episodes_past_nine_parse() {
+            let season: EpisodeAndAct = "e10a1".parse().unwrap();
+            assert_eq!((season.episode(), season.act()), (10, 1));
+            assert_eq!(season.to_string(), "e10a1");
+            assert_eq!(serde_json::to_string(&season).unwrap(), r#""e10a1""#);
+            assert!(EpisodeAndAct::from_numbers(9, 3) < season);
+
+            assert_eq!(EpisodeAndAct::new(12, 3), "e12a3".parse());
+            assert_eq!(EpisodeAndAct::new(5, 4), Err(SeasonParseError::InvalidAct(4)));
+            for invalid in ["e10", "ea1", "e+1a1", "e1a", "10a1", "e99999999999a1"] {
+                assert!(matches!(
+                    invalid.parse::<EpisodeAndAct>(),
+                    Err(SeasonParseError::InvalidFormat(_))
+                ));
+            }
+        }
+
+        proptest::proptest! {
+            #[test]
+            fn episode_and_act_round_trips(season in proptest::prelude::any::<EpisodeAndAct>()) {
+                let parsed = season.to_value().parse::<EpisodeAndAct>().unwrap();
+                proptest::prop_assert_eq!(parsed.to_value(), season.to_value());
+            }
+
+            #[test]
+            fn episode_and_act_parser_never_panics(input in ".*") {
+                let _ = input.parse::<EpisodeAndAct>();
+            }
         }
     }
 }
 
 mod account_data {
     use crate::{AccountRegion, ValorantAPIData};
+    use chrono::{DateTime, Utc};
     use serde::{Deserialize, Serialize};
 
     #[derive(Serialize, Deserialize, Debug)]
@@ -339,7 +2848,7 @@ mod account_data {
         last_update_raw: u32,
     }
 
-    #[derive(Serialize, Deserialize, Debug)]
+    #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
     pub struct ProfileBanner {
         small: String,
         large: String,
@@ -347,16 +2856,185 @@ mod account_data {
         id: String,
     }
 
+    impl AccountData {
+        pub fn puuid(&self) -> &str {
+            &self.puuid
+        }
+
+        pub fn region(&self) -> AccountRegion {
+            self.region
+        }
+
+        pub fn account_level(&self) -> u32 {
+            self.account_level
+        }
+
+        pub fn name(&self) -> &str {
+            &self.name
+        }
+
+        pub fn tag(&self) -> &str {
+            &self.tag
+        }
+
+        pub fn card(&self) -> &ProfileBanner {
+            &self.card
+        }
+
+        /// When the account was last refreshed. The payload also has this as a phrase like
+        /// `12 minutes ago`, which is fixed at fetch time; format this instead with
+        /// [`TimeAgo`](crate::time_ago::TimeAgo).
+        pub fn last_update(&self) -> DateTime<Utc> {
+            DateTime::from_timestamp(i64::from(self.last_update_raw), 0).unwrap_or_default()
+        }
+    }
+
+    impl ProfileBanner {
+        pub fn id(&self) -> &str {
+            &self.id
+        }
+
+        pub fn small(&self) -> &str {
+            &self.small
+        }
+
+        pub fn large(&self) -> &str {
+            &self.large
+        }
+
+        pub fn wide(&self) -> &str {
+            &self.wide
+        }
+    }
+
     impl ValorantAPIData for AccountData {}
 
+    impl ProfileBanner {
+        pub(crate) fn for_card(id: String) -> Self {
+            let art =
+                |kind: &str| format!("https://media.valorant-api.com/playercards/{id}/{kind}art.png");
+            ProfileBanner {
+                small: art("small"),
+                large: art("large"),
+                wide: art("wide"),
+                id,
+            }
+        }
+    }
+
+    /// The `v2/account` payload, which only references the card by ID and reports the last
+    /// update as a timestamp. Convert it into [`AccountData`] to share code with `v1`.
+    #[derive(Serialize, Deserialize, Debug)]
+    pub struct AccountDataV2 {
+        puuid: String,
+        region: AccountRegion,
+        account_level: u32,
+        name: String,
+        tag: String,
+        card: String,
+        updated_at: DateTime<Utc>,
+    }
+
+    impl AccountDataV2 {
+        pub fn puuid(&self) -> &str {
+            &self.puuid
+        }
+
+        pub fn region(&self) -> AccountRegion {
+            self.region
+        }
+
+        pub fn account_level(&self) -> u32 {
+            self.account_level
+        }
+
+        pub fn name(&self) -> &str {
+            &self.name
+        }
+
+        pub fn tag(&self) -> &str {
+            &self.tag
+        }
+
+        /// The ID of the equipped player card.
+        pub fn card_id(&self) -> &str {
+            &self.card
+        }
+
+        pub fn updated_at(&self) -> DateTime<Utc> {
+            self.updated_at
+        }
+    }
+
+    impl ValorantAPIData for AccountDataV2 {}
+
+    impl From<AccountDataV2> for AccountData {
+        fn from(v2: AccountDataV2) -> Self {
+            AccountData {
+                puuid: v2.puuid,
+                region: v2.region,
+                account_level: v2.account_level,
+                name: v2.name,
+                tag: v2.tag,
+                card: ProfileBanner::for_card(v2.card),
+                last_update: v2.updated_at.to_rfc3339(),
+                last_update_raw: v2.updated_at.timestamp() as u32,
+            }
+        }
+    }
+
+    #[cfg(any(test, feature = "test-support"))]
+    mod fake {
+        use super::*;
+        use crate::fake::{Fake, FakeRng};
+
+        impl Fake for AccountData {
+            fn fake_with(rng: &mut FakeRng) -> Self {
+                let minutes_ago = rng.between(0, 59);
+                AccountData {
+                    puuid: rng.uuid(),
+                    region: AccountRegion::fake_with(rng),
+                    account_level: rng.between(1, 500),
+                    name: rng.name(),
+                    tag: rng.tag(),
+                    card: ProfileBanner::fake_with(rng),
+                    last_update: match minutes_ago {
+                        0 => "Now".to_string(),
+                        1 => "1 minute ago".to_string(),
+                        minutes => format!("{minutes} minutes ago"),
+                    },
+                    last_update_raw: 1_676_749_780 - minutes_ago * 60,
+                }
+            }
+        }
+
+        impl Fake for ProfileBanner {
+            fn fake_with(rng: &mut FakeRng) -> Self {
+                ProfileBanner::for_card(rng.uuid())
+            }
+        }
+
+        impl Fake for AccountRegion {
+            fn fake_with(rng: &mut FakeRng) -> Self {
+                match rng.between(0, 5) {
+                    0 => AccountRegion::EU,
+                    1 => AccountRegion::NA,
+                    2 => AccountRegion::AP,
+                    3 => AccountRegion::KR,
+                    4 => AccountRegion::BR,
+                    _ => AccountRegion::LATAM,
+                }
+            }
+        }
+    }
+
     #[cfg(test)]
-    mod test {
+    pub(crate) mod test {
         use super::*;
-        use crate::{ApiResponse, ValorantClient};
+        use crate::fixtures::decode;
+        use crate::ApiResponse;
 
-        #[test]
-        fn deserialize_response() {
-            let response_200 = r#"{
+        pub(crate) const RESPONSE_200: &str = r#"{
                 "status": 200,
                 "data": {
                     "puuid": "b44adaae-ab83-5001-a296-89ea0de0bce3",
@@ -375,10 +3053,49 @@ mod account_data {
                 }
             }"#;
 
-            let result: ApiResponse<AccountData> = serde_json::from_str(response_200).unwrap();
+        #[test]
+        fn deserialize_response() {
+            let result: ApiResponse<AccountData> = serde_json::from_str(RESPONSE_200).unwrap();
             dbg!(result);
         }
 
+        #[test]
+        fn last_update_is_a_timestamp() {
+            let data: AccountData = decode(RESPONSE_200);
+            let fetched = DateTime::from_timestamp(1_676_750_500, 0).unwrap();
+            assert_eq!(
+                crate::time_ago::TimeAgo::between(data.last_update(), fetched).to_string(),
+                "12 minutes ago"
+            );
+        }
+
+        #[test]
+        fn v2_converts_to_v1() {
+            let v2: AccountDataV2 = serde_json::from_str(
+                r#"{
+                    "puuid": "b44adaae-ab83-5001-a296-89ea0de0bce3",
+                    "region": "eu",
+                    "account_level": 125,
+                    "name": "NitroSniper",
+                    "tag": "NERD",
+                    "card": "bb6ae873-43ec-efb4-3ea6-93ac00a82d4e",
+                    "title": "e3ca05a4-4e44-9afe-3791-7d96ca8f71fa",
+                    "platforms": ["PC"],
+                    "updated_at": "2023-02-18T19:49:40Z"
+                }"#,
+            )
+            .unwrap();
+            let v1: AccountData = decode(RESPONSE_200);
+            assert_eq!(v2.card_id(), "bb6ae873-43ec-efb4-3ea6-93ac00a82d4e");
+            assert_eq!(v2.updated_at().timestamp(), v1.last_update().timestamp());
+            let converted = AccountData::from(v2);
+            assert_eq!(converted.last_update_raw, v1.last_update_raw);
+            assert_eq!(
+                serde_json::to_value(converted.card()).unwrap(),
+                serde_json::to_value(v1.card()).unwrap()
+            );
+        }
+
         #[test]
         fn deserialize_response_na() {
             let response_200 = r#"{
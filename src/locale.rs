@@ -0,0 +1,269 @@
+//! Translations of the crate's human readable output.
+//!
+//! Messages live in catalogs written in a subset of Fluent: one `id = text` per line, `#`
+//! comments, and variables as `{ $name }`. Catalogs for a few languages are built in, and
+//! applications can load their own. Messages a catalog leaves out fall back to English.
+
+use crate::currency::Currency;
+use crate::mmr_data::{PlacementProgress, PLACEMENT_GAMES};
+use std::collections::HashMap;
+use std::fmt;
+
+const BUILT_IN: [(&str, &str); 5] = [
+    ("en", include_str!("../locales/en.ftl")),
+    ("de", include_str!("../locales/de.ftl")),
+    ("es", include_str!("../locales/es.ftl")),
+    ("fr", include_str!("../locales/fr.ftl")),
+    ("pt-BR", include_str!("../locales/pt-BR.ftl")),
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum CatalogError {
+    /// A line that isn't a comment or an `id = text` message, numbered from 1.
+    InvalidLine(usize),
+    /// A `{` that doesn't hold a `$variable`, on this line.
+    InvalidPlaceable(usize),
+}
+
+impl fmt::Display for CatalogError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CatalogError::InvalidLine(line) => write!(f, "Line {line} is not a message"),
+            CatalogError::InvalidPlaceable(line) => {
+                write!(f, "Line {line} has a placeable that isn't a variable")
+            }
+        }
+    }
+}
+
+impl std::error::Error for CatalogError {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Part {
+    Text(String),
+    Variable(String),
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Catalog {
+    messages: HashMap<String, Vec<Part>>,
+}
+
+impl Catalog {
+    /// The locales with built in catalogs, e.g. `de` or `pt-BR`.
+    pub fn built_in_locales() -> impl Iterator<Item = &'static str> {
+        BUILT_IN.into_iter().map(|(locale, _)| locale)
+    }
+
+    /// The built in catalog for `locale`, or English for locales without one. A region the
+    /// crate has no catalog for falls back to its language, so `de-AT` uses `de`.
+    pub fn for_locale(locale: &str) -> Self {
+        let language = locale.split(['-', '_']).next().unwrap_or(locale);
+        let source = BUILT_IN
+            .iter()
+            .find(|(code, _)| code.eq_ignore_ascii_case(locale))
+            .or_else(|| {
+                BUILT_IN
+                    .iter()
+                    .find(|(code, _)| code.eq_ignore_ascii_case(language))
+            })
+            .map_or(BUILT_IN[0].1, |(_, source)| source);
+        Catalog::parse(source).expect("built in catalogs are valid")
+    }
+
+    pub fn english() -> Self {
+        Catalog::for_locale("en")
+    }
+
+    pub fn parse(source: &str) -> Result<Self, CatalogError> {
+        let mut messages = HashMap::new();
+        for (index, line) in source.lines().enumerate() {
+            let line_number = index + 1;
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (id, text) = line
+                .split_once('=')
+                .ok_or(CatalogError::InvalidLine(line_number))?;
+            let id = id.trim();
+            if id.is_empty() || !id.chars().all(|c| c.is_alphanumeric() || c == '-') {
+                return Err(CatalogError::InvalidLine(line_number));
+            }
+            messages.insert(id.to_string(), parse_pattern(text.trim(), line_number)?);
+        }
+        Ok(Catalog { messages })
+    }
+
+    /// Formats a message, falling back to its English text when this catalog doesn't have it.
+    /// Variables without a value are left as `{$name}`.
+    pub fn format(&self, id: &str, args: &[(&str, &dyn fmt::Display)]) -> String {
+        let parts = match self.messages.get(id) {
+            Some(parts) => parts.clone(),
+            None => match Catalog::english().messages.remove(id) {
+                Some(parts) => parts,
+                None => return id.to_string(),
+            },
+        };
+        parts
+            .iter()
+            .map(|part| match part {
+                Part::Text(text) => text.clone(),
+                Part::Variable(name) => args
+                    .iter()
+                    .find(|(arg, _)| arg == name)
+                    .map_or_else(|| format!("{{${name}}}"), |(_, value)| value.to_string()),
+            })
+            .collect()
+    }
+}
+
+fn parse_pattern(text: &str, line: usize) -> Result<Vec<Part>, CatalogError> {
+    let mut parts = Vec::new();
+    let mut rest = text;
+    while let Some(start) = rest.find('{') {
+        if start > 0 {
+            parts.push(Part::Text(rest[..start].to_string()));
+        }
+        let end = rest[start..]
+            .find('}')
+            .ok_or(CatalogError::InvalidPlaceable(line))?;
+        let variable = rest[start + 1..start + end]
+            .trim()
+            .strip_prefix('$')
+            .filter(|name| !name.is_empty())
+            .ok_or(CatalogError::InvalidPlaceable(line))?;
+        parts.push(Part::Variable(variable.to_string()));
+        rest = &rest[start + end + 1..];
+    }
+    if !rest.is_empty() {
+        parts.push(Part::Text(rest.to_string()));
+    }
+    Ok(parts)
+}
+
+/// Output that can be produced in other languages than the English of `Display`. Applications
+/// may implement it for their own output.
+pub trait Localize {
+    fn localize(&self, catalog: &Catalog) -> String;
+}
+
+impl Localize for Currency {
+    fn localize(&self, catalog: &Catalog) -> String {
+        catalog.format(
+            match self {
+                Currency::ValorantPoints => "currency-valorant-points",
+                Currency::RadianitePoints => "currency-radianite-points",
+                Currency::KingdomCredits => "currency-kingdom-credits",
+            },
+            &[],
+        )
+    }
+}
+
+impl Localize for PlacementProgress {
+    fn localize(&self, catalog: &Catalog) -> String {
+        let played = self.games_played();
+        match &self.provisional_tier_patched {
+            Some(rank) => catalog.format(
+                "placement-progress-provisional",
+                &[
+                    ("played", &played),
+                    ("total", &PLACEMENT_GAMES),
+                    ("rank", rank),
+                ],
+            ),
+            None => catalog.format(
+                "placement-progress",
+                &[("played", &played), ("total", &PLACEMENT_GAMES)],
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "analytics")]
+impl Localize for crate::analytics::GamesDistribution {
+    fn localize(&self, catalog: &Catalog) -> String {
+        match (self.median(), self.percentile(0.9)) {
+            (Some(median), Some(unlucky)) => catalog.format(
+                "games-to-reach",
+                &[
+                    ("median", &median),
+                    ("unlucky", &unlucky),
+                    ("share", &format!("{:.0}", self.reached_share() * 100.0)),
+                ],
+            ),
+            _ => catalog.format("games-not-reached", &[]),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::tier::Tier;
+
+    #[test]
+    fn built_in_catalogs_cover_english() {
+        let english = Catalog::english();
+        for locale in Catalog::built_in_locales() {
+            let catalog = Catalog::for_locale(locale);
+            assert_eq!(
+                catalog.messages.len(),
+                english.messages.len(),
+                "{locale} is missing messages"
+            );
+        }
+    }
+
+    #[test]
+    fn localizes_output() {
+        let german = Catalog::for_locale("de-AT");
+        assert_eq!(
+            Currency::RadianitePoints.localize(&german),
+            "Radianit-Punkte"
+        );
+        // English matches Display
+        let english = Catalog::for_locale("ko");
+        assert_eq!(
+            Currency::KingdomCredits.localize(&english),
+            Currency::KingdomCredits.to_string()
+        );
+
+        let placing = PlacementProgress {
+            games_left: 2,
+            provisional_tier: Some(Tier::Platinum2),
+            provisional_tier_patched: Some("Platinum 2".to_string()),
+        };
+        assert_eq!(placing.localize(&english), placing.to_string());
+        assert_eq!(
+            placing.localize(&Catalog::for_locale("pt-BR")),
+            "3 de 5 partidas de classificação jogadas, provisoriamente Platinum 2"
+        );
+    }
+
+    #[test]
+    fn custom_catalogs_fall_back_to_english() {
+        let catalog =
+            Catalog::parse("# Only one message\ncurrency-valorant-points = VP ({ $unused })\n")
+                .unwrap();
+        assert_eq!(
+            Currency::ValorantPoints.localize(&catalog),
+            "VP ({$unused})"
+        );
+        assert_eq!(
+            Currency::RadianitePoints.localize(&catalog),
+            "Radianite Points"
+        );
+
+        assert_eq!(
+            Catalog::parse("no equals").unwrap_err(),
+            CatalogError::InvalidLine(1)
+        );
+        assert_eq!(
+            Catalog::parse("\nid = { name }").unwrap_err(),
+            CatalogError::InvalidPlaceable(2)
+        );
+    }
+}
@@ -0,0 +1,354 @@
+//! The competitive map pool and map vetoes over it, for tournament bots running bans.
+//!
+//! The content endpoint lists every map in the game but doesn't say which ones are in the
+//! competitive rotation. [`MapPool::from_content`] keeps the standard spike maps, and
+//! [`MapPool::played_in`] narrows them down to the maps recent competitive matches were played
+//! on, which only ever come from the rotation.
+
+use crate::content::Content;
+#[cfg(feature = "client")]
+use crate::error::ValorantError;
+use crate::match_data::{MatchData, MatchMode};
+#[cfg(feature = "client")]
+use crate::{ValorantApiType, ValorantClient};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct MapPool {
+    maps: Vec<String>,
+}
+
+impl MapPool {
+    pub fn new<S: Into<String>>(maps: impl IntoIterator<Item = S>) -> Self {
+        let mut pool = MapPool { maps: Vec::new() };
+        for map in maps {
+            let map = map.into();
+            if !pool.contains(&map) {
+                pool.maps.push(map);
+            }
+        }
+        pool
+    }
+
+    /// Every standard map the content endpoint lists, rotated out or not.
+    pub fn from_content(content: &Content) -> Self {
+        MapPool::new(
+            content
+                .maps()
+                .iter()
+                .filter(|map| map.is_standard())
+                .map(|map| map.name()),
+        )
+    }
+
+    /// Keeps the maps competitive matches among `matches` were played on. Other modes are
+    /// ignored, as they rotate differently.
+    pub fn played_in<'a>(mut self, matches: impl IntoIterator<Item = &'a MatchData>) -> Self {
+        let played: Vec<&str> = matches
+            .into_iter()
+            .filter(|data| *data.mode() == MatchMode::Competitive)
+            .map(|data| data.map())
+            .collect();
+        self.maps
+            .retain(|map| played.iter().any(|played| played.eq_ignore_ascii_case(map)));
+        self
+    }
+
+    pub fn without(mut self, map: &str) -> Self {
+        self.maps.retain(|known| !known.eq_ignore_ascii_case(map));
+        self
+    }
+
+    /// In the order the content endpoint lists them.
+    pub fn maps(&self) -> &[String] {
+        &self.maps
+    }
+
+    pub fn contains(&self, map: &str) -> bool {
+        self.find(map).is_some()
+    }
+
+    fn find(&self, map: &str) -> Option<&String> {
+        self.maps
+            .iter()
+            .find(|known| known.eq_ignore_ascii_case(map))
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum VetoFormat {
+    Bo1,
+    Bo3,
+    Bo5,
+}
+
+impl VetoFormat {
+    pub fn games(self) -> usize {
+        match self {
+            VetoFormat::Bo1 => 1,
+            VetoFormat::Bo3 => 3,
+            VetoFormat::Bo5 => 5,
+        }
+    }
+}
+
+/// The team that starts the veto is `A`, e.g. the winner of a coin toss.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum VetoTeam {
+    A,
+    B,
+}
+
+impl VetoTeam {
+    pub fn opponent(self) -> Self {
+        match self {
+            VetoTeam::A => VetoTeam::B,
+            VetoTeam::B => VetoTeam::A,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum VetoAction {
+    Ban,
+    Pick,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VetoStep {
+    pub team: VetoTeam,
+    pub action: VetoAction,
+}
+
+/// A map that will be played, and who picked it. The decider is picked by nobody.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct VetoMap {
+    pub map: String,
+    pub picked_by: Option<VetoTeam>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum VetoError {
+    /// The pool has fewer maps than the format plays.
+    PoolTooSmall {
+        maps: usize,
+        games: usize,
+    },
+    NotInPool(String),
+    AlreadyVetoed(String),
+    Finished,
+}
+
+impl fmt::Display for VetoError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VetoError::PoolTooSmall { maps, games } => {
+                write!(f, "A pool of {maps} maps is too small for {games} games")
+            }
+            VetoError::NotInPool(map) => write!(f, "{map} isn't in the map pool"),
+            VetoError::AlreadyVetoed(map) => write!(f, "{map} was already banned or picked"),
+            VetoError::Finished => write!(f, "The veto is over"),
+        }
+    }
+}
+
+impl std::error::Error for VetoError {}
+
+/// A ban and pick sequence over a map pool.
+///
+/// Both teams ban once, then pick one map each until all but the decider are picked, and ban
+/// the rest in turns. A best of one is bans only. The teams alternate throughout, starting with
+/// [`VetoTeam::A`], which for a best of three over seven maps gives the usual ban, ban, pick,
+/// pick, ban, ban and decider.
+#[derive(Debug, Clone)]
+pub struct Veto {
+    pool: MapPool,
+    steps: Vec<VetoStep>,
+    history: Vec<(VetoStep, String)>,
+}
+
+impl Veto {
+    pub fn new(pool: MapPool, format: VetoFormat) -> Result<Self, VetoError> {
+        let (maps, games) = (pool.maps.len(), format.games());
+        if maps < games {
+            return Err(VetoError::PoolTooSmall { maps, games });
+        }
+        let bans = maps - games;
+        let opening_bans = if games == 1 { bans } else { bans.min(2) };
+        let actions = std::iter::repeat_n(VetoAction::Ban, opening_bans)
+            .chain(std::iter::repeat_n(VetoAction::Pick, games - 1))
+            .chain(std::iter::repeat_n(VetoAction::Ban, bans - opening_bans));
+        let mut team = VetoTeam::A;
+        let steps = actions
+            .map(|action| {
+                let step = VetoStep { team, action };
+                team = team.opponent();
+                step
+            })
+            .collect();
+        Ok(Veto {
+            pool,
+            steps,
+            history: Vec::new(),
+        })
+    }
+
+    /// Who bans or picks next, `None` once only the decider is left.
+    pub fn next_step(&self) -> Option<VetoStep> {
+        self.steps.get(self.history.len()).copied()
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.next_step().is_none()
+    }
+
+    /// Bans or picks `map` for the team whose turn it is. Map names are compared without case.
+    pub fn choose(&mut self, map: &str) -> Result<VetoStep, VetoError> {
+        let step = self.next_step().ok_or(VetoError::Finished)?;
+        let map = self
+            .pool
+            .find(map)
+            .ok_or_else(|| VetoError::NotInPool(map.to_string()))?
+            .clone();
+        if self.history.iter().any(|(_, vetoed)| *vetoed == map) {
+            return Err(VetoError::AlreadyVetoed(map));
+        }
+        self.history.push((step, map));
+        Ok(step)
+    }
+
+    /// The maps nobody has banned or picked yet.
+    pub fn remaining(&self) -> Vec<&str> {
+        self.pool
+            .maps
+            .iter()
+            .filter(|map| !self.history.iter().any(|(_, vetoed)| vetoed == *map))
+            .map(String::as_str)
+            .collect()
+    }
+
+    /// Every ban and pick so far, in order.
+    pub fn history(&self) -> &[(VetoStep, String)] {
+        &self.history
+    }
+
+    /// The maps to play in order, once the veto is finished: the picks, then the decider.
+    pub fn maps(&self) -> Option<Vec<VetoMap>> {
+        if !self.is_finished() {
+            return None;
+        }
+        let picks = self
+            .history
+            .iter()
+            .filter(|(step, _)| step.action == VetoAction::Pick)
+            .map(|(step, map)| VetoMap {
+                map: map.clone(),
+                picked_by: Some(step.team),
+            });
+        let decider = self.remaining().into_iter().map(|map| VetoMap {
+            map: map.to_string(),
+            picked_by: None,
+        });
+        Some(picks.chain(decider).collect())
+    }
+}
+
+#[cfg(feature = "client")]
+impl ValorantClient<'_> {
+    /// Every standard map from the content endpoint. Narrow it down to the rotation with
+    /// [`MapPool::played_in`].
+    pub async fn map_pool(&self) -> Result<MapPool, ValorantError> {
+        let content = self
+            .send::<Content>(&ValorantApiType::Content { locale: None })
+            .await?;
+        Ok(MapPool::from_content(&content))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    #[cfg(feature = "client")]
+    use crate::fixtures::{decode, CONTENT, MATCH_HISTORY};
+    #[cfg(feature = "client")]
+    use crate::mock_server::{MockResponse, MockServer};
+
+    fn seven_maps() -> MapPool {
+        MapPool::new([
+            "Ascent", "Bind", "Haven", "Split", "Icebox", "Breeze", "Lotus",
+        ])
+    }
+
+    #[cfg(feature = "client")]
+    #[tokio::test]
+    async fn the_pool_comes_from_the_content_endpoint() {
+        let server =
+            MockServer::routes(vec![("/v1/content", MockResponse::json(200, CONTENT))]).await;
+        let url = server.url();
+        let client = ValorantClient::new().change_api_endpoint(&url);
+
+        let pool = client.map_pool().await.unwrap();
+        assert_eq!(pool.maps().len(), 9);
+        assert!(pool.contains("lotus"));
+        assert!(!pool.contains("The Range"));
+        assert!(!pool.contains("District"));
+
+        let data: Vec<MatchData> = decode(MATCH_HISTORY);
+        assert_eq!(pool.played_in(&data).maps(), ["Ascent"]);
+    }
+
+    #[test]
+    fn best_of_three_runs_the_usual_sequence() {
+        let mut veto = Veto::new(seven_maps(), VetoFormat::Bo3).unwrap();
+        let sequence: Vec<_> = veto.steps.iter().map(|step| step.action).collect();
+        use VetoAction::{Ban, Pick};
+        assert_eq!(sequence, [Ban, Ban, Pick, Pick, Ban, Ban]);
+
+        assert_eq!(veto.choose("ascent").unwrap().team, VetoTeam::A);
+        assert_eq!(
+            veto.choose("Ascent"),
+            Err(VetoError::AlreadyVetoed("Ascent".to_string()))
+        );
+        assert_eq!(
+            veto.choose("Pearl"),
+            Err(VetoError::NotInPool("Pearl".to_string()))
+        );
+        for map in ["Bind", "Haven", "Split", "Icebox", "Breeze"] {
+            veto.choose(map).unwrap();
+        }
+        assert!(veto.is_finished());
+        assert_eq!(veto.choose("Lotus"), Err(VetoError::Finished));
+        assert_eq!(
+            veto.maps().unwrap(),
+            [
+                VetoMap {
+                    map: "Haven".to_string(),
+                    picked_by: Some(VetoTeam::A),
+                },
+                VetoMap {
+                    map: "Split".to_string(),
+                    picked_by: Some(VetoTeam::B),
+                },
+                VetoMap {
+                    map: "Lotus".to_string(),
+                    picked_by: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn best_of_one_is_bans_only() {
+        let veto = Veto::new(seven_maps(), VetoFormat::Bo1).unwrap();
+        assert_eq!(veto.steps.len(), 6);
+        assert!(veto.steps.iter().all(|step| step.action == VetoAction::Ban));
+        assert_eq!(veto.steps[5].team, VetoTeam::B);
+        assert_eq!(
+            Veto::new(MapPool::new(["Ascent", "Bind"]), VetoFormat::Bo3).unwrap_err(),
+            VetoError::PoolTooSmall { maps: 2, games: 3 }
+        );
+    }
+}
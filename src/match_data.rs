@@ -0,0 +1,1091 @@
+use crate::account_data::ProfileBanner;
+use crate::ids::{MatchId, PlayerIdentity};
+use crate::match_kind::{PlayerEconomy, SpikeRounds};
+use crate::sanity::SanityWarning;
+use crate::ValorantAPIData;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct MatchData {
+    metadata: MatchMetadata,
+    players: MatchPlayers,
+    #[serde(default)]
+    teams: MatchTeams,
+    #[serde(default)]
+    observers: Vec<Observer>,
+    #[serde(default)]
+    coaches: Vec<Coach>,
+    #[serde(default)]
+    rounds: Vec<RoundSummary>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct MatchMetadata {
+    map: String,
+    game_version: String,
+    game_length: u64,
+    game_start: u64,
+    game_start_patched: String,
+    rounds_played: u32,
+    mode: String,
+    #[serde(default)]
+    mode_id: MatchMode,
+    #[serde(default)]
+    queue: String,
+    season_id: String,
+    #[serde(rename = "matchid")]
+    match_id: MatchId,
+    region: String,
+    cluster: String,
+    #[serde(default)]
+    premier_info: Option<PremierInfo>,
+}
+
+/// Links a Premier match to its tournament. Both IDs are null outside of Premier.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct PremierInfo {
+    tournament_id: Option<String>,
+    matchup_id: Option<String>,
+}
+
+impl PremierInfo {
+    pub fn tournament_id(&self) -> Option<&str> {
+        self.tournament_id.as_deref()
+    }
+
+    pub fn matchup_id(&self) -> Option<&str> {
+        self.matchup_id.as_deref()
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+struct MatchPlayers {
+    all_players: Vec<MatchPlayer>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct MatchPlayer {
+    puuid: String,
+    name: String,
+    tag: String,
+    team: String,
+    level: u32,
+    character: String,
+    // Custom games have no rank, so the tier is 0 and its name may be missing
+    #[serde(rename = "currenttier", default)]
+    current_tier: u32,
+    #[serde(rename = "currenttier_patched", default)]
+    current_tier_patched: String,
+    #[serde(default)]
+    party_id: String,
+    // Bots and some older matches have no customization
+    #[serde(default)]
+    player_card: String,
+    #[serde(default)]
+    player_title: String,
+    #[serde(default)]
+    preferred_level_border: Option<String>,
+    // Missing from older matches
+    #[serde(default)]
+    behavior: Behavior,
+    stats: PlayerStats,
+    // Only sent for modes with an economy, read through `StandardMatch::economy`
+    #[serde(default)]
+    economy: Option<PlayerEconomy>,
+}
+
+/// The card, title and level border a player displayed in a match. Titles are only an ID, their
+/// text comes from [`AssetClient::player_title`](crate::assets::AssetClient::player_title).
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct PlayerCustomization {
+    pub card: Option<ProfileBanner>,
+    pub title_id: Option<String>,
+    /// Missing for players who never picked a border, who show the one of their level.
+    pub level_border: Option<LevelBorder>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct LevelBorder {
+    pub id: String,
+    /// The frame around the level number on the scoreboard.
+    pub level_number_appearance: String,
+    /// The frame around the small card art.
+    pub small_player_card_appearance: String,
+}
+
+impl LevelBorder {
+    fn for_id(id: String) -> Self {
+        let art =
+            |kind: &str| format!("https://media.valorant-api.com/levelborders/{id}/{kind}.png");
+        LevelBorder {
+            level_number_appearance: art("levelnumberappearance"),
+            small_player_card_appearance: art("smallplayercardappearance"),
+            id,
+        }
+    }
+}
+
+/// A spectator of a custom game, usually a caster or tournament admin.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Observer {
+    puuid: String,
+    name: String,
+    tag: String,
+    #[serde(default)]
+    team: String,
+    level: u32,
+    #[serde(default)]
+    party_id: String,
+}
+
+impl Observer {
+    pub fn puuid(&self) -> &str {
+        &self.puuid
+    }
+
+    pub fn identity(&self) -> PlayerIdentity {
+        PlayerIdentity::from_fields(&self.puuid, &self.name, &self.tag)
+    }
+}
+
+/// A coach attached to one of the teams of a custom game.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Coach {
+    puuid: String,
+    team: String,
+}
+
+impl Coach {
+    pub fn puuid(&self) -> &str {
+        &self.puuid
+    }
+
+    pub fn team(&self) -> &str {
+        &self.team
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct PlayerStats {
+    score: u32,
+    kills: u32,
+    deaths: u32,
+    assists: u32,
+    bodyshots: u32,
+    headshots: u32,
+    legshots: u32,
+}
+
+/// What the game's behavior detection noticed about a player during the match.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[non_exhaustive]
+pub struct Behavior {
+    pub afk_rounds: u32,
+    pub friendly_fire: FriendlyFire,
+    /// Rounds the player didn't leave spawn in.
+    pub rounds_in_spawn: u32,
+}
+
+/// Damage dealt between teammates.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[non_exhaustive]
+pub struct FriendlyFire {
+    pub incoming: u32,
+    pub outgoing: u32,
+}
+
+/// The behavior that can get a player penalized, for accountability bots.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[non_exhaustive]
+pub struct PenaltyFlags {
+    pub afk: bool,
+    pub friendly_fire: bool,
+    pub stayed_in_spawn: bool,
+}
+
+impl PenaltyFlags {
+    pub fn any(&self) -> bool {
+        self.afk || self.friendly_fire || self.stayed_in_spawn
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Default)]
+struct MatchTeams {
+    red: Option<TeamResult>,
+    blue: Option<TeamResult>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+struct TeamResult {
+    has_won: bool,
+    rounds_won: u32,
+    rounds_lost: u32,
+    #[serde(default)]
+    roster: Option<TeamRoster>,
+}
+
+/// The registered team behind a side in Premier and tournament customs.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct TeamRoster {
+    members: Vec<String>,
+    name: String,
+    tag: String,
+}
+
+impl TeamRoster {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn tag(&self) -> &str {
+        &self.tag
+    }
+
+    /// The PUUIDs of the roster's players.
+    pub fn members(&self) -> &[String] {
+        &self.members
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct RoundSummary {
+    winning_team: String,
+    end_type: String,
+}
+
+impl RoundSummary {
+    pub fn winning_team(&self) -> &str {
+        &self.winning_team
+    }
+
+    /// How the round ended, e.g. `Eliminated`, `Bomb defused` or `Surrendered`.
+    pub fn end_type(&self) -> &str {
+        &self.end_type
+    }
+}
+
+/// The game mode, parsed from the metadata's `mode_id`. Modes differ in whether there are
+/// teams, an economy and which score wins, so consumers should branch on this instead of the
+/// display name.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash, Default)]
+#[serde(from = "String", into = "String")]
+pub enum MatchMode {
+    Competitive,
+    Unrated,
+    Swiftplay,
+    SpikeRush,
+    Deathmatch,
+    TeamDeathmatch,
+    Escalation,
+    Replication,
+    Snowball,
+    Premier,
+    /// Custom games have an empty mode ID.
+    Custom,
+    /// Payloads without a mode ID, which don't say what was played.
+    #[default]
+    Unknown,
+    Other(String),
+}
+
+impl MatchMode {
+    pub fn id(&self) -> &str {
+        match self {
+            MatchMode::Competitive => "competitive",
+            MatchMode::Unrated => "unrated",
+            MatchMode::Swiftplay => "swiftplay",
+            MatchMode::SpikeRush => "spikerush",
+            MatchMode::Deathmatch => "deathmatch",
+            MatchMode::TeamDeathmatch => "hurm",
+            MatchMode::Escalation => "ggteam",
+            MatchMode::Replication => "onefa",
+            MatchMode::Snowball => "snowball",
+            MatchMode::Premier => "premier",
+            MatchMode::Custom => "",
+            MatchMode::Unknown => "unknown",
+            MatchMode::Other(id) => id,
+        }
+    }
+
+    /// Deathmatch puts every player on their own team.
+    pub fn is_free_for_all(&self) -> bool {
+        matches!(self, MatchMode::Deathmatch)
+    }
+
+    /// Whether credits carry over between rounds, so what players can buy depends on the
+    /// rounds before. Swiftplay hands out fixed credits every round.
+    pub fn has_economy(&self) -> bool {
+        matches!(
+            self,
+            MatchMode::Competitive | MatchMode::Unrated | MatchMode::Premier | MatchMode::Custom
+        )
+    }
+
+    /// The rounds needed to win without overtime, for modes with a fixed round target.
+    pub fn rounds_to_win(&self) -> Option<u32> {
+        match self {
+            MatchMode::Competitive | MatchMode::Unrated | MatchMode::Premier => Some(13),
+            MatchMode::Swiftplay | MatchMode::Replication => Some(5),
+            MatchMode::SpikeRush => Some(4),
+            _ => None,
+        }
+    }
+}
+
+impl From<String> for MatchMode {
+    fn from(id: String) -> Self {
+        match id.to_ascii_lowercase().as_str() {
+            "competitive" => MatchMode::Competitive,
+            "unrated" => MatchMode::Unrated,
+            "swiftplay" => MatchMode::Swiftplay,
+            "spikerush" => MatchMode::SpikeRush,
+            "deathmatch" => MatchMode::Deathmatch,
+            "hurm" | "teamdeathmatch" => MatchMode::TeamDeathmatch,
+            "ggteam" => MatchMode::Escalation,
+            "onefa" => MatchMode::Replication,
+            "snowball" => MatchMode::Snowball,
+            "premier" => MatchMode::Premier,
+            "" | "custom" => MatchMode::Custom,
+            "unknown" => MatchMode::Unknown,
+            _ => MatchMode::Other(id),
+        }
+    }
+}
+
+impl From<MatchMode> for String {
+    fn from(mode: MatchMode) -> Self {
+        mode.id().to_string()
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MatchResult {
+    Win,
+    Loss,
+    Draw,
+}
+
+/// How a match went for one player. In deathmatch the "rounds" are the player's kills against
+/// the best kill count among their opponents.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MatchOutcome {
+    pub result: MatchResult,
+    pub rounds_for: u32,
+    pub rounds_against: u32,
+}
+
+/// Players who queued together.
+#[derive(Debug, Clone)]
+pub struct Party<'a> {
+    pub id: &'a str,
+    pub players: Vec<&'a MatchPlayer>,
+}
+
+impl Party<'_> {
+    pub fn is_premade(&self) -> bool {
+        self.players.len() > 1
+    }
+
+    pub fn queue_size(&self) -> QueueSize {
+        QueueSize::from_party_size(self.players.len())
+    }
+}
+
+/// How many players queued together, which matters when reading win rates: a five stack wins
+/// more often than five solo queuers of the same rank.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum QueueSize {
+    Solo,
+    Duo,
+    Trio,
+    /// Only possible outside competitive queues.
+    Quad,
+    FiveStack,
+}
+
+impl QueueSize {
+    /// Parties above five, which only custom games allow, count as five stacks.
+    pub fn from_party_size(players: usize) -> Self {
+        match players {
+            0 | 1 => QueueSize::Solo,
+            2 => QueueSize::Duo,
+            3 => QueueSize::Trio,
+            4 => QueueSize::Quad,
+            _ => QueueSize::FiveStack,
+        }
+    }
+
+    pub fn is_stack(&self) -> bool {
+        *self != QueueSize::Solo
+    }
+}
+
+impl MatchOutcome {
+    fn from_scores(rounds_for: u32, rounds_against: u32) -> Self {
+        let result = match rounds_for.cmp(&rounds_against) {
+            std::cmp::Ordering::Greater => MatchResult::Win,
+            std::cmp::Ordering::Less => MatchResult::Loss,
+            std::cmp::Ordering::Equal => MatchResult::Draw,
+        };
+        MatchOutcome {
+            result,
+            rounds_for,
+            rounds_against,
+        }
+    }
+}
+
+impl MatchData {
+    pub fn match_id(&self) -> &MatchId {
+        &self.metadata.match_id
+    }
+
+    pub fn map(&self) -> &str {
+        &self.metadata.map
+    }
+
+    pub fn started_at(&self) -> DateTime<Utc> {
+        DateTime::from_timestamp(self.metadata.game_start as i64, 0).unwrap_or_default()
+    }
+
+    pub fn players(&self) -> &[MatchPlayer] {
+        &self.players.all_players
+    }
+
+    /// The players whose behavior raised any [`PenaltyFlags`], with their flags.
+    pub fn penalized_players(&self) -> Vec<(&MatchPlayer, PenaltyFlags)> {
+        self.players
+            .all_players
+            .iter()
+            .map(|player| (player, player.penalty_flags()))
+            .filter(|(_, flags)| flags.any())
+            .collect()
+    }
+
+    /// Looks a player up by Riot ID, ignoring case like the game does.
+    pub fn find_player(&self, name: &str, tag: &str) -> Option<&MatchPlayer> {
+        self.players.all_players.iter().find(|player| {
+            player.name.eq_ignore_ascii_case(name) && player.tag.eq_ignore_ascii_case(tag)
+        })
+    }
+
+    pub fn observers(&self) -> &[Observer] {
+        &self.observers
+    }
+
+    pub fn coaches(&self) -> &[Coach] {
+        &self.coaches
+    }
+
+    pub fn mode(&self) -> &MatchMode {
+        &self.metadata.mode_id
+    }
+
+    /// Custom games have no mode ID, and report their queue as `Custom`.
+    pub fn is_custom(&self) -> bool {
+        self.metadata.mode_id == MatchMode::Custom
+            || self.metadata.queue.eq_ignore_ascii_case("custom")
+    }
+
+    pub fn premier_info(&self) -> Option<&PremierInfo> {
+        self.metadata
+            .premier_info
+            .as_ref()
+            .filter(|info| info.tournament_id.is_some() || info.matchup_id.is_some())
+    }
+
+    pub fn rounds(&self) -> &[RoundSummary] {
+        &self.rounds
+    }
+
+    /// The number of rounds played, also for history payloads that leave the rounds out.
+    pub fn rounds_played(&self) -> u32 {
+        self.metadata.rounds_played
+    }
+
+    /// The team attacking in the given round, counted from 0. Red attacks first; sides swap at
+    /// half time and after every overtime round. `None` for modes without spike rounds.
+    pub fn attacking_team(&self, round: usize) -> Option<&'static str> {
+        Some(SpikeRounds::for_match(self)?.attacking_team(round))
+    }
+
+    /// The team that won, or `None` for draws and modes without teams.
+    pub fn winning_team(&self) -> Option<&'static str> {
+        match (&self.teams.red, &self.teams.blue) {
+            (Some(red), _) if red.has_won => Some("Red"),
+            (_, Some(blue)) if blue.has_won => Some("Blue"),
+            _ => None,
+        }
+    }
+
+    /// Whether the match ended because one team surrendered.
+    pub fn is_forfeit(&self) -> bool {
+        self.rounds
+            .last()
+            .is_some_and(|round| round.end_type.eq_ignore_ascii_case("surrendered"))
+    }
+
+    /// The roster a side played as, keyed by the player's team, e.g. `Red`.
+    pub fn roster(&self, team: &str) -> Option<&TeamRoster> {
+        self.team(team)?.roster.as_ref()
+    }
+
+    pub fn find_player_by_puuid(&self, puuid: &str) -> Option<&MatchPlayer> {
+        self.players
+            .all_players
+            .iter()
+            .find(|player| player.puuid.eq_ignore_ascii_case(puuid))
+    }
+
+    /// Classifies the match for one player. Deathmatch is free for all, so the player is
+    /// compared against the rest of the lobby instead of a team. Team modes without team
+    /// results, like some team deathmatch payloads, fall back to comparing team kills.
+    pub fn result_for(&self, puuid: &str) -> Option<MatchOutcome> {
+        let player = self.find_player_by_puuid(puuid)?;
+        if self.mode().is_free_for_all() {
+            let best_opponent = self
+                .players
+                .all_players
+                .iter()
+                .filter(|other| other.puuid != player.puuid)
+                .map(|other| other.stats.kills)
+                .max()
+                .unwrap_or(0);
+            return Some(MatchOutcome::from_scores(player.stats.kills, best_opponent));
+        }
+
+        if let Some(team) = self.team(&player.team) {
+            return Some(MatchOutcome {
+                result: self.team_result(player)?,
+                rounds_for: team.rounds_won,
+                rounds_against: team.rounds_lost,
+            });
+        }
+        let team_kills = |team: bool| {
+            self.players
+                .all_players
+                .iter()
+                .filter(|other| other.team.eq_ignore_ascii_case(&player.team) == team)
+                .map(|other| other.stats.kills)
+                .sum()
+        };
+        Some(MatchOutcome::from_scores(
+            team_kills(true),
+            team_kills(false),
+        ))
+    }
+
+    /// Groups the lobby by party, largest first. Players without a party ID are treated as
+    /// solo queuers.
+    pub fn parties(&self) -> Vec<Party<'_>> {
+        let mut parties: Vec<Party<'_>> = Vec::new();
+        for player in &self.players.all_players {
+            let id = if player.party_id.is_empty() {
+                &player.puuid
+            } else {
+                &player.party_id
+            };
+            match parties.iter_mut().find(|party| party.id == id) {
+                Some(party) => party.players.push(player),
+                None => parties.push(Party {
+                    id,
+                    players: vec![player],
+                }),
+            }
+        }
+        // Stable, so parties of the same size keep lobby order
+        parties.sort_by_key(|party| std::cmp::Reverse(party.players.len()));
+        parties
+    }
+
+    /// The size of the party the given player queued in.
+    pub fn queue_size_of(&self, puuid: &str) -> Option<QueueSize> {
+        let player = self.find_player_by_puuid(puuid)?;
+        self.parties()
+            .into_iter()
+            .find(|party| {
+                party
+                    .players
+                    .iter()
+                    .any(|member| member.puuid == player.puuid)
+            })
+            .map(|party| party.queue_size())
+    }
+
+    /// The party sizes a team queued as, largest first, e.g. `[Duo, Solo, Solo, Solo]`.
+    pub fn premade_sizes(&self, team: &str) -> Vec<QueueSize> {
+        self.parties()
+            .iter()
+            .filter(|party| party.players[0].team.eq_ignore_ascii_case(team))
+            .map(Party::queue_size)
+            .collect()
+    }
+
+    /// The rounds a side won, keyed by the player's team, e.g. `Red`. `None` for modes without
+    /// teams.
+    pub fn rounds_won(&self, team: &str) -> Option<u32> {
+        Some(self.team(team)?.rounds_won)
+    }
+
+    fn team(&self, team: &str) -> Option<&TeamResult> {
+        match team.to_ascii_lowercase().as_str() {
+            "red" => self.teams.red.as_ref(),
+            "blue" => self.teams.blue.as_ref(),
+            _ => None,
+        }
+    }
+
+    /// The result of the given player's team, if the mode has teams.
+    pub fn team_result(&self, player: &MatchPlayer) -> Option<MatchResult> {
+        let team = self.team(&player.team)?;
+        Some(if team.has_won {
+            MatchResult::Win
+        } else if team.rounds_won == team.rounds_lost {
+            MatchResult::Draw
+        } else {
+            MatchResult::Loss
+        })
+    }
+}
+
+impl MatchPlayer {
+    pub fn puuid(&self) -> &str {
+        &self.puuid
+    }
+
+    pub fn party_id(&self) -> &str {
+        &self.party_id
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn tag(&self) -> &str {
+        &self.tag
+    }
+
+    pub fn team(&self) -> &str {
+        &self.team
+    }
+
+    pub fn agent(&self) -> &str {
+        &self.character
+    }
+
+    /// The player's rank tier, 0 in custom games.
+    pub fn current_tier(&self) -> u32 {
+        self.current_tier
+    }
+
+    /// The rank's name, empty when the tier is 0.
+    pub fn current_tier_patched(&self) -> &str {
+        &self.current_tier_patched
+    }
+
+    pub fn level(&self) -> u32 {
+        self.level
+    }
+
+    pub fn stats(&self) -> &PlayerStats {
+        &self.stats
+    }
+
+    pub(crate) fn economy(&self) -> Option<&PlayerEconomy> {
+        self.economy.as_ref()
+    }
+
+    pub fn behavior(&self) -> Behavior {
+        self.behavior
+    }
+
+    /// Damage taken from teammates doesn't count, only damage dealt to them.
+    pub fn penalty_flags(&self) -> PenaltyFlags {
+        PenaltyFlags {
+            afk: self.behavior.afk_rounds > 0,
+            friendly_fire: self.behavior.friendly_fire.outgoing > 0,
+            stayed_in_spawn: self.behavior.rounds_in_spawn > 0,
+        }
+    }
+
+    pub fn identity(&self) -> PlayerIdentity {
+        PlayerIdentity::from_fields(&self.puuid, &self.name, &self.tag)
+    }
+
+    pub fn customization(&self) -> PlayerCustomization {
+        let non_empty = |id: &str| (!id.is_empty()).then(|| id.to_string());
+        PlayerCustomization {
+            card: non_empty(&self.player_card).map(ProfileBanner::for_card),
+            title_id: non_empty(&self.player_title),
+            level_border: self
+                .preferred_level_border
+                .as_deref()
+                .and_then(non_empty)
+                .map(LevelBorder::for_id),
+        }
+    }
+}
+
+impl PlayerStats {
+    pub fn score(&self) -> u32 {
+        self.score
+    }
+
+    pub fn kills(&self) -> u32 {
+        self.kills
+    }
+
+    pub fn deaths(&self) -> u32 {
+        self.deaths
+    }
+
+    pub fn assists(&self) -> u32 {
+        self.assists
+    }
+
+    pub fn headshots(&self) -> u32 {
+        self.headshots
+    }
+
+    pub fn bodyshots(&self) -> u32 {
+        self.bodyshots
+    }
+
+    pub fn legshots(&self) -> u32 {
+        self.legshots
+    }
+}
+
+impl ValorantAPIData for MatchData {
+    fn sanity_warnings(&self) -> Vec<SanityWarning> {
+        let played = self.metadata.rounds_played;
+        [("Red", &self.teams.red), ("Blue", &self.teams.blue)]
+            .into_iter()
+            .filter_map(|(team, result)| {
+                let won = result.as_ref()?.rounds_won;
+                (won > played).then(|| SanityWarning::RoundsWonExceedPlayed {
+                    team: team.to_string(),
+                    won,
+                    played,
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(any(test, feature = "test-support"))]
+pub(crate) mod fake {
+    use super::*;
+    use crate::fake::{Fake, FakeRng};
+    use crate::fixtures::{decode, MATCH_CUSTOM, MATCH_DEATHMATCH, MATCH_HISTORY};
+
+    /// Builds a match for tests from one of the recorded fixtures, changing only what the test
+    /// is about.
+    #[derive(Debug)]
+    pub struct FakeMatch {
+        data: MatchData,
+    }
+
+    impl FakeMatch {
+        /// A competitive match on Ascent, won 13 to 10 by red.
+        pub fn competitive() -> Self {
+            Self::recorded(MATCH_HISTORY)
+        }
+
+        /// A tournament custom between two registered rosters, forfeited by blue.
+        pub fn custom() -> Self {
+            Self::recorded(MATCH_CUSTOM)
+        }
+
+        pub fn deathmatch() -> Self {
+            Self::recorded(MATCH_DEATHMATCH)
+        }
+
+        fn recorded(fixture: &str) -> Self {
+            let mut data: Vec<MatchData> = decode(fixture);
+            FakeMatch {
+                data: data.remove(0),
+            }
+        }
+
+        pub fn id(mut self, id: MatchId) -> Self {
+            self.data.metadata.match_id = id;
+            self
+        }
+
+        /// Moves the start by whole days, earlier for negative `days`.
+        pub fn days_later(mut self, days: i64) -> Self {
+            let start = self.data.metadata.game_start as i64 + days * 86_400;
+            self.data.metadata.game_start = start as u64;
+            self
+        }
+
+        /// Hands the win to `team`, e.g. `Red`, by flipping the score and every round. Draws
+        /// stay draws.
+        pub fn won_by(mut self, team: &str) -> Self {
+            let Some(winner) = self.data.winning_team() else {
+                return self;
+            };
+            if winner.eq_ignore_ascii_case(team) {
+                return self;
+            }
+            for result in [&mut self.data.teams.red, &mut self.data.teams.blue]
+                .into_iter()
+                .flatten()
+            {
+                result.has_won = !result.has_won;
+                std::mem::swap(&mut result.rounds_won, &mut result.rounds_lost);
+            }
+            for round in &mut self.data.rounds {
+                swap_colour(&mut round.winning_team);
+            }
+            self
+        }
+
+        /// Puts everyone on the other colour, as if the teams had picked the other side.
+        pub fn swap_colours(mut self) -> Self {
+            let data = &mut self.data;
+            for player in &mut data.players.all_players {
+                swap_colour(&mut player.team);
+            }
+            for observer in &mut data.observers {
+                swap_colour(&mut observer.team);
+            }
+            for coach in &mut data.coaches {
+                swap_colour(&mut coach.team);
+            }
+            for round in &mut data.rounds {
+                swap_colour(&mut round.winning_team);
+            }
+            std::mem::swap(&mut data.teams.red, &mut data.teams.blue);
+            self
+        }
+
+        pub fn build(self) -> MatchData {
+            self.data
+        }
+
+        /// The body the match endpoint answers with.
+        pub fn response(&self) -> String {
+            crate::fake::success(&self.data)
+        }
+    }
+
+    fn swap_colour(team: &mut String) {
+        let swapped = match team.as_str() {
+            "Red" => "Blue",
+            "Blue" => "Red",
+            "red" => "blue",
+            "blue" => "red",
+            _ => return,
+        };
+        *team = swapped.to_string();
+    }
+
+    impl Fake for MatchData {
+        fn fake_with(rng: &mut FakeRng) -> Self {
+            let game = FakeMatch::competitive()
+                .id(rng.uuid().parse().unwrap())
+                .days_later(i64::from(rng.between(0, 365)));
+            match rng.chance(50) {
+                true => game.won_by("Blue").build(),
+                false => game.build(),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::fixtures::{decode, MATCH_CUSTOM, MATCH_DEATHMATCH, MATCH_HISTORY};
+
+    #[test]
+    fn deserialize_response() {
+        let data: Vec<MatchData> = decode(MATCH_HISTORY);
+        let game = &data[0];
+        assert_eq!(
+            game.match_id().to_string(),
+            "0c6bd2a0-2c1f-4b87-9b1e-6f3f5d2a7e11"
+        );
+        assert_eq!(game.map(), "Ascent");
+        assert_eq!(game.rounds_played(), 23);
+    }
+
+    #[test]
+    fn penalty_flags() {
+        let data: Vec<MatchData> = decode(MATCH_HISTORY);
+        let penalized = data[0].penalized_players();
+        assert_eq!(penalized.len(), 1);
+        let (player, flags) = penalized[0];
+        assert_eq!(player.behavior().afk_rounds, 2);
+        assert_eq!(player.behavior().friendly_fire.outgoing, 45);
+        assert!(flags.afk && flags.friendly_fire && !flags.stayed_in_spawn);
+    }
+
+    #[test]
+    fn team_result() {
+        let data: Vec<MatchData> = decode(MATCH_HISTORY);
+        let game = &data[0];
+
+        let winner = game.find_player("anonymous", "0000").unwrap();
+        assert_eq!(game.team_result(winner), Some(MatchResult::Win));
+
+        let loser = game.find_player("Opponent", "EUW").unwrap();
+        assert_eq!(game.team_result(loser), Some(MatchResult::Loss));
+
+        assert!(game.find_player("Nobody", "NONE").is_none());
+    }
+
+    #[test]
+    fn result_for() {
+        let data: Vec<MatchData> = decode(MATCH_HISTORY);
+        let game = &data[0];
+        let winner = game.find_player("anonymous", "0000").unwrap();
+        assert_eq!(
+            game.result_for(&winner.puuid.to_ascii_uppercase()),
+            Some(MatchOutcome {
+                result: MatchResult::Win,
+                rounds_for: 13,
+                rounds_against: 10,
+            })
+        );
+        assert_eq!(game.result_for("not-in-this-match"), None);
+
+        let data: Vec<MatchData> = decode(MATCH_DEATHMATCH);
+        let deathmatch = &data[0];
+        let first = deathmatch
+            .result_for("e2b1c3d4-1111-4a2b-9c3d-000000000001")
+            .unwrap();
+        assert_eq!(first.result, MatchResult::Win);
+        assert_eq!((first.rounds_for, first.rounds_against), (40, 34));
+        let last = deathmatch
+            .result_for("e2b1c3d4-4444-4a2b-9c3d-000000000004")
+            .unwrap();
+        assert_eq!(last.result, MatchResult::Loss);
+        assert_eq!((last.rounds_for, last.rounds_against), (12, 40));
+    }
+
+    #[test]
+    fn parties() {
+        let data: Vec<MatchData> = decode(MATCH_HISTORY);
+        let parties = data[0].parties();
+        assert_eq!(parties.len(), 9);
+        assert!(parties[0].is_premade());
+        assert_eq!(parties[0].players.len(), 2);
+        assert!(parties[0]
+            .players
+            .iter()
+            .all(|player| player.party_id() == parties[0].id));
+        assert!(parties[1..].iter().all(|party| !party.is_premade()));
+
+        use QueueSize::*;
+        assert_eq!(data[0].premade_sizes("Red"), [Duo, Solo, Solo, Solo]);
+        assert_eq!(data[0].premade_sizes("blue"), [Solo; 5]);
+        assert_eq!(
+            data[0].queue_size_of(parties[0].players[0].puuid()),
+            Some(Duo)
+        );
+        assert_eq!(data[0].queue_size_of("nobody"), None);
+        assert!(!QueueSize::from_party_size(1).is_stack());
+        assert_eq!(QueueSize::from_party_size(7), FiveStack);
+    }
+
+    #[test]
+    fn player_customization() {
+        let with_border = MATCH_HISTORY.replacen(
+            r#""player_title": "47adec26-793d-0e45-3f50-82492d83a823","#,
+            r#""player_title": "47adec26-793d-0e45-3f50-82492d83a823",
+                        "preferred_level_border": "ebc736cd-4b6a-137b-e2b0-1486e31312c9","#,
+            1,
+        );
+        let data: Vec<MatchData> = decode(&with_border);
+        let players = data[0].players();
+
+        let customization = players[0].customization();
+        // Resolves to the same art the payload links to
+        assert_eq!(
+            customization.card.unwrap().small(),
+            "https://media.valorant-api.com/playercards/d93936e1-daca-3c06-f5ff-0c03bb5d7385/smallart.png"
+        );
+        assert_eq!(
+            customization.title_id.as_deref(),
+            Some("47adec26-793d-0e45-3f50-82492d83a823")
+        );
+        assert_eq!(
+            customization.level_border.unwrap().level_number_appearance,
+            "https://media.valorant-api.com/levelborders/ebc736cd-4b6a-137b-e2b0-1486e31312c9/levelnumberappearance.png"
+        );
+        assert!(players[1].customization().level_border.is_none());
+    }
+
+    #[test]
+    fn observers_and_coaches() {
+        let data: Vec<MatchData> = decode(MATCH_CUSTOM);
+        let game = &data[0];
+        assert_eq!(game.observers().len(), 1);
+        assert_eq!(game.observers()[0].identity().to_string(), "CasterOne#LIVE");
+        assert_eq!(
+            game.coaches().iter().map(Coach::team).collect::<Vec<_>>(),
+            ["Red", "Blue"]
+        );
+        // Observers and coaches aren't part of the lobby
+        assert!(game
+            .find_player_by_puuid(game.observers()[0].puuid())
+            .is_none());
+
+        let data: Vec<MatchData> = decode(MATCH_HISTORY);
+        assert!(data[0].observers().is_empty() && data[0].coaches().is_empty());
+    }
+
+    #[test]
+    fn custom_tournament_game() {
+        let data: Vec<MatchData> = decode(MATCH_CUSTOM);
+        let game = &data[0];
+        assert!(game.is_custom());
+        assert!(game.is_forfeit());
+        assert_eq!(game.rounds().len(), 20);
+        assert_eq!(
+            game.premier_info().and_then(PremierInfo::tournament_id),
+            Some("b7d3e5f1-2a4c-4e6b-8d0f-1a3c5e7b9d2f")
+        );
+        assert_eq!(game.roster("red").map(TeamRoster::name), Some("Nerd Herd"));
+        assert_eq!(game.roster("Blue").unwrap().members().len(), 5);
+
+        // A forfeit still counts as a win for the team that stayed
+        let winner = game.find_player("anonymous", "0000").unwrap();
+        let outcome = game.result_for(winner.puuid()).unwrap();
+        assert_eq!(outcome.result, MatchResult::Win);
+        assert_eq!((outcome.rounds_for, outcome.rounds_against), (11, 9));
+
+        let data: Vec<MatchData> = decode(MATCH_HISTORY);
+        assert!(!data[0].is_custom() && !data[0].is_forfeit());
+        assert!(data[0].premier_info().is_none());
+    }
+
+    #[test]
+    fn match_modes() {
+        let modes = |fixture: &str| {
+            let data: Vec<MatchData> = decode(fixture);
+            data[0].mode().clone()
+        };
+        assert_eq!(modes(MATCH_HISTORY), MatchMode::Competitive);
+        assert_eq!(modes(MATCH_DEATHMATCH), MatchMode::Deathmatch);
+        assert_eq!(modes(MATCH_CUSTOM), MatchMode::Custom);
+
+        assert!(MatchMode::Deathmatch.is_free_for_all() && !MatchMode::Deathmatch.has_economy());
+        assert!(!MatchMode::Escalation.has_economy());
+        assert_eq!(MatchMode::Swiftplay.rounds_to_win(), Some(5));
+        assert_eq!(MatchMode::TeamDeathmatch.rounds_to_win(), None);
+
+        let unknown = MatchMode::from("newmode".to_string());
+        assert_eq!(unknown, MatchMode::Other("newmode".to_string()));
+        assert_eq!(
+            serde_json::to_string(&MatchMode::TeamDeathmatch).unwrap(),
+            r#""hurm""#
+        );
+    }
+}
@@ -0,0 +1,377 @@
+//! Matches read by the rules of the mode they were played in.
+//!
+//! Every mode comes in the same payload, but what its rounds and scores mean differs:
+//! Deathmatch has neither teams nor rounds, Escalation is won by clearing weapon levels, and
+//! only modes where credits carry over between rounds report an economy. A [`MatchKind`]
+//! decodes a match into the model of its mode, which only has what the mode actually has,
+//! instead of leaving every consumer to check which parts of a [`MatchData`] apply.
+
+use crate::match_data::{MatchData, MatchMode};
+use crate::sanity::SanityWarning;
+use crate::ValorantAPIData;
+use serde::{Deserialize, Serialize};
+
+/// A match decoded by its mode, e.g. from `request::<Vec<MatchKind>>` on the match history.
+#[derive(Deserialize, Debug)]
+#[serde(from = "MatchData")]
+#[non_exhaustive]
+pub enum MatchKind {
+    /// Spike rounds with an economy: competitive, unrated, Premier and custom games.
+    Standard(StandardMatch),
+    /// Spike rounds to five without an economy or overtime.
+    Swiftplay(SwiftplayMatch),
+    Deathmatch(DeathmatchMatch),
+    Escalation(EscalationMatch),
+    /// Modes without a model of their own, like Spike Rush or team deathmatch, and matches
+    /// without a mode ID.
+    Other(MatchData),
+}
+
+impl From<MatchData> for MatchKind {
+    fn from(data: MatchData) -> Self {
+        match data.mode() {
+            MatchMode::Competitive
+            | MatchMode::Unrated
+            | MatchMode::Premier
+            | MatchMode::Custom => MatchKind::Standard(StandardMatch::new(data)),
+            MatchMode::Swiftplay => MatchKind::Swiftplay(SwiftplayMatch::new(data)),
+            MatchMode::Deathmatch => MatchKind::Deathmatch(DeathmatchMatch::new(data)),
+            MatchMode::Escalation => MatchKind::Escalation(EscalationMatch { data }),
+            _ => MatchKind::Other(data),
+        }
+    }
+}
+
+impl MatchKind {
+    /// The match as the API sent it, for everything that is the same in every mode.
+    pub fn data(&self) -> &MatchData {
+        match self {
+            MatchKind::Standard(game) => &game.data,
+            MatchKind::Swiftplay(game) => &game.data,
+            MatchKind::Deathmatch(game) => &game.data,
+            MatchKind::Escalation(game) => &game.data,
+            MatchKind::Other(data) => data,
+        }
+    }
+
+    pub fn into_data(self) -> MatchData {
+        match self {
+            MatchKind::Standard(game) => game.data,
+            MatchKind::Swiftplay(game) => game.data,
+            MatchKind::Deathmatch(game) => game.data,
+            MatchKind::Escalation(game) => game.data,
+            MatchKind::Other(data) => data,
+        }
+    }
+}
+
+impl ValorantAPIData for MatchKind {
+    fn sanity_warnings(&self) -> Vec<SanityWarning> {
+        self.data().sanity_warnings()
+    }
+}
+
+/// The round rules of a mode played as attack and defense around the spike.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct SpikeRounds {
+    /// Rounds needed to win in regulation.
+    pub rounds_to_win: u32,
+    /// Rounds before the sides swap.
+    pub half_length: u32,
+    /// Whether a tied regulation goes to overtime, rather than a single deciding round.
+    pub overtime: bool,
+}
+
+impl SpikeRounds {
+    /// The rules of the match's mode, or `None` for modes without spike rounds. Custom games
+    /// are assumed to use the competitive rules.
+    pub(crate) fn for_match(data: &MatchData) -> Option<Self> {
+        let (rounds_to_win, overtime) = match data.mode() {
+            MatchMode::Custom => (13, true),
+            MatchMode::Swiftplay => (data.mode().rounds_to_win()?, false),
+            mode if mode.has_economy() => (mode.rounds_to_win()?, true),
+            _ => return None,
+        };
+        Some(SpikeRounds {
+            rounds_to_win,
+            half_length: rounds_to_win - 1,
+            overtime,
+        })
+    }
+
+    /// The team attacking in the given round, counted from 0. Red attacks first; sides swap at
+    /// half time and after every round past regulation.
+    pub fn attacking_team(&self, round: usize) -> &'static str {
+        let half = self.half_length as usize;
+        let red_attacks = match round.checked_sub(2 * half) {
+            None => round < half,
+            Some(extra_round) => extra_round % 2 == 0,
+        };
+        if red_attacks {
+            "Red"
+        } else {
+            "Blue"
+        }
+    }
+}
+
+/// What a player spent and carried over a match.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct PlayerEconomy {
+    pub spent: CreditTotals,
+    /// The value of the weapons, shields and abilities the player went into rounds with.
+    pub loadout_value: CreditTotals,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct CreditTotals {
+    pub overall: u32,
+    /// Per round.
+    pub average: u32,
+}
+
+/// Every player's economy in a match. History payloads may leave it out, which leaves it empty.
+#[derive(Debug, Clone, Default)]
+pub struct Economy {
+    players: Vec<(String, PlayerEconomy)>,
+}
+
+impl Economy {
+    fn of(data: &MatchData) -> Self {
+        let players = data
+            .players()
+            .iter()
+            .filter_map(|player| Some((player.puuid().to_string(), *player.economy()?)))
+            .collect();
+        Economy { players }
+    }
+
+    pub fn player(&self, puuid: &str) -> Option<&PlayerEconomy> {
+        self.players
+            .iter()
+            .find(|(id, _)| id.eq_ignore_ascii_case(puuid))
+            .map(|(_, economy)| economy)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.players.is_empty()
+    }
+}
+
+/// A competitive, unrated, Premier or custom game.
+#[derive(Debug)]
+pub struct StandardMatch {
+    data: MatchData,
+    rounds: SpikeRounds,
+    economy: Economy,
+}
+
+impl StandardMatch {
+    fn new(data: MatchData) -> Self {
+        let rounds = SpikeRounds::for_match(&data).expect("standard modes have spike rounds");
+        let economy = Economy::of(&data);
+        StandardMatch {
+            data,
+            rounds,
+            economy,
+        }
+    }
+
+    pub fn data(&self) -> &MatchData {
+        &self.data
+    }
+
+    pub fn rounds(&self) -> &SpikeRounds {
+        &self.rounds
+    }
+
+    pub fn economy(&self) -> &Economy {
+        &self.economy
+    }
+}
+
+/// A Swiftplay game, where everyone gets the same credits every round.
+#[derive(Debug)]
+pub struct SwiftplayMatch {
+    data: MatchData,
+    rounds: SpikeRounds,
+}
+
+impl SwiftplayMatch {
+    fn new(data: MatchData) -> Self {
+        let rounds = SpikeRounds::for_match(&data).expect("swiftplay has spike rounds");
+        SwiftplayMatch { data, rounds }
+    }
+
+    pub fn data(&self) -> &MatchData {
+        &self.data
+    }
+
+    pub fn rounds(&self) -> &SpikeRounds {
+        &self.rounds
+    }
+}
+
+/// A free for all Deathmatch, won by the first player to the kill target.
+#[derive(Debug)]
+pub struct DeathmatchMatch {
+    data: MatchData,
+    standings: Vec<Standing>,
+}
+
+/// A player's place in a Deathmatch.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Standing {
+    pub puuid: String,
+    pub kills: u32,
+    pub deaths: u32,
+}
+
+impl DeathmatchMatch {
+    /// The kills that end a Deathmatch.
+    pub const KILL_TARGET: u32 = 40;
+
+    fn new(data: MatchData) -> Self {
+        let mut standings: Vec<Standing> = data
+            .players()
+            .iter()
+            .map(|player| Standing {
+                puuid: player.puuid().to_string(),
+                kills: player.stats().kills(),
+                deaths: player.stats().deaths(),
+            })
+            .collect();
+        // Stable, so players on the same kills keep lobby order
+        standings.sort_by_key(|standing| std::cmp::Reverse(standing.kills));
+        DeathmatchMatch { data, standings }
+    }
+
+    pub fn data(&self) -> &MatchData {
+        &self.data
+    }
+
+    /// Every player, most kills first.
+    pub fn standings(&self) -> &[Standing] {
+        &self.standings
+    }
+
+    /// The player with the most kills, shared places going to the first in the lobby.
+    pub fn winner(&self) -> Option<&Standing> {
+        self.standings.first()
+    }
+}
+
+/// An Escalation game, where teams work through the weapon levels and the first to clear the
+/// last one wins.
+#[derive(Debug)]
+pub struct EscalationMatch {
+    data: MatchData,
+}
+
+impl EscalationMatch {
+    /// The weapon levels to clear.
+    pub const LEVELS: u32 = 12;
+
+    pub fn data(&self) -> &MatchData {
+        &self.data
+    }
+
+    /// The levels a team cleared, which the API reports as its rounds won.
+    pub fn levels_cleared(&self, team: &str) -> Option<u32> {
+        self.data.rounds_won(team)
+    }
+
+    pub fn winning_team(&self) -> Option<&'static str> {
+        self.data.winning_team()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::fixtures::{decode, MATCH_CUSTOM, MATCH_DEATHMATCH, MATCH_HISTORY};
+
+    fn kind(fixture: &str) -> MatchKind {
+        decode::<Vec<MatchKind>>(fixture).remove(0)
+    }
+
+    // The fixture's first match, reported as another mode
+    fn as_mode(fixture: &str, mode_id: Option<&str>) -> MatchKind {
+        let mut response: serde_json::Value = serde_json::from_str(fixture).unwrap();
+        let metadata = response["data"][0]["metadata"].as_object_mut().unwrap();
+        match mode_id {
+            Some(mode_id) => metadata.insert("mode_id".to_string(), mode_id.into()),
+            None => metadata.remove("mode_id"),
+        };
+        kind(&response.to_string())
+    }
+
+    #[test]
+    fn matches_decode_by_mode() {
+        let MatchKind::Standard(competitive) = kind(MATCH_HISTORY) else {
+            panic!("expected a standard match");
+        };
+        assert_eq!(competitive.rounds().rounds_to_win, 13);
+        assert!(competitive.economy().is_empty());
+
+        let MatchKind::Standard(custom) = kind(MATCH_CUSTOM) else {
+            panic!("expected a standard match");
+        };
+        assert_eq!(custom.rounds().half_length, 12);
+
+        let MatchKind::Deathmatch(deathmatch) = kind(MATCH_DEATHMATCH) else {
+            panic!("expected a deathmatch");
+        };
+        let kills = deathmatch
+            .standings()
+            .iter()
+            .map(|standing| standing.kills)
+            .collect::<Vec<_>>();
+        assert!(kills.windows(2).all(|pair| pair[0] >= pair[1]));
+        assert_eq!(
+            deathmatch.winner().map(|winner| winner.kills),
+            kills.first().copied()
+        );
+
+        let MatchKind::Swiftplay(swiftplay) = as_mode(MATCH_HISTORY, Some("swiftplay")) else {
+            panic!("expected a swiftplay match");
+        };
+        let rounds = swiftplay.rounds();
+        assert_eq!((rounds.rounds_to_win, rounds.overtime), (5, false));
+        assert_eq!(rounds.attacking_team(4), "Blue");
+        assert_eq!(rounds.attacking_team(8), "Red");
+
+        let MatchKind::Escalation(escalation) = as_mode(MATCH_HISTORY, Some("ggteam")) else {
+            panic!("expected an escalation match");
+        };
+        assert_eq!(escalation.levels_cleared("Red"), Some(13));
+        assert_eq!(escalation.winning_team(), Some("Red"));
+
+        let unknown = as_mode(MATCH_HISTORY, None);
+        assert!(matches!(unknown, MatchKind::Other(_)));
+        assert_eq!(unknown.data().mode(), &MatchMode::Unknown);
+        assert!(!unknown.data().is_custom());
+    }
+
+    #[test]
+    fn standard_matches_keep_the_economy() {
+        let mut response: serde_json::Value = serde_json::from_str(MATCH_HISTORY).unwrap();
+        let player = &mut response["data"][0]["players"]["all_players"][0];
+        player["economy"] = serde_json::json!({
+            "spent": {"overall": 61000, "average": 2652},
+            "loadout_value": {"overall": 84200, "average": 3660}
+        });
+        let puuid = player["puuid"].as_str().unwrap().to_string();
+
+        let MatchKind::Standard(game) = kind(&response.to_string()) else {
+            panic!("expected a standard match");
+        };
+        let economy = game.economy().player(&puuid).unwrap();
+        assert_eq!(economy.spent.overall, 61000);
+        assert_eq!(economy.loadout_value.average, 3660);
+    }
+}
@@ -0,0 +1,350 @@
+use crate::ids::MatchId;
+use crate::match_data::{MatchData, MatchResult};
+use crate::mmr_data::RankImages;
+use crate::sanity::{self, SanityWarning};
+use crate::ValorantAPIData;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct MMRHistoryEntry {
+    #[serde(rename = "currenttier")]
+    current_tier: u32,
+    #[serde(rename = "currenttierpatched")]
+    current_tier_patched: String,
+    images: RankImages,
+    match_id: MatchId,
+    map: MapReference,
+    season_id: String,
+    ranking_in_tier: u32,
+    mmr_change_to_last_game: i32,
+    elo: u32,
+    date: String,
+    date_raw: u64,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct MapReference {
+    name: String,
+    id: String,
+}
+
+impl MMRHistoryEntry {
+    pub fn match_id(&self) -> &MatchId {
+        &self.match_id
+    }
+
+    pub fn mmr_change_to_last_game(&self) -> i32 {
+        self.mmr_change_to_last_game
+    }
+
+    pub fn date(&self) -> DateTime<Utc> {
+        DateTime::from_timestamp(self.date_raw as i64, 0).unwrap_or_default()
+    }
+
+    pub fn current_tier(&self) -> u32 {
+        self.current_tier
+    }
+
+    pub fn ranking_in_tier(&self) -> u32 {
+        self.ranking_in_tier
+    }
+
+    /// The tier's name, e.g. `Platinum 2`.
+    pub fn current_tier_patched(&self) -> &str {
+        &self.current_tier_patched
+    }
+
+    /// The RR earned since Iron 1, which charts the rank as one line across promotions.
+    pub fn elo(&self) -> u32 {
+        self.elo
+    }
+
+    pub fn map(&self) -> &str {
+        &self.map.name
+    }
+
+    pub fn season_id(&self) -> &str {
+        &self.season_id
+    }
+}
+
+impl ValorantAPIData for MMRHistoryEntry {
+    fn sanity_warnings(&self) -> Vec<SanityWarning> {
+        let mut warnings = Vec::new();
+        sanity::check_rank(
+            self.current_tier,
+            self.ranking_in_tier,
+            Some(self.elo),
+            &mut warnings,
+        );
+        warnings
+    }
+}
+
+/// An entry of `v2/mmr-history`, which nests the tier and season and reports the date as a
+/// timestamp. Convert it into [`MMRHistoryEntry`] to share code with `v1`.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct MMRHistoryEntryV2 {
+    match_id: MatchId,
+    tier: TierReference,
+    map: MapReference,
+    season: SeasonReference,
+    rr: u32,
+    last_change: i32,
+    elo: u32,
+    #[serde(default)]
+    refunded_rr: u32,
+    #[serde(default)]
+    was_derank_protected: bool,
+    date: DateTime<Utc>,
+}
+
+impl MMRHistoryEntryV2 {
+    /// RR given back because a player in the match was penalized, e.g. for leaving it.
+    pub fn refunded_rr(&self) -> u32 {
+        self.refunded_rr
+    }
+
+    /// Whether derank protection kept the loss from demoting the player.
+    pub fn was_derank_protected(&self) -> bool {
+        self.was_derank_protected
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+struct TierReference {
+    id: u32,
+    name: String,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+struct SeasonReference {
+    id: String,
+}
+
+impl ValorantAPIData for MMRHistoryEntryV2 {}
+
+impl From<MMRHistoryEntryV2> for MMRHistoryEntry {
+    fn from(v2: MMRHistoryEntryV2) -> Self {
+        MMRHistoryEntry {
+            current_tier: v2.tier.id,
+            current_tier_patched: v2.tier.name,
+            images: RankImages::for_tier(v2.tier.id),
+            match_id: v2.match_id,
+            map: v2.map,
+            season_id: v2.season.id,
+            ranking_in_tier: v2.rr,
+            mmr_change_to_last_game: v2.last_change,
+            elo: v2.elo,
+            // The same format v1 uses, e.g. `Tuesday, February 14, 2023 9:00 PM`
+            date: v2.date.format("%A, %B %-d, %Y %-I:%M %p").to_string(),
+            date_raw: v2.date.timestamp() as u64,
+        }
+    }
+}
+
+/// One ranked game's RR movement, linked to the match it came from.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct MMRChange {
+    pub match_id: MatchId,
+    pub rr_delta: i32,
+    pub map: String,
+    /// `None` when the match isn't part of the supplied match history.
+    pub result: Option<MatchResult>,
+}
+
+impl ValorantAPIData for MMRChange {}
+
+/// Joins MMR history entries to the matches they were earned in, using the shared match ID.
+pub fn annotate(
+    history: &[MMRHistoryEntry],
+    matches: &[MatchData],
+    name: &str,
+    tag: &str,
+) -> Vec<MMRChange> {
+    history
+        .iter()
+        .map(|entry| {
+            let result = matches
+                .iter()
+                .find(|game| *game.match_id() == entry.match_id)
+                .and_then(|game| game.team_result(game.find_player(name, tag)?));
+            MMRChange {
+                match_id: entry.match_id.clone(),
+                rr_delta: entry.mmr_change_to_last_game,
+                map: entry.map.name.clone(),
+                result,
+            }
+        })
+        .collect()
+}
+
+/// How a ranked game moved a player's rank.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RankMovement {
+    Promoted,
+    Demoted,
+    /// A loss at 0 RR that derank protection kept from demoting.
+    Shielded,
+    Stayed,
+}
+
+/// Labels each entry of a history, newest first as the API returns it, by comparing it to the
+/// entry before it. The oldest entry is compared to the rank its RR change implies.
+///
+/// Shields are only detected when the API reports the RR the loss would have cost.
+pub fn rank_movements(history: &[MMRHistoryEntry]) -> Vec<RankMovement> {
+    history
+        .iter()
+        .enumerate()
+        .map(|(index, entry)| {
+            let (previous_tier, previous_rr) = match history.get(index + 1) {
+                Some(previous) => (
+                    previous.current_tier as i64,
+                    previous.ranking_in_tier as i64,
+                ),
+                None => {
+                    let total = entry.current_tier as i64 * 100 + entry.ranking_in_tier as i64
+                        - entry.mmr_change_to_last_game as i64;
+                    (total.div_euclid(100), total.rem_euclid(100))
+                }
+            };
+            let tier = entry.current_tier as i64;
+            let change = entry.mmr_change_to_last_game as i64;
+            if tier > previous_tier {
+                RankMovement::Promoted
+            } else if tier < previous_tier {
+                RankMovement::Demoted
+            } else if change < 0 && entry.ranking_in_tier == 0 && previous_rr + change < 0 {
+                RankMovement::Shielded
+            } else {
+                RankMovement::Stayed
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::fixtures::{decode, MATCH_HISTORY, MMR_HISTORY};
+
+    #[test]
+    fn deserialize_response() {
+        let history: Vec<MMRHistoryEntry> = decode(MMR_HISTORY);
+        let entry = &history[0];
+        assert_eq!(entry.current_tier(), 16);
+        assert_eq!(entry.mmr_change_to_last_game(), 21);
+        assert_eq!(entry.date().timestamp(), 1676408400);
+    }
+
+    #[test]
+    fn entries_chart_rr_over_time() {
+        let history: Vec<MMRHistoryEntry> = decode(MMR_HISTORY);
+        let entry = &history[0];
+        assert_eq!(entry.current_tier_patched(), "Platinum 2");
+        assert_eq!(entry.elo(), 1347);
+        assert_eq!(entry.map(), "Ascent");
+        assert_eq!(entry.season_id(), "34093c29-4306-43de-452f-3f944bde22be");
+        assert_eq!(entry.date().timestamp(), 1676408400);
+    }
+
+    #[test]
+    fn v2_entries_convert_to_v1() {
+        let v2: MMRHistoryEntryV2 = serde_json::from_str(
+            r#"{
+                "match_id": "0c6bd2a0-2c1f-4b87-9b1e-6f3f5d2a7e11",
+                "tier": {"id": 16, "name": "Platinum 2"},
+                "map": {"id": "7eaecc1b-4337-bbf6-6ab9-04b8f06b3319", "name": "Ascent"},
+                "season": {"id": "34093c29-4306-43de-452f-3f944bde22be", "short": "e6a1"},
+                "rr": 47,
+                "last_change": 21,
+                "elo": 1347,
+                "refunded_rr": 0,
+                "was_derank_protected": false,
+                "date": "2023-02-14T21:00:00.000Z"
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(v2.refunded_rr(), 0);
+        assert!(!v2.was_derank_protected());
+        let v1: Vec<MMRHistoryEntry> = decode(MMR_HISTORY);
+        assert_eq!(
+            serde_json::to_value(MMRHistoryEntry::from(v2)).unwrap(),
+            serde_json::to_value(&v1[0]).unwrap()
+        );
+    }
+
+    #[test]
+    fn annotate_links_matches() {
+        let history: Vec<MMRHistoryEntry> = decode(MMR_HISTORY);
+        let matches: Vec<MatchData> = decode(MATCH_HISTORY);
+
+        let changes = annotate(&history, &matches, "Anonymous", "0000");
+        assert_eq!(
+            changes,
+            vec![
+                MMRChange {
+                    match_id: "0c6bd2a0-2c1f-4b87-9b1e-6f3f5d2a7e11".parse().unwrap(),
+                    rr_delta: 21,
+                    map: "Ascent".to_string(),
+                    result: Some(MatchResult::Win),
+                },
+                MMRChange {
+                    match_id: "7d3e88f4-91b2-4c55-8a0e-2b6c1f4d9a35".parse().unwrap(),
+                    rr_delta: -18,
+                    map: "Haven".to_string(),
+                    result: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn labels_rank_movements() {
+        let entry = |tier: u32, rr: u32, change: i32| {
+            MMRHistoryEntry::from(
+                serde_json::from_value::<MMRHistoryEntryV2>(serde_json::json!({
+                    "match_id": "0c6bd2a0-2c1f-4b87-9b1e-6f3f5d2a7e11",
+                    "tier": {"id": tier, "name": "Platinum"},
+                    "map": {"id": "7eaecc1b-4337-bbf6-6ab9-04b8f06b3319", "name": "Ascent"},
+                    "season": {"id": "34093c29-4306-43de-452f-3f944bde22be"},
+                    "rr": rr,
+                    "last_change": change,
+                    "elo": 0,
+                    "date": "2023-02-14T21:00:00.000Z"
+                }))
+                .unwrap(),
+            )
+        };
+        // Newest first
+        let history = [
+            entry(16, 5, 20),
+            entry(15, 85, -15),
+            entry(16, 0, -18),
+            entry(16, 0, -8),
+            entry(16, 8, 18),
+            entry(15, 90, 20),
+        ];
+        assert_eq!(
+            rank_movements(&history),
+            [
+                RankMovement::Promoted,
+                RankMovement::Demoted,
+                RankMovement::Shielded,
+                RankMovement::Stayed,
+                RankMovement::Promoted,
+                RankMovement::Stayed,
+            ]
+        );
+
+        let history: Vec<MMRHistoryEntry> = decode(MMR_HISTORY);
+        assert_eq!(rank_movements(&history), [RankMovement::Stayed; 2]);
+        // The oldest entry falls back to its own RR change
+        assert_eq!(
+            rank_movements(&[entry(16, 8, 21)]),
+            [RankMovement::Promoted]
+        );
+    }
+}
@@ -0,0 +1,242 @@
+//! A tiny HTTP server on localhost that replays canned responses, so the client can be tested
+//! without reaching the real API.
+//!
+//! [`MockServer::rate_limited`] also plays the API's rate limiting, for testing how code backs
+//! off from 429s. Apps get this module with the `test-support` feature.
+
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+
+#[derive(Debug, Clone)]
+pub struct MockResponse {
+    status: u16,
+    headers: Vec<(String, String)>,
+    body: String,
+    delay: Duration,
+}
+
+impl MockResponse {
+    pub fn json(status: u16, body: &str) -> Self {
+        MockResponse {
+            status,
+            headers: vec![("content-type".to_string(), "application/json".to_string())],
+            body: body.to_string(),
+            delay: Duration::ZERO,
+        }
+    }
+
+    pub fn header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    /// Holds the response back, to simulate a slow API.
+    pub fn delay(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RecordedRequest {
+    pub method: String,
+    pub path: String,
+    pub headers: Vec<(String, String)>,
+}
+
+impl RecordedRequest {
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// Rate limiting as the API does it: `limit` requests are answered, the next ones are rejected
+/// with a 429 and `Retry-After`, and then the window starts over. Windows are counted in
+/// requests rather than time, so tests behave the same however fast they run.
+#[derive(Debug, Clone, Copy)]
+pub struct RateLimitSimulator {
+    limit: u32,
+    rejections: u32,
+    retry_after: Duration,
+}
+
+impl RateLimitSimulator {
+    /// Answers `limit` requests per window, then rejects one with a `Retry-After` of a second.
+    pub fn new(limit: u32) -> Self {
+        RateLimitSimulator {
+            limit,
+            rejections: 1,
+            retry_after: Duration::from_secs(1),
+        }
+    }
+
+    /// How many requests are rejected before the window starts over.
+    pub fn rejections(mut self, rejections: u32) -> Self {
+        self.rejections = rejections;
+        self
+    }
+
+    /// The `Retry-After` sent with rejections, in whole seconds rounded up. Zero lets clients
+    /// retry immediately, which keeps tests fast.
+    pub fn retry_after(mut self, retry_after: Duration) -> Self {
+        self.retry_after = retry_after;
+        self
+    }
+
+    // The response to the request at `position` in the window, if it is rejected, and the rate
+    // limit headers it carries
+    fn answer(&self, position: u32) -> (bool, Vec<(&'static str, String)>) {
+        let seconds = self.retry_after.as_secs() + u64::from(self.retry_after.subsec_nanos() > 0);
+        let remaining = self.limit.saturating_sub(position + 1);
+        let mut headers = vec![
+            ("x-ratelimit-limit", self.limit.to_string()),
+            ("x-ratelimit-remaining", remaining.to_string()),
+        ];
+        if remaining == 0 {
+            headers.push(("x-ratelimit-reset", seconds.to_string()));
+        }
+        let rejected = position >= self.limit;
+        if rejected {
+            headers.push(("retry-after", seconds.to_string()));
+        }
+        (rejected, headers)
+    }
+}
+
+pub struct MockServer {
+    url: String,
+    requests: Arc<Mutex<Vec<RecordedRequest>>>,
+}
+
+impl MockServer {
+    /// Serves `responses` in order, repeating the last one once the list is exhausted.
+    pub async fn start(responses: Vec<MockResponse>) -> Self {
+        let mut served = 0;
+        Self::serve(move |_| {
+            let response = responses[served.min(responses.len() - 1)].clone();
+            served += 1;
+            response
+        })
+        .await
+    }
+
+    /// Serves `responses` like [`start`](Self::start), behind the rate limit `simulator` plays.
+    /// Rejected requests answer with the API's 429 and don't use up a response.
+    pub async fn rate_limited(responses: Vec<MockResponse>, simulator: RateLimitSimulator) -> Self {
+        let window = (simulator.limit + simulator.rejections).max(1);
+        let (mut received, mut served) = (0, 0);
+        Self::serve(move |_| {
+            let (rejected, headers) = simulator.answer(received % window);
+            received += 1;
+            let mut response = if rejected {
+                MockResponse::json(429, crate::fixtures::RATE_LIMITED)
+            } else {
+                served += 1;
+                responses[(served - 1).min(responses.len() - 1)].clone()
+            };
+            for (name, value) in headers {
+                response = response.header(name, &value);
+            }
+            response
+        })
+        .await
+    }
+
+    /// Answers each request with the response of the first route whose path it ends with, or a
+    /// 404 if none match.
+    pub async fn routes(routes: Vec<(&str, MockResponse)>) -> Self {
+        let routes = routes
+            .into_iter()
+            .map(|(path, response)| (path.to_string(), response))
+            .collect::<Vec<_>>();
+        Self::serve(move |request| {
+            routes
+                .iter()
+                .find(|(path, _)| request.path.ends_with(path.as_str()))
+                .map(|(_, response)| response.clone())
+                .unwrap_or_else(|| MockResponse::json(404, crate::fixtures::NOT_FOUND))
+        })
+        .await
+    }
+
+    async fn serve<F>(mut respond: F) -> Self
+    where
+        F: FnMut(&RecordedRequest) -> MockResponse + Send + 'static,
+    {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(Vec::new()));
+
+        let recorded = Arc::clone(&requests);
+        tokio::spawn(async move {
+            loop {
+                let Ok((mut stream, _)) = listener.accept().await else {
+                    return;
+                };
+                let Some(request) = read_request(&mut stream).await else {
+                    continue;
+                };
+                let response = respond(&request);
+                recorded.lock().unwrap().push(request);
+                tokio::time::sleep(response.delay).await;
+
+                let mut raw = format!(
+                    "HTTP/1.1 {} Mock\r\ncontent-length: {}\r\nconnection: close\r\n",
+                    response.status,
+                    response.body.len()
+                );
+                for (name, value) in &response.headers {
+                    raw.push_str(&format!("{name}: {value}\r\n"));
+                }
+                raw.push_str("\r\n");
+                raw.push_str(&response.body);
+                let _ = stream.write_all(raw.as_bytes()).await;
+                let _ = stream.shutdown().await;
+            }
+        });
+
+        MockServer { url, requests }
+    }
+
+    pub fn url(&self) -> String {
+        self.url.clone()
+    }
+
+    pub fn requests(&self) -> Vec<RecordedRequest> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+async fn read_request(stream: &mut tokio::net::TcpStream) -> Option<RecordedRequest> {
+    let mut buffer = Vec::new();
+    let mut chunk = [0; 1024];
+    while !buffer.windows(4).any(|window| window == b"\r\n\r\n") {
+        let read = stream.read(&mut chunk).await.ok()?;
+        if read == 0 {
+            return None;
+        }
+        buffer.extend_from_slice(&chunk[..read]);
+    }
+
+    let head = String::from_utf8_lossy(&buffer);
+    let mut lines = head.split("\r\n");
+    let mut request_line = lines.next()?.split(' ');
+    let method = request_line.next()?.to_string();
+    let path = request_line.next()?.to_string();
+    let headers = lines
+        .take_while(|line| !line.is_empty())
+        .filter_map(|line| line.split_once(':'))
+        .map(|(name, value)| (name.trim().to_string(), value.trim().to_string()))
+        .collect();
+
+    Some(RecordedRequest {
+        method,
+        path,
+        headers,
+    })
+}
@@ -0,0 +1,213 @@
+//! Articles from the official website, and an Atom feed built from them.
+//!
+//! [`NewsFeed`] holds the feed's metadata and entries, so any feed writer can serialize it.
+//! With the `atom` feature it also renders itself as Atom XML, ready to serve to feed readers.
+
+#[cfg(feature = "client")]
+use crate::error::ValorantError;
+use crate::ValorantAPIData;
+#[cfg(feature = "client")]
+use crate::{ValorantApiType, ValorantClient};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct NewsArticle {
+    banner_url: String,
+    category: String,
+    date: DateTime<Utc>,
+    #[serde(default)]
+    external_link: Option<String>,
+    title: String,
+    url: String,
+}
+
+impl NewsArticle {
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
+    /// E.g. `game_updates`, `esports` or `dev`.
+    pub fn category(&self) -> &str {
+        &self.category
+    }
+
+    pub fn published_at(&self) -> DateTime<Utc> {
+        self.date
+    }
+
+    pub fn banner_url(&self) -> &str {
+        &self.banner_url
+    }
+
+    /// The article's page on the website.
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Where the article actually lives, for articles that only link elsewhere, like the
+    /// esports site.
+    pub fn external_link(&self) -> Option<&str> {
+        self.external_link.as_deref()
+    }
+}
+
+impl ValorantAPIData for NewsArticle {}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct FeedEntry {
+    /// The article's website URL, which stays the same when the article is edited.
+    pub id: String,
+    pub title: String,
+    pub link: String,
+    pub category: String,
+    pub image: String,
+    pub updated: DateTime<Utc>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct NewsFeed {
+    pub id: String,
+    pub title: String,
+    /// The page the feed is about.
+    pub link: String,
+    /// The newest entry's date, or the Unix epoch for an empty feed.
+    pub updated: DateTime<Utc>,
+    /// Newest first.
+    pub entries: Vec<FeedEntry>,
+}
+
+impl NewsFeed {
+    /// Turns articles into a feed titled `title` about the page at `link`, which also serves
+    /// as the feed's ID.
+    pub fn from_articles(title: &str, link: &str, articles: &[NewsArticle]) -> Self {
+        let mut entries: Vec<FeedEntry> = articles
+            .iter()
+            .map(|article| FeedEntry {
+                id: article.url.clone(),
+                title: article.title.clone(),
+                link: article
+                    .external_link
+                    .clone()
+                    .unwrap_or_else(|| article.url.clone()),
+                category: article.category.clone(),
+                image: article.banner_url.clone(),
+                updated: article.date,
+            })
+            .collect();
+        entries.sort_by_key(|entry| std::cmp::Reverse(entry.updated));
+        NewsFeed {
+            id: link.to_string(),
+            title: title.to_string(),
+            link: link.to_string(),
+            updated: entries
+                .first()
+                .map_or(DateTime::UNIX_EPOCH, |entry| entry.updated),
+            entries,
+        }
+    }
+
+    /// Renders the feed as an Atom document.
+    #[cfg(feature = "atom")]
+    pub fn to_atom(&self) -> String {
+        let mut xml = String::from(r#"<?xml version="1.0" encoding="utf-8"?>"#);
+        xml.push_str(r#"<feed xmlns="http://www.w3.org/2005/Atom">"#);
+        xml.push_str(&format!(
+            r#"<id>{}</id><title>{}</title><link href="{}"/><updated>{}</updated>"#,
+            escape(&self.id),
+            escape(&self.title),
+            escape(&self.link),
+            self.updated.to_rfc3339()
+        ));
+        for entry in &self.entries {
+            xml.push_str(&format!(
+                concat!(
+                    r#"<entry><id>{}</id><title>{}</title><link href="{}"/>"#,
+                    r#"<category term="{}"/><link rel="enclosure" type="image/jpeg" href="{}"/>"#,
+                    r#"<updated>{}</updated></entry>"#
+                ),
+                escape(&entry.id),
+                escape(&entry.title),
+                escape(&entry.link),
+                escape(&entry.category),
+                escape(&entry.image),
+                entry.updated.to_rfc3339()
+            ));
+        }
+        xml.push_str("</feed>");
+        xml
+    }
+}
+
+#[cfg(feature = "atom")]
+fn escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(feature = "client")]
+impl ValorantClient<'_> {
+    /// The latest articles of the website edition for `country_code`, e.g. `en-us`.
+    pub async fn news(&self, country_code: &str) -> Result<Vec<NewsArticle>, ValorantError> {
+        self.request::<Vec<NewsArticle>>(ValorantApiType::Website { country_code })
+            .await
+    }
+}
+
+#[cfg(all(test, feature = "client"))]
+mod test {
+    use super::*;
+    use crate::fixtures::WEBSITE;
+    use crate::mock_server::{MockResponse, MockServer};
+
+    async fn articles() -> Vec<NewsArticle> {
+        let server = MockServer::routes(vec![(
+            "/v1/website/en-us",
+            MockResponse::json(200, WEBSITE),
+        )])
+        .await;
+        let url = server.url();
+        let client = ValorantClient::new().change_api_endpoint(&url);
+        client.news("en-us").await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn articles_become_feed_entries() {
+        let articles = articles().await;
+        let feed = NewsFeed::from_articles(
+            "VALORANT news",
+            "https://playvalorant.com/en-us/news/",
+            &articles,
+        );
+        assert_eq!(feed.updated, articles[0].published_at());
+        assert_eq!(feed.entries.len(), 2);
+        assert_eq!(feed.entries[0].title, "VALORANT Patch Notes 6.04");
+        assert_eq!(
+            feed.entries[1].link,
+            "https://valorantesports.com/news/lock-in-sao-paulo-bracket"
+        );
+        assert_eq!(feed.entries[1].id, articles[1].url());
+
+        let empty = NewsFeed::from_articles("Nothing", "https://example.com", &[]);
+        assert_eq!(empty.updated, DateTime::UNIX_EPOCH);
+    }
+
+    #[cfg(feature = "atom")]
+    #[tokio::test]
+    async fn renders_atom() {
+        let feed = NewsFeed::from_articles(
+            "VALORANT news",
+            "https://playvalorant.com/en-us/news/",
+            &articles().await,
+        );
+        let xml = feed.to_atom();
+        assert!(xml.starts_with(r#"<?xml version="1.0" encoding="utf-8"?><feed xmlns="#));
+        assert!(xml.ends_with("</feed>"));
+        assert_eq!(xml.matches("<entry>").count(), 2);
+        assert!(xml.contains("<updated>2023-02-21T17:00:00+00:00</updated>"));
+        assert!(xml.contains("<title>LOCK//IN: Brackets &amp; &lt;Schedule&gt;</title>"));
+    }
+}
@@ -0,0 +1,67 @@
+//! Folding of user-typed Riot IDs, so `  anonymous#0000` finds `Anonymous#0000`.
+//!
+//! [`fold`] trims the value, collapses runs of whitespace into one space, lowercases it and
+//! composes accented Latin letters typed as a letter followed by a combining mark, which is how
+//! some keyboards and phones send them. Full Unicode normalization needs the Unicode data
+//! tables; composing the accents Riot names actually use covers what players type.
+
+// The combining marks, the lowercase letters they compose with and the composed letters
+const COMPOSITIONS: &[(char, &str, &str)] = &[
+    ('\u{300}', "aeinouwy", "àèìǹòùẁỳ"),
+    ('\u{301}', "acegiklmnoprsuwyz", "áćéǵíḱĺḿńóṕŕśúẃýź"),
+    ('\u{302}', "aceghijosuwyz", "âĉêĝĥîĵôŝûŵŷẑ"),
+    ('\u{303}', "aeinouvy", "ãẽĩñõũṽỹ"),
+    ('\u{304}', "aegiouy", "āēḡīōūȳ"),
+    ('\u{306}', "aegiou", "ăĕğĭŏŭ"),
+    ('\u{307}', "abcdefghmnoprstwxyz", "ȧḃċḋėḟġḣṁṅȯṗṙṡṫẇẋẏż"),
+    ('\u{308}', "aehiotuwxy", "äëḧïöẗüẅẍÿ"),
+    ('\u{30a}', "auwy", "åůẘẙ"),
+    ('\u{30b}', "ou", "őű"),
+    ('\u{30c}', "acdeghijklnorstuz", "ǎčďěǧȟǐǰǩľňǒřšťǔž"),
+    ('\u{327}', "cdeghklnrst", "çḑȩģḩķļņŗşţ"),
+    ('\u{328}', "aeiou", "ąęįǫų"),
+];
+
+/// The form two names are compared in. Equal folds mean the names match.
+pub fn fold(value: &str) -> String {
+    let lowercase = value
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase();
+    let mut folded = String::with_capacity(lowercase.len());
+    for character in lowercase.chars() {
+        match folded.pop() {
+            Some(base) => match compose(base, character) {
+                Some(composed) => folded.push(composed),
+                None => {
+                    folded.push(base);
+                    folded.push(character);
+                }
+            },
+            None => folded.push(character),
+        }
+    }
+    folded
+}
+
+fn compose(base: char, mark: char) -> Option<char> {
+    let (_, bases, composed) = COMPOSITIONS.iter().find(|(known, _, _)| *known == mark)?;
+    let index = bases.chars().position(|known| known == base)?;
+    composed.chars().nth(index)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn folds_case_whitespace_and_accents() {
+        assert_eq!(fold("  Team   NERD \t"), "team nerd");
+        assert_eq!(fold("Jose\u{301}"), fold("JOSÉ"));
+        assert_eq!(fold("Zoe\u{308}y"), "zoëy");
+        // Marks without a precomposed letter are kept as they are
+        assert_eq!(fold("q\u{301}"), "q\u{301}");
+        assert_eq!(fold("\u{301}a"), "\u{301}a");
+    }
+}
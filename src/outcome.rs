@@ -0,0 +1,62 @@
+//! Results of composite operations whose sub-requests may fail independently.
+//!
+//! A composite helper runs its sub-requests (legs) concurrently. A transport error in any leg
+//! is fatal: the other legs are cancelled and the helper returns the error. A leg the API
+//! answers with a failure only loses that leg's data, and is reported in
+//! [`PartialOutcome::failures`] next to everything that did succeed.
+
+#[cfg(feature = "client")]
+use crate::error::ValorantError;
+use crate::sanity::SanityWarning;
+use crate::{ApiError, ValorantAPIData};
+
+#[derive(Debug)]
+pub struct PartialOutcome<T> {
+    pub value: T,
+    pub failures: Vec<LegFailure>,
+}
+
+impl<T> PartialOutcome<T> {
+    #[cfg(feature = "client")]
+    pub(crate) fn new(value: T) -> Self {
+        PartialOutcome {
+            value,
+            failures: Vec::new(),
+        }
+    }
+
+    /// Whether every leg succeeded.
+    pub fn is_complete(&self) -> bool {
+        self.failures.is_empty()
+    }
+
+    pub fn into_value(self) -> T {
+        self.value
+    }
+
+    /// The value if every leg succeeded, else the first failed leg as an error.
+    #[cfg(feature = "client")]
+    pub(crate) fn into_complete(self) -> Result<T, ValorantError> {
+        match self.failures.into_iter().next() {
+            Some(LegFailure { status, errors, .. }) => {
+                Err(ValorantError::from_failure(status, errors))
+            }
+            None => Ok(self.value),
+        }
+    }
+}
+
+impl<T: ValorantAPIData> ValorantAPIData for PartialOutcome<T> {
+    fn sanity_warnings(&self) -> Vec<SanityWarning> {
+        self.value.sanity_warnings()
+    }
+}
+
+/// A leg the API answered with a failure.
+#[derive(Debug)]
+pub struct LegFailure {
+    /// What the leg fetched, e.g. `match history` or an act like `e6a1`.
+    pub leg: String,
+    pub status: u32,
+    pub errors: Vec<ApiError>,
+}
@@ -0,0 +1,107 @@
+//! Where a rank stands among all ranked players of a region, for "top 3%" displays.
+//!
+//! The API doesn't report how players are spread over the tiers, so the shares come from the
+//! distributions Riot and community sites publish per act and region. Within a tier, players
+//! are assumed to be spread evenly over its 100 RR. Immortal and Radiant players are all on the
+//! leaderboard, which places them exactly instead.
+
+use crate::leaderboard::{LeaderboardIndex, RankCutoff};
+use std::collections::BTreeMap;
+
+/// The share of a region's ranked players in each tier.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct TierDistribution {
+    shares: BTreeMap<u32, f64>,
+}
+
+impl TierDistribution {
+    /// Takes `(tier, share)` pairs, e.g. `(16, 7.4)` for 7.4% in Platinum 2. Shares are
+    /// scaled to add up to one, so percentages, fractions and player counts all work.
+    pub fn new(shares: impl IntoIterator<Item = (u32, f64)>) -> Self {
+        let mut distribution = BTreeMap::new();
+        for (tier, share) in shares {
+            *distribution.entry(tier).or_insert(0.0) += share.max(0.0);
+        }
+        let total: f64 = distribution.values().sum();
+        if total > 0.0 {
+            distribution.values_mut().for_each(|share| *share /= total);
+        }
+        TierDistribution {
+            shares: distribution,
+        }
+    }
+
+    /// The fraction of ranked players in `tier`.
+    pub fn share(&self, tier: u32) -> f64 {
+        self.shares.get(&tier).copied().unwrap_or_default()
+    }
+
+    /// The percentage of ranked players below `rr` into `tier`.
+    pub fn percentile(&self, tier: u32, rr: u32) -> f64 {
+        let below: f64 = self.shares.range(..tier).map(|(_, share)| share).sum();
+        let within = self.share(tier) * f64::from(rr.min(100)) / 100.0;
+        (below + within) * 100.0
+    }
+
+    /// Like [`percentile`](Self::percentile), but players who made it onto the leaderboard
+    /// are placed by their leaderboard RR among everyone on it. RR of leaderboard tiers keeps
+    /// counting past 100, e.g. 431 for an Immortal 3.
+    pub fn percentile_on(&self, leaderboard: &LeaderboardIndex, tier: u32, rr: u32) -> f64 {
+        let lowest = RankCutoff::Immortal1.tier();
+        if tier < lowest || leaderboard.is_empty() {
+            return self.percentile(tier, rr);
+        }
+        let below = leaderboard
+            .entries()
+            .iter()
+            .filter(|entry| entry.ranked_rating() < rr)
+            .count() as f64
+            / leaderboard.len() as f64;
+        let board: f64 = self.shares.range(lowest..).map(|(_, share)| share).sum();
+        (1.0 - board + board * below) * 100.0
+    }
+
+    /// The percentage of ranked players at or above `rr` into `tier`, e.g. `3.0` for "top 3%".
+    pub fn top(&self, tier: u32, rr: u32) -> f64 {
+        100.0 - self.percentile(tier, rr)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::fixtures::{decode, LEADERBOARD};
+    use crate::leaderboard::Leaderboard;
+
+    fn approx(actual: f64, expected: f64) {
+        assert!((actual - expected).abs() < 1e-9, "{actual} != {expected}");
+    }
+
+    #[test]
+    fn percentiles_interpolate_within_tiers() {
+        let distribution = TierDistribution::new([(3, 20.0), (4, 30.0), (24, 40.0), (27, 10.0)]);
+        approx(distribution.share(4), 0.3);
+        approx(distribution.percentile(4, 50), 35.0);
+        approx(distribution.top(4, 50), 65.0);
+        approx(distribution.percentile(3, 0), 0.0);
+        // Tiers nobody is reported in sit on top of the tiers below them
+        approx(distribution.percentile(16, 99), 50.0);
+        approx(distribution.percentile(27, 1000), 100.0);
+        assert_eq!(TierDistribution::new([]).percentile(16, 50), 0.0);
+    }
+
+    #[test]
+    fn leaderboard_tiers_are_placed_by_rr() {
+        let data: Leaderboard = decode(LEADERBOARD);
+        let leaderboard = LeaderboardIndex::new(data);
+        let distribution = TierDistribution::new([(3, 20.0), (4, 30.0), (24, 40.0), (27, 10.0)]);
+
+        // Two of the five players on the board have less than 431 RR
+        approx(distribution.percentile_on(&leaderboard, 26, 431), 70.0);
+        approx(distribution.percentile_on(&leaderboard, 4, 50), 35.0);
+        approx(
+            distribution.percentile_on(&LeaderboardIndex::default(), 24, 50),
+            70.0,
+        );
+    }
+}
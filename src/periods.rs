@@ -0,0 +1,126 @@
+//! A player's results grouped by day or week in the player's own time zone.
+//!
+//! A match played at 1am in Berlin belongs to that day there, even though it is still the day
+//! before in UTC. Every grouping takes the time zone as any [`TimeZone`], so a
+//! [`FixedOffset`](chrono::FixedOffset), [`Local`](chrono::Local) or a `chrono_tz::Tz` for
+//! zones with daylight saving time all work.
+
+use crate::match_data::{MatchData, MatchResult};
+use chrono::{DateTime, Datelike, Duration, NaiveDate, TimeZone};
+
+/// The results of one day or week, which starts on Monday.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct PeriodSummary {
+    pub start: NaiveDate,
+    pub matches: u32,
+    pub wins: u32,
+    pub losses: u32,
+    pub draws: u32,
+}
+
+impl PeriodSummary {
+    fn new(start: NaiveDate) -> Self {
+        PeriodSummary {
+            start,
+            matches: 0,
+            wins: 0,
+            losses: 0,
+            draws: 0,
+        }
+    }
+
+    fn add(&mut self, result: MatchResult) {
+        self.matches += 1;
+        match result {
+            MatchResult::Win => self.wins += 1,
+            MatchResult::Loss => self.losses += 1,
+            MatchResult::Draw => self.draws += 1,
+        }
+    }
+}
+
+/// The player's results per day in `zone`, oldest first. Days without matches are left out.
+pub fn by_day<Tz: TimeZone>(matches: &[MatchData], puuid: &str, zone: &Tz) -> Vec<PeriodSummary> {
+    group(matches, puuid, |game| local_date(game, zone))
+}
+
+/// The player's results per week in `zone`, oldest first.
+pub fn by_week<Tz: TimeZone>(matches: &[MatchData], puuid: &str, zone: &Tz) -> Vec<PeriodSummary> {
+    group(matches, puuid, |game| {
+        let date = local_date(game, zone);
+        date - Duration::days(date.weekday().num_days_from_monday().into())
+    })
+}
+
+/// The player's results on the day `now` falls on, in the zone `now` is in.
+pub fn today<Tz: TimeZone>(
+    matches: &[MatchData],
+    puuid: &str,
+    now: &DateTime<Tz>,
+) -> PeriodSummary {
+    let date = now.date_naive();
+    by_day(matches, puuid, &now.timezone())
+        .into_iter()
+        .find(|day| day.start == date)
+        .unwrap_or_else(|| PeriodSummary::new(date))
+}
+
+fn local_date<Tz: TimeZone>(game: &MatchData, zone: &Tz) -> NaiveDate {
+    game.started_at().with_timezone(zone).date_naive()
+}
+
+fn group(
+    matches: &[MatchData],
+    puuid: &str,
+    period: impl Fn(&MatchData) -> NaiveDate,
+) -> Vec<PeriodSummary> {
+    let mut periods: Vec<PeriodSummary> = Vec::new();
+    for game in matches {
+        let Some(outcome) = game.result_for(puuid) else {
+            continue;
+        };
+        let start = period(game);
+        match periods.iter_mut().find(|summary| summary.start == start) {
+            Some(summary) => summary.add(outcome.result),
+            None => {
+                let mut summary = PeriodSummary::new(start);
+                summary.add(outcome.result);
+                periods.push(summary);
+            }
+        }
+    }
+    periods.sort_by_key(|summary| summary.start);
+    periods
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::fake::FakeMatch;
+    use chrono::{FixedOffset, Utc};
+
+    const ANONYMOUS: &str = "81862fc9-634f-806f-abf4-a07c56600224";
+
+    #[test]
+    fn days_follow_the_players_zone() {
+        let data = [FakeMatch::competitive().build()];
+        let date = |day| NaiveDate::from_ymd_opt(2023, 2, day).unwrap();
+
+        // The match started on Tuesday, February 14 at 9pm UTC
+        let days = by_day(&data, ANONYMOUS, &Utc);
+        assert_eq!(days.len(), 1);
+        assert_eq!((days[0].start, days[0].wins), (date(14), 1));
+        let tashkent = FixedOffset::east_opt(5 * 3600).unwrap();
+        assert_eq!(by_day(&data, ANONYMOUS, &tashkent)[0].start, date(15));
+        assert_eq!(by_week(&data, ANONYMOUS, &tashkent)[0].start, date(13));
+
+        let evening = tashkent.with_ymd_and_hms(2023, 2, 15, 20, 0, 0).unwrap();
+        assert_eq!(today(&data, ANONYMOUS, &evening).matches, 1);
+        assert_eq!(
+            today(&data, ANONYMOUS, &(evening - Duration::days(1))).matches,
+            0
+        );
+        assert!(by_day(&data, "nobody", &Utc).is_empty());
+    }
+}
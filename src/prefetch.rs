@@ -0,0 +1,90 @@
+//! Warming the client's caches before the first user interaction.
+//!
+//! The live act and game version are fetched lazily on first use, which makes whoever asks first
+//! wait for an extra round trip. Prefetching fetches both up front, and a refresh task keeps them
+//! fresh by refetching whenever the cached act is about to expire.
+//!
+//! Competitive tier images need no prefetch: their URLs are derived from the tier locally.
+
+use crate::error::ValorantError;
+use crate::{AccountRegion, ValorantClient, CURRENT_ACT_TTL};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::task::JoinHandle;
+
+// How long a failed refresh waits before trying again
+const RETRY_DELAY: Duration = Duration::from_secs(60);
+
+impl ValorantClient<'_> {
+    /// Fetches the live act and the game version of `region` so later calls are answered from
+    /// the cache.
+    pub async fn prefetch(&self, region: AccountRegion) -> Result<(), ValorantError> {
+        let (act, version) = futures::join!(self.fetch_current_act(), self.check_version(region));
+        act?;
+        version?;
+        Ok(())
+    }
+}
+
+impl ValorantClient<'static> {
+    /// Prefetches in the background, then refreshes the cache every time it expires until the
+    /// returned task is aborted. Failed refreshes are retried after a minute.
+    pub fn spawn_prefetch(self: Arc<Self>, region: AccountRegion) -> JoinHandle<()> {
+        self.spawn_refresh(region, CURRENT_ACT_TTL)
+    }
+
+    fn spawn_refresh(self: Arc<Self>, region: AccountRegion, every: Duration) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            loop {
+                let delay = match self.prefetch(region).await {
+                    Ok(()) => every,
+                    Err(_) => RETRY_DELAY,
+                };
+                tokio::time::sleep(delay).await;
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::fixtures::{CONTENT, VERSION};
+    use crate::mock_server::{MockResponse, MockServer};
+
+    #[tokio::test]
+    async fn prefetched_data_is_served_from_the_cache() {
+        let server = MockServer::routes(vec![
+            ("/v1/content", MockResponse::json(200, CONTENT)),
+            ("/v1/version/eu", MockResponse::json(200, VERSION)),
+        ])
+        .await;
+        let url = server.url();
+        let client = ValorantClient::new().change_api_endpoint(&url);
+
+        client.prefetch(AccountRegion::EU).await.unwrap();
+        assert_eq!(server.requests().len(), 2);
+        assert!(client.current_act().await.unwrap().is_some());
+        assert_eq!(server.requests().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn the_refresh_task_refetches_on_expiry() {
+        let server = MockServer::routes(vec![
+            ("/v1/content", MockResponse::json(200, CONTENT)),
+            ("/v1/version/eu", MockResponse::json(200, VERSION)),
+        ])
+        .await;
+        let url: &'static str = Box::leak(server.url().into_boxed_str());
+        let client = Arc::new(ValorantClient::new().change_api_endpoint(url));
+
+        let task = client
+            .clone()
+            .spawn_refresh(AccountRegion::EU, Duration::from_millis(50));
+        tokio::time::sleep(Duration::from_millis(120)).await;
+        task.abort();
+        // The initial prefetch plus at least one refresh
+        assert!(server.requests().len() >= 4);
+        assert!(client.current_act().await.unwrap().is_some());
+    }
+}
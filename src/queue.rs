@@ -0,0 +1,149 @@
+//! A durable queue of pending polling and backfill work.
+//!
+//! Jobs stay in the queue until they are marked complete, and every change is written to the
+//! storage backend, so a process that crashes or is redeployed picks up where it left off. Use a
+//! [`MemoryStorage`](crate::storage::MemoryStorage) when persistence isn't needed.
+
+use crate::storage::{Storage, StorageExt};
+use crate::AccountRegion;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::io;
+use std::sync::Mutex;
+
+const QUEUES: &str = "work_queues";
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub enum Job {
+    /// Check a player for new matches and rank changes.
+    PollPlayer {
+        region: AccountRegion,
+        name: String,
+        tag: String,
+    },
+    /// Fetch one page of a player's stored matches.
+    BackfillPage {
+        region: AccountRegion,
+        name: String,
+        tag: String,
+        page: u32,
+    },
+}
+
+pub struct WorkQueue<S> {
+    storage: S,
+    name: String,
+    jobs: Mutex<VecDeque<Job>>,
+}
+
+impl<S: Storage> WorkQueue<S> {
+    /// Opens the queue called `name`, restoring the jobs left over from the last run.
+    pub fn open(storage: S, name: &str) -> io::Result<Self> {
+        let jobs = storage.get_json(QUEUES, name)?.unwrap_or_default();
+        Ok(WorkQueue {
+            storage,
+            name: name.to_string(),
+            jobs: Mutex::new(jobs),
+        })
+    }
+
+    /// Adds a job to the back of the queue. Returns false if the exact job is already queued.
+    pub fn push(&self, job: Job) -> io::Result<bool> {
+        let mut jobs = self.jobs.lock().unwrap();
+        if jobs.contains(&job) {
+            return Ok(false);
+        }
+        jobs.push_back(job);
+        self.save(&jobs)?;
+        Ok(true)
+    }
+
+    /// The job to work on next. It stays queued until [`complete`](Self::complete) is called.
+    pub fn peek(&self) -> Option<Job> {
+        self.jobs.lock().unwrap().front().cloned()
+    }
+
+    /// Removes a finished job. Returns false if it wasn't queued.
+    pub fn complete(&self, job: &Job) -> io::Result<bool> {
+        let mut jobs = self.jobs.lock().unwrap();
+        let Some(index) = jobs.iter().position(|queued| queued == job) else {
+            return Ok(false);
+        };
+        jobs.remove(index);
+        self.save(&jobs)?;
+        Ok(true)
+    }
+
+    /// Moves a job that failed to the back of the queue so other work isn't blocked by it.
+    pub fn requeue(&self, job: &Job) -> io::Result<()> {
+        let mut jobs = self.jobs.lock().unwrap();
+        if let Some(index) = jobs.iter().position(|queued| queued == job) {
+            let job = jobs.remove(index).unwrap();
+            jobs.push_back(job);
+            self.save(&jobs)?;
+        }
+        Ok(())
+    }
+
+    pub fn jobs(&self) -> Vec<Job> {
+        self.jobs.lock().unwrap().iter().cloned().collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.jobs.lock().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.jobs.lock().unwrap().is_empty()
+    }
+
+    fn save(&self, jobs: &VecDeque<Job>) -> io::Result<()> {
+        self.storage.put_json(QUEUES, &self.name, jobs)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::storage::test::temp_dir;
+    use crate::storage::FileStorage;
+
+    fn poll(name: &str) -> Job {
+        Job::PollPlayer {
+            region: AccountRegion::EU,
+            name: name.to_string(),
+            tag: "NERD".to_string(),
+        }
+    }
+
+    #[test]
+    fn survives_a_restart() {
+        let root = temp_dir("work-queue");
+        {
+            let queue = WorkQueue::open(FileStorage::new(&root), "watcher").unwrap();
+            assert!(queue.push(poll("NitroSniper")).unwrap());
+            assert!(!queue.push(poll("NitroSniper")).unwrap());
+            queue
+                .push(Job::BackfillPage {
+                    region: AccountRegion::EU,
+                    name: "NitroSniper".to_string(),
+                    tag: "NERD".to_string(),
+                    page: 3,
+                })
+                .unwrap();
+            queue.push(poll("Anoca")).unwrap();
+
+            let first = queue.peek().unwrap();
+            queue.requeue(&first).unwrap();
+            let second = queue.peek().unwrap();
+            assert!(queue.complete(&second).unwrap());
+            // The process "crashes" here with two jobs left
+        }
+
+        let queue = WorkQueue::open(FileStorage::new(&root), "watcher").unwrap();
+        assert_eq!(queue.jobs(), [poll("Anoca"), poll("NitroSniper")]);
+        let other = WorkQueue::open(FileStorage::new(&root), "other").unwrap();
+        assert!(other.is_empty());
+        std::fs::remove_dir_all(root).unwrap();
+    }
+}
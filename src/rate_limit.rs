@@ -0,0 +1,295 @@
+//! Client side rate limiting, so bots stay under their API key's limit instead of collecting 429s.
+//!
+//! A [`RateBudget`] enforces a limit the caller configured. The API also reports the key's
+//! actual budget with every answer, which the client keeps as a [`ResponseMeta`] and can wait
+//! on before the next request would be rejected. The key's budget is shared by every endpoint,
+//! so the client keeps a single one for all of them, whichever route answered last.
+
+use crate::retry::parse_retry_after;
+use reqwest::header::HeaderMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// A token bucket that refills `requests` tokens every `period`. Cloning the budget shares it,
+/// so every client it's attached to draws from the same limit.
+#[derive(Debug, Clone)]
+pub struct RateBudget {
+    inner: Arc<Mutex<Bucket>>,
+}
+
+#[derive(Debug)]
+struct Bucket {
+    capacity: f64,
+    tokens: f64,
+    refill_per_second: f64,
+    refilled_at: Instant,
+}
+
+impl Bucket {
+    fn refill(&mut self, now: Instant) {
+        let elapsed = now.duration_since(self.refilled_at).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.refill_per_second).min(self.capacity);
+        self.refilled_at = now;
+    }
+}
+
+impl RateBudget {
+    /// Allows `requests` requests per `period`, e.g. `RateBudget::new(30, Duration::from_secs(60))`
+    /// for the basic HenrikDev key. The bucket starts full.
+    pub fn new(requests: u32, period: Duration) -> Self {
+        assert!(requests > 0, "a rate budget needs at least one request");
+        assert!(!period.is_zero(), "a rate budget needs a non zero period");
+        let capacity = f64::from(requests);
+        RateBudget {
+            inner: Arc::new(Mutex::new(Bucket {
+                capacity,
+                tokens: capacity,
+                refill_per_second: capacity / period.as_secs_f64(),
+                refilled_at: Instant::now(),
+            })),
+        }
+    }
+
+    /// Takes a token if one is available right now.
+    pub fn try_acquire(&self) -> bool {
+        self.reserve().is_none()
+    }
+
+    /// Waits until a token is available and takes it.
+    pub async fn acquire(&self) {
+        while let Some(wait) = self.reserve() {
+            tokio::time::sleep(wait).await;
+        }
+    }
+
+    /// The number of requests that could be sent right now without waiting.
+    pub fn available(&self) -> u32 {
+        let mut bucket = self.inner.lock().unwrap();
+        bucket.refill(Instant::now());
+        bucket.tokens as u32
+    }
+
+    // Takes a token, or returns how long until the next one is available
+    fn reserve(&self) -> Option<Duration> {
+        let mut bucket = self.inner.lock().unwrap();
+        bucket.refill(Instant::now());
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            None
+        } else {
+            Some(Duration::from_secs_f64(
+                (1.0 - bucket.tokens) / bucket.refill_per_second,
+            ))
+        }
+    }
+}
+
+/// The rate limit headers of an answer of the API.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[non_exhaustive]
+pub struct ResponseMeta {
+    /// `x-ratelimit-limit`, the requests the key may send per window.
+    pub limit: Option<u32>,
+    /// `x-ratelimit-remaining`, the requests left in the current window.
+    pub remaining: Option<u32>,
+    /// `x-ratelimit-reset`, how long the window had left when the answer was sent.
+    pub reset: Option<Duration>,
+    /// `retry-after`, sent along with 429s.
+    pub retry_after: Option<Duration>,
+}
+
+impl ResponseMeta {
+    pub(crate) fn from_headers(headers: &HeaderMap) -> Self {
+        let header = |name: &str| headers.get(name)?.to_str().ok();
+        let number = |name: &str| header(name)?.trim().parse::<u32>().ok();
+        ResponseMeta {
+            limit: number("x-ratelimit-limit"),
+            remaining: number("x-ratelimit-remaining"),
+            reset: number("x-ratelimit-reset").map(|seconds| Duration::from_secs(seconds.into())),
+            retry_after: header("retry-after").and_then(parse_retry_after),
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        *self == ResponseMeta::default()
+    }
+
+    /// How long after the answer the next request has to wait to not be rejected: the
+    /// `retry-after` of a 429, or the rest of the window once no requests remain.
+    pub fn wait(&self) -> Option<Duration> {
+        match (self.retry_after, self.remaining) {
+            (Some(wait), _) => Some(wait),
+            (None, Some(0)) => self.reset,
+            _ => None,
+        }
+    }
+}
+
+// The API key's limit as the latest rate limit headers reported it, and whether requests wait
+// on it. HenrikDev counts the limit per key across every endpoint, so this is one bucket for
+// all routes rather than one per route.
+#[derive(Debug, Default)]
+pub(crate) struct KeyLimits {
+    throttle: bool,
+    latest: Mutex<Option<(Instant, ResponseMeta)>>,
+}
+
+impl KeyLimits {
+    pub(crate) fn new(throttle: bool) -> Self {
+        KeyLimits {
+            throttle,
+            latest: Mutex::new(None),
+        }
+    }
+
+    // Answers without rate limit headers, like those of some proxies, keep the last known ones
+    pub(crate) fn record(&self, headers: &HeaderMap) {
+        let meta = ResponseMeta::from_headers(headers);
+        if !meta.is_empty() {
+            *self.latest.lock().unwrap() = Some((Instant::now(), meta));
+        }
+    }
+
+    pub(crate) fn latest(&self) -> Option<ResponseMeta> {
+        self.latest.lock().unwrap().map(|(_, meta)| meta)
+    }
+
+    pub(crate) async fn wait(&self) {
+        if !self.throttle {
+            return;
+        }
+        let until = self
+            .latest
+            .lock()
+            .unwrap()
+            .and_then(|(received, meta)| Some(received + meta.wait()?));
+        if let Some(until) = until {
+            tokio::time::sleep_until(until.into()).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::fixtures::{ACCOUNT, MMR};
+    use crate::mock_server::{MockResponse, MockServer};
+    use crate::prelude::*;
+
+    #[test]
+    fn bucket_drains_and_refills() {
+        let budget = RateBudget::new(2, Duration::from_millis(100));
+        assert!(budget.try_acquire());
+        assert!(budget.clone().try_acquire());
+        assert!(!budget.try_acquire());
+        assert_eq!(budget.available(), 0);
+
+        std::thread::sleep(Duration::from_millis(60));
+        assert!(budget.try_acquire());
+    }
+
+    #[tokio::test]
+    async fn acquire_waits_for_a_token() {
+        let budget = RateBudget::new(2, Duration::from_millis(100));
+        let start = Instant::now();
+        for _ in 0..4 {
+            budget.acquire().await;
+        }
+        // The first two are free, the other two need a refill of 50ms each
+        assert!(start.elapsed() >= Duration::from_millis(90));
+    }
+
+    #[test]
+    fn headers_describe_the_budget() {
+        let mut headers = HeaderMap::new();
+        headers.insert("x-ratelimit-limit", "30".parse().unwrap());
+        headers.insert("x-ratelimit-remaining", "0".parse().unwrap());
+        headers.insert("x-ratelimit-reset", "12".parse().unwrap());
+        let meta = ResponseMeta::from_headers(&headers);
+        assert_eq!(meta.limit, Some(30));
+        assert_eq!(meta.wait(), Some(Duration::from_secs(12)));
+
+        headers.insert("x-ratelimit-remaining", "4".parse().unwrap());
+        assert_eq!(ResponseMeta::from_headers(&headers).wait(), None);
+        headers.insert("retry-after", "3".parse().unwrap());
+        assert_eq!(
+            ResponseMeta::from_headers(&headers).wait(),
+            Some(Duration::from_secs(3))
+        );
+        assert!(ResponseMeta::from_headers(&HeaderMap::new()).is_empty());
+    }
+
+    #[tokio::test]
+    async fn throttled_clients_wait_for_the_window() {
+        let server = MockServer::start(vec![MockResponse::json(200, ACCOUNT)
+            .header("x-ratelimit-limit", "30")
+            .header("x-ratelimit-remaining", "0")
+            .header("x-ratelimit-reset", "1")])
+        .await;
+        let url = server.url();
+        let client = ValorantClient::builder()
+            .api_endpoint(&url)
+            .throttle_on_rate_limit_headers(true)
+            .build()
+            .unwrap();
+        assert_eq!(client.rate_limit(), None);
+
+        let start = Instant::now();
+        for _ in 0..2 {
+            client
+                .request::<AccountData>(ValorantApiType::AccountData {
+                    name: "Anonymous",
+                    tag: "0000",
+                })
+                .await
+                .unwrap();
+        }
+        assert!(start.elapsed() >= Duration::from_millis(900));
+        let meta = client.rate_limit().unwrap();
+        assert_eq!((meta.limit, meta.remaining), (Some(30), Some(0)));
+    }
+
+    #[tokio::test]
+    async fn endpoints_share_the_key_limit() {
+        let server = MockServer::routes(vec![
+            (
+                "/v1/account/Anonymous/0000",
+                MockResponse::json(200, ACCOUNT)
+                    .header("x-ratelimit-remaining", "0")
+                    .header("x-ratelimit-reset", "1"),
+            ),
+            (
+                "/v2/mmr/eu/Anonymous/0000",
+                MockResponse::json(200, MMR).header("x-ratelimit-remaining", "29"),
+            ),
+        ])
+        .await;
+        let url = server.url();
+        let client = ValorantClient::builder()
+            .api_endpoint(&url)
+            .throttle_on_rate_limit_headers(true)
+            .build()
+            .unwrap();
+
+        client
+            .request::<AccountData>(ValorantApiType::AccountData {
+                name: "Anonymous",
+                tag: "0000",
+            })
+            .await
+            .unwrap();
+        // The exhausted window is the key's, so another endpoint waits for it too
+        let start = Instant::now();
+        client
+            .request::<MMRData>(ValorantApiType::MMRData {
+                region: AccountRegion::EU,
+                name: "Anonymous",
+                tag: "0000",
+                filter: None,
+            })
+            .await
+            .unwrap();
+        assert!(start.elapsed() >= Duration::from_millis(900));
+        assert_eq!(client.rate_limit().unwrap().remaining, Some(29));
+    }
+}
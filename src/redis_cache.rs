@@ -0,0 +1,112 @@
+//! A [`ResponseCache`] in Redis, so a fleet of bot processes shares one cache and each player
+//! is only fetched once per cooldown across all of them.
+
+use crate::cache::{CachedResponse, ResponseCache};
+use futures::future::BoxFuture;
+use redis::aio::MultiplexedConnection;
+use std::io;
+use std::time::Duration;
+
+const DEFAULT_PREFIX: &str = "valorant-api:";
+
+pub struct RedisCache {
+    connection: MultiplexedConnection,
+    prefix: String,
+}
+
+impl RedisCache {
+    /// Connects to the server at `url`, e.g. `redis://127.0.0.1/`.
+    pub async fn connect(url: &str) -> redis::RedisResult<Self> {
+        let client = redis::Client::open(url)?;
+        Ok(RedisCache::new(
+            client.get_multiplexed_tokio_connection().await?,
+        ))
+    }
+
+    pub fn new(connection: MultiplexedConnection) -> Self {
+        RedisCache {
+            connection,
+            prefix: DEFAULT_PREFIX.to_string(),
+        }
+    }
+
+    /// Prepended to every key, `valorant-api:` by default.
+    pub fn prefix(mut self, prefix: &str) -> Self {
+        self.prefix = prefix.to_string();
+        self
+    }
+}
+
+impl ResponseCache for RedisCache {
+    fn get<'a>(&'a self, key: &'a str) -> BoxFuture<'a, io::Result<Option<CachedResponse>>> {
+        Box::pin(async move {
+            let mut connection = self.connection.clone();
+            let value: Option<Vec<u8>> = redis::cmd("GET")
+                .arg(format!("{}{key}", self.prefix))
+                .query_async(&mut connection)
+                .await
+                .map_err(io::Error::other)?;
+            Ok(value.as_deref().and_then(decode))
+        })
+    }
+
+    fn put<'a>(
+        &'a self,
+        key: &'a str,
+        response: CachedResponse,
+        ttl: Duration,
+    ) -> BoxFuture<'a, io::Result<()>> {
+        Box::pin(async move {
+            let mut connection = self.connection.clone();
+            redis::cmd("SET")
+                .arg(format!("{}{key}", self.prefix))
+                .arg(encode(&response))
+                .arg("PX")
+                .arg(ttl.as_millis().max(1) as u64)
+                .query_async::<_, ()>(&mut connection)
+                .await
+                .map_err(io::Error::other)
+        })
+    }
+
+    fn invalidate<'a>(&'a self, key: &'a str) -> BoxFuture<'a, io::Result<()>> {
+        Box::pin(async move {
+            let mut connection = self.connection.clone();
+            redis::cmd("DEL")
+                .arg(format!("{}{key}", self.prefix))
+                .query_async::<_, ()>(&mut connection)
+                .await
+                .map_err(io::Error::other)
+        })
+    }
+}
+
+// The status code goes in front of the body as two big endian bytes
+fn encode(response: &CachedResponse) -> Vec<u8> {
+    let mut value = response.status.to_be_bytes().to_vec();
+    value.extend_from_slice(&response.body);
+    value
+}
+
+fn decode(value: &[u8]) -> Option<CachedResponse> {
+    let (status, body) = value.split_first_chunk::<2>()?;
+    Some(CachedResponse {
+        status: u16::from_be_bytes(*status),
+        body: body.to_vec(),
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn values_round_trip() {
+        let response = CachedResponse {
+            status: 404,
+            body: br#"{"status": 404}"#.to_vec(),
+        };
+        assert_eq!(decode(&encode(&response)), Some(response));
+        assert_eq!(decode(b"x"), None);
+    }
+}
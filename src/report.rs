@@ -0,0 +1,210 @@
+//! Reports rendered as Markdown documents, for posting to Discord forums or GitHub gists.
+//!
+//! [`to_markdown`] renders anything implementing [`Markdown`]: a [`SeriesReport`] of a scrim
+//! block or best of, and the day or week [`PeriodSummary`]s of a player's session or season.
+//! Names are escaped, so a team called `a|b` doesn't break a table.
+
+use crate::periods::PeriodSummary;
+use crate::series::{SeriesPlayer, SeriesReport};
+use std::fmt::Write;
+
+/// A report that can be written out as Markdown.
+pub trait Markdown {
+    fn write_markdown(&self, out: &mut String);
+}
+
+pub fn to_markdown(report: &(impl Markdown + ?Sized)) -> String {
+    let mut out = String::new();
+    report.write_markdown(&mut out);
+    out
+}
+
+impl Markdown for SeriesReport {
+    fn write_markdown(&self, out: &mut String) {
+        let [first, second] = &self.teams;
+        let _ = writeln!(
+            out,
+            "# {} {} - {} {}\n",
+            escape(&first.name),
+            first.maps_won,
+            second.maps_won,
+            escape(&second.name)
+        );
+
+        out.push_str("## Maps\n\n");
+        table(
+            out,
+            &["Map", &first.name, &second.name, "Winner"],
+            &[false, true, true, false],
+        );
+        for map in &self.maps {
+            let winner = match map.winner {
+                Some(team) => escape(&self.teams[team].name),
+                None => "Draw".to_string(),
+            };
+            let forfeit = if map.forfeit { " (forfeit)" } else { "" };
+            let _ = writeln!(
+                out,
+                "| {} | {} | {} | {winner}{forfeit} |",
+                escape(&map.map),
+                map.score[0],
+                map.score[1]
+            );
+        }
+
+        out.push_str("\n## Sides\n\n");
+        table(out, &["Team", "Attack", "Defense"], &[false, true, true]);
+        for team in &self.teams {
+            let _ = writeln!(
+                out,
+                "| {} | {}/{} | {}/{} |",
+                escape(&team.name),
+                team.attack.won,
+                team.attack.played,
+                team.defense.won,
+                team.defense.played
+            );
+        }
+
+        for (index, team) in self.teams.iter().enumerate() {
+            let _ = writeln!(out, "\n## {}\n", escape(&team.name));
+            table(
+                out,
+                &["Player", "Agents", "ACS", "K", "D", "A", "K/D", "HS%"],
+                &[false, false, true, true, true, true, true, true],
+            );
+            for player in self.players.iter().filter(|player| player.team == index) {
+                player_row(out, player);
+            }
+        }
+
+        if !self.missing.is_empty() {
+            out.push_str("\n## Missing\n\n");
+            for id in &self.missing {
+                let _ = writeln!(out, "- `{}`", id.as_str());
+            }
+        }
+    }
+}
+
+fn player_row(out: &mut String, player: &SeriesPlayer) {
+    let _ = writeln!(
+        out,
+        "| {}#{} | {} | {:.0} | {} | {} | {} | {:.2} | {:.0}% |",
+        escape(&player.name),
+        escape(&player.tag),
+        escape(&player.agents.join(", ")),
+        player.acs(),
+        player.kills,
+        player.deaths,
+        player.assists,
+        player.kd(),
+        player.headshot_rate() * 100.0
+    );
+}
+
+/// A table of the periods with a total row, e.g. the days of a season.
+impl Markdown for [PeriodSummary] {
+    fn write_markdown(&self, out: &mut String) {
+        table(
+            out,
+            &["From", "Matches", "W", "L", "D", "Win rate"],
+            &[false, true, true, true, true, true],
+        );
+        let mut total = [0; 4];
+        for period in self {
+            row(
+                out,
+                &period.start.to_string(),
+                [period.matches, period.wins, period.losses, period.draws],
+            );
+            total[0] += period.matches;
+            total[1] += period.wins;
+            total[2] += period.losses;
+            total[3] += period.draws;
+        }
+        row(out, "**Total**", total);
+
+        fn row(out: &mut String, label: &str, [matches, wins, losses, draws]: [u32; 4]) {
+            let rate = match matches {
+                0 => 0.0,
+                matches => f64::from(wins) / f64::from(matches) * 100.0,
+            };
+            let _ = writeln!(
+                out,
+                "| {label} | {matches} | {wins} | {losses} | {draws} | {rate:.0}% |"
+            );
+        }
+    }
+}
+
+impl Markdown for Vec<PeriodSummary> {
+    fn write_markdown(&self, out: &mut String) {
+        self.as_slice().write_markdown(out);
+    }
+}
+
+// A header row and the alignment row, numeric columns aligned right
+fn table(out: &mut String, headers: &[&str], numeric: &[bool]) {
+    let headers: Vec<_> = headers.iter().map(|header| escape(header)).collect();
+    let _ = writeln!(out, "| {} |", headers.join(" | "));
+    let alignments: Vec<_> = numeric
+        .iter()
+        .map(|&numeric| if numeric { "---:" } else { "---" })
+        .collect();
+    let _ = writeln!(out, "| {} |", alignments.join(" | "));
+}
+
+// Characters that would end a table cell or start formatting
+fn escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for character in value.chars() {
+        if matches!(
+            character,
+            '|' | '*' | '_' | '`' | '#' | '\\' | '<' | '>' | '[' | ']'
+        ) {
+            escaped.push('\\');
+        }
+        escaped.push(character);
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::fixtures::{decode, MATCH_CUSTOM, MATCH_HISTORY};
+    use crate::match_data::MatchData;
+
+    fn matches(fixture: &str) -> Vec<MatchData> {
+        decode::<Vec<MatchData>>(fixture)
+    }
+
+    #[test]
+    fn renders_series() {
+        let report = SeriesReport::from_matches(&matches(MATCH_CUSTOM));
+        insta::assert_snapshot!(to_markdown(&report));
+    }
+
+    #[test]
+    fn renders_periods() {
+        let days = crate::periods::by_day(
+            &matches(MATCH_HISTORY),
+            "81862fc9-634f-806f-abf4-a07c56600224",
+            &chrono::Utc,
+        );
+        assert_eq!(
+            to_markdown(&days),
+            "| From | Matches | W | L | D | Win rate |\n\
+             | --- | ---: | ---: | ---: | ---: | ---: |\n\
+             | 2023-02-14 | 1 | 1 | 0 | 0 | 100% |\n\
+             | **Total** | 1 | 1 | 0 | 0 | 100% |\n"
+        );
+    }
+
+    #[test]
+    fn names_are_escaped() {
+        assert_eq!(escape("a|b_c"), r"a\|b\_c");
+        assert_eq!(escape("Nerd Herd"), "Nerd Herd");
+    }
+}
@@ -0,0 +1,436 @@
+//! Whether a failed request is worth sending again, without matching on transport internals.
+//!
+//! [`RetryHint`] is implemented by the failure side of [`ApiResponse`] and by every error the
+//! client returns. Rate limits (429), request timeouts (408) and server errors are retryable,
+//! as are transport timeouts and refused connections. Answers of the API that carried a
+//! `Retry-After` header report how long it asked to wait.
+//!
+//! A [`RetryPolicy`] attached to the client resends those requests itself, backing off
+//! exponentially between attempts.
+
+use crate::outcome::LegFailure;
+use crate::{ApiError, ApiResponse, ValorantAPIData};
+use chrono::{DateTime, Utc};
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::time::Duration;
+
+pub trait RetryHint {
+    /// Whether sending the same request again may succeed.
+    fn is_retryable(&self) -> bool;
+
+    /// How long the API asked to wait before the next attempt.
+    fn retry_after(&self) -> Option<Duration>;
+
+    /// Whether the API doesn't know the player, match or resource asked for.
+    fn is_not_found(&self) -> bool;
+}
+
+pub(crate) fn retryable_status(status: u32) -> bool {
+    matches!(status, 408 | 429 | 500..=599)
+}
+
+fn first_retry_after(errors: &[ApiError]) -> Option<Duration> {
+    errors.iter().find_map(|error| error.retry_after)
+}
+
+// Either delay-seconds or an HTTP date, which is treated as zero once it passed
+pub(crate) fn parse_retry_after(value: &str) -> Option<Duration> {
+    let value = value.trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+    let date = DateTime::parse_from_rfc2822(value).ok()?;
+    Some(
+        (date.with_timezone(&Utc) - Utc::now())
+            .to_std()
+            .unwrap_or_default(),
+    )
+}
+
+impl RetryHint for reqwest::Error {
+    fn is_retryable(&self) -> bool {
+        self.is_timeout()
+            || self.is_connect()
+            || self
+                .status()
+                .is_some_and(|status| retryable_status(u32::from(status.as_u16())))
+    }
+
+    fn retry_after(&self) -> Option<Duration> {
+        None
+    }
+
+    fn is_not_found(&self) -> bool {
+        self.status() == Some(reqwest::StatusCode::NOT_FOUND)
+    }
+}
+
+/// How often and how patiently the client resends requests that failed with a 408, a 429, a
+/// server error, a timeout or a refused connection.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RetryPolicy {
+    max_attempts: u32,
+    base_delay: Duration,
+    max_delay: Duration,
+    jitter: f64,
+}
+
+impl Default for RetryPolicy {
+    /// Three attempts, waiting half a second and then a second, each up to 20% shorter.
+    fn default() -> Self {
+        RetryPolicy {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(30),
+            jitter: 0.2,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Sends every request up to `max_attempts` times in total, the first attempt included.
+    pub fn new(max_attempts: u32) -> Self {
+        RetryPolicy {
+            max_attempts: max_attempts.max(1),
+            ..RetryPolicy::default()
+        }
+    }
+
+    /// The wait before the first retry, doubled for every retry after it.
+    pub fn base_delay(mut self, delay: Duration) -> Self {
+        self.base_delay = delay;
+        self
+    }
+
+    pub fn max_delay(mut self, delay: Duration) -> Self {
+        self.max_delay = delay;
+        self
+    }
+
+    /// Shortens every wait by a random share of up to `share`, so clients that failed together
+    /// don't retry together.
+    pub fn jitter(mut self, share: f64) -> Self {
+        self.jitter = share.clamp(0.0, 1.0);
+        self
+    }
+
+    pub fn max_attempts(&self) -> u32 {
+        self.max_attempts
+    }
+
+    /// The wait before retry number `retry`, starting at 1. A `Retry-After` the API sent
+    /// takes precedence when it is longer, unless it is longer than the maximum delay, in which
+    /// case the request is not retried and its response is returned as is.
+    pub fn delay(&self, retry: u32) -> Duration {
+        let doublings = retry.saturating_sub(1).min(31);
+        let delay = self
+            .base_delay
+            .saturating_mul(1 << doublings)
+            .min(self.max_delay);
+        delay.mul_f64(1.0 - self.jitter * random_share())
+    }
+
+    // The wait before retry number `retry` of a response that sent `retry_after`, or None when
+    // the API asked for a longer wait than the policy allows
+    pub(crate) fn delay_after(
+        &self,
+        retry: u32,
+        retry_after: Option<Duration>,
+    ) -> Option<Duration> {
+        match retry_after {
+            Some(retry_after) if retry_after > self.max_delay => None,
+            retry_after => Some(self.delay(retry).max(retry_after.unwrap_or_default())),
+        }
+    }
+}
+
+// A share in 0..1 from the randomly keyed std hasher, which is plenty for jitter
+fn random_share() -> f64 {
+    let random = RandomState::new().build_hasher().finish();
+    (random >> 11) as f64 / (1u64 << 53) as f64
+}
+
+/// A success is neither retryable nor missing.
+impl<T: ValorantAPIData> RetryHint for ApiResponse<T> {
+    fn is_retryable(&self) -> bool {
+        match self {
+            ApiResponse::Success { .. } => false,
+            ApiResponse::Failure { status, .. } => retryable_status(*status),
+        }
+    }
+
+    fn retry_after(&self) -> Option<Duration> {
+        match self {
+            ApiResponse::Success { .. } => None,
+            ApiResponse::Failure { errors, .. } => first_retry_after(errors),
+        }
+    }
+
+    fn is_not_found(&self) -> bool {
+        matches!(self, ApiResponse::Failure { status: 404, .. })
+    }
+}
+
+impl RetryHint for LegFailure {
+    fn is_retryable(&self) -> bool {
+        retryable_status(self.status)
+    }
+
+    fn retry_after(&self) -> Option<Duration> {
+        first_retry_after(&self.errors)
+    }
+
+    fn is_not_found(&self) -> bool {
+        self.status == 404
+    }
+}
+
+/// Storage failures are local and not retried.
+#[cfg(feature = "storage")]
+impl RetryHint for crate::archive::FetchError {
+    fn is_retryable(&self) -> bool {
+        match self {
+            crate::archive::FetchError::Request(error) => error.is_retryable(),
+            crate::archive::FetchError::Storage(_) => false,
+        }
+    }
+
+    fn retry_after(&self) -> Option<Duration> {
+        match self {
+            crate::archive::FetchError::Request(error) => error.retry_after(),
+            crate::archive::FetchError::Storage(_) => None,
+        }
+    }
+
+    fn is_not_found(&self) -> bool {
+        match self {
+            crate::archive::FetchError::Request(error) => error.is_not_found(),
+            crate::archive::FetchError::Storage(_) => false,
+        }
+    }
+}
+
+#[cfg(feature = "storage")]
+impl RetryHint for crate::backfill::BackfillError {
+    fn is_retryable(&self) -> bool {
+        match self {
+            crate::backfill::BackfillError::Request(error) => error.is_retryable(),
+            crate::backfill::BackfillError::Storage(_) => false,
+        }
+    }
+
+    fn retry_after(&self) -> Option<Duration> {
+        match self {
+            crate::backfill::BackfillError::Request(error) => error.retry_after(),
+            crate::backfill::BackfillError::Storage(_) => None,
+        }
+    }
+
+    fn is_not_found(&self) -> bool {
+        match self {
+            crate::backfill::BackfillError::Request(error) => error.is_not_found(),
+            crate::backfill::BackfillError::Storage(_) => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::fixtures::{ACCOUNT, NOT_FOUND};
+    use crate::mock_server::{MockResponse, MockServer, RateLimitSimulator};
+    use crate::prelude::*;
+
+    const RATE_LIMITED: &str =
+        r#"{"status":429,"errors":[{"message":"Rate Limited","code":0,"details":"null"}]}"#;
+
+    #[tokio::test]
+    async fn failures_carry_retry_hints() {
+        let server = MockServer::start(vec![
+            MockResponse::json(429, RATE_LIMITED).header("retry-after", "7"),
+            MockResponse::json(404, NOT_FOUND),
+        ])
+        .await;
+        let url = server.url();
+        let client = ValorantClient::new().change_api_endpoint(&url);
+        let account = || ValorantApiType::AccountData {
+            name: "Anonymous",
+            tag: "0000",
+        };
+
+        let limited = client.request::<AccountData>(account()).await.unwrap_err();
+        assert!(limited.is_retryable());
+        assert!(!limited.is_not_found());
+        assert_eq!(limited.retry_after(), Some(Duration::from_secs(7)));
+
+        let missing = client.request::<AccountData>(account()).await.unwrap_err();
+        assert!(missing.is_not_found());
+        assert!(!missing.is_retryable());
+        assert_eq!(missing.retry_after(), None);
+    }
+
+    #[tokio::test]
+    async fn refused_connections_are_retryable() {
+        let client = ValorantClient::new().change_api_endpoint("http://127.0.0.1:1");
+        let error = client
+            .request::<AccountData>(ValorantApiType::AccountData {
+                name: "Anonymous",
+                tag: "0000",
+            })
+            .await
+            .unwrap_err();
+        assert!(error.is_retryable());
+        assert!(!error.is_not_found());
+        assert!(!ValorantError::DeadlineExceeded.is_retryable());
+    }
+
+    #[test]
+    fn delays_back_off_exponentially() {
+        let policy = RetryPolicy::new(5)
+            .base_delay(Duration::from_millis(100))
+            .max_delay(Duration::from_millis(350))
+            .jitter(0.0);
+        let delays: Vec<_> = (1..=4)
+            .map(|retry| policy.delay(retry).as_millis())
+            .collect();
+        assert_eq!(delays, [100, 200, 350, 350]);
+        assert_eq!(RetryPolicy::new(0).max_attempts(), 1);
+
+        let jittered = RetryPolicy::default().jitter(0.5).delay(1);
+        assert!(jittered > Duration::from_millis(250) && jittered <= Duration::from_millis(500));
+    }
+
+    #[tokio::test]
+    async fn server_errors_are_retried() {
+        let server = MockServer::start(vec![
+            MockResponse::json(503, RATE_LIMITED),
+            MockResponse::json(429, RATE_LIMITED).header("retry-after", "0"),
+            MockResponse::json(200, ACCOUNT),
+            MockResponse::json(404, NOT_FOUND),
+        ])
+        .await;
+        let url = server.url();
+        let policy = RetryPolicy::new(3).base_delay(Duration::from_millis(10));
+        let client = ValorantClient::builder()
+            .api_endpoint(&url)
+            .retry_policy(policy)
+            .build()
+            .unwrap();
+        let account = || ValorantApiType::AccountData {
+            name: "Anonymous",
+            tag: "0000",
+        };
+
+        client.request::<AccountData>(account()).await.unwrap();
+        assert_eq!(server.requests().len(), 3);
+
+        // A 404 is an answer and is not retried
+        let missing = client.request::<AccountData>(account()).await.unwrap_err();
+        assert!(missing.is_not_found());
+        assert_eq!(server.requests().len(), 4);
+    }
+
+    #[tokio::test]
+    async fn retries_stop_after_the_last_attempt() {
+        let server = MockServer::start(vec![MockResponse::json(503, RATE_LIMITED)]).await;
+        let url = server.url();
+        let client = ValorantClient::builder()
+            .api_endpoint(&url)
+            .retry_policy(RetryPolicy::new(2).base_delay(Duration::from_millis(10)))
+            .build()
+            .unwrap();
+        let failed = client
+            .request::<AccountData>(ValorantApiType::AccountData {
+                name: "Anonymous",
+                tag: "0000",
+            })
+            .await
+            .unwrap_err();
+        assert!(failed.is_retryable());
+        assert_eq!(server.requests().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn retry_afters_past_the_maximum_delay_are_not_waited_for() {
+        let server = MockServer::start(vec![
+            MockResponse::json(429, RATE_LIMITED).header("retry-after", "86400"),
+            MockResponse::json(200, ACCOUNT),
+        ])
+        .await;
+        let url = server.url();
+        let policy = RetryPolicy::new(3).max_delay(Duration::from_secs(1));
+        let client = ValorantClient::builder()
+            .api_endpoint(&url)
+            .retry_policy(policy)
+            .build()
+            .unwrap();
+        let limited = client
+            .request::<AccountData>(ValorantApiType::AccountData {
+                name: "Anonymous",
+                tag: "0000",
+            })
+            .await
+            .unwrap_err();
+        assert_eq!(limited.retry_after(), Some(Duration::from_secs(86400)));
+        assert_eq!(server.requests().len(), 1);
+
+        let policy = RetryPolicy::new(3).jitter(0.0);
+        assert_eq!(
+            policy.delay_after(1, Some(Duration::from_secs(2))),
+            Some(Duration::from_secs(2))
+        );
+        assert_eq!(
+            policy.delay_after(1, None),
+            Some(Duration::from_millis(500))
+        );
+        assert_eq!(policy.delay_after(1, Some(Duration::from_secs(31))), None);
+    }
+
+    #[tokio::test]
+    async fn simulated_rate_limits_are_waited_out() {
+        let simulator = RateLimitSimulator::new(2)
+            .rejections(2)
+            .retry_after(Duration::ZERO);
+        let server =
+            MockServer::rate_limited(vec![MockResponse::json(200, ACCOUNT)], simulator).await;
+        let url = server.url();
+        let account = || ValorantApiType::AccountData {
+            name: "Anonymous",
+            tag: "0000",
+        };
+
+        // Without a policy the third request sees the 429
+        let client = ValorantClient::new().change_api_endpoint(&url);
+        for _ in 0..2 {
+            client.request::<AccountData>(account()).await.unwrap();
+        }
+        let limited = client.request::<AccountData>(account()).await.unwrap_err();
+        assert!(limited.is_retryable());
+        assert_eq!(limited.retry_after(), Some(Duration::ZERO));
+        assert_eq!(client.rate_limit().unwrap().remaining, Some(0));
+
+        // One rejection is left in the window, which a retry gets past
+        let client = ValorantClient::builder()
+            .api_endpoint(&url)
+            .retry_policy(RetryPolicy::new(3).base_delay(Duration::from_millis(10)))
+            .build()
+            .unwrap();
+        client.request::<AccountData>(account()).await.unwrap();
+        assert_eq!(server.requests().len(), 5);
+        assert_eq!(client.rate_limit().unwrap().remaining, Some(1));
+    }
+
+    #[test]
+    fn retry_after_accepts_seconds_and_dates() {
+        assert_eq!(parse_retry_after(" 120 "), Some(Duration::from_secs(120)));
+        assert_eq!(
+            parse_retry_after("Wed, 21 Oct 2015 07:28:00 GMT"),
+            Some(Duration::ZERO)
+        );
+        let soon = (Utc::now() + chrono::Duration::minutes(5)).to_rfc2822();
+        let wait = parse_retry_after(&soon).unwrap();
+        assert!(wait > Duration::from_secs(290) && wait <= Duration::from_secs(300));
+        assert_eq!(parse_retry_after("soon"), None);
+    }
+}
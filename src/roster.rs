@@ -0,0 +1,419 @@
+//! Named groups of players, like a team or a clan, handled as one unit.
+//!
+//! A [`Roster`] lists its members by Riot ID or PUUID and serializes to JSON, so tools can keep
+//! it in a config file. The client fetches every member's rank into a [`RosterSnapshot`], which
+//! compares against earlier snapshots and exports as CSV. Watching a roster repeats the snapshot
+//! on an interval and reports what changed.
+
+#[cfg(feature = "client")]
+use crate::bulk::BulkResult;
+use crate::ids::{Puuid, RiotId};
+use crate::mmr_data::MMRData;
+use crate::AccountRegion;
+#[cfg(feature = "client")]
+use crate::{ValorantApiType, ValorantClient};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+#[cfg(feature = "client")]
+use std::sync::Arc;
+#[cfg(feature = "client")]
+use std::time::Duration;
+#[cfg(feature = "client")]
+use tokio::sync::mpsc;
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum RosterMember {
+    RiotId(RiotId),
+    Puuid(Puuid),
+}
+
+impl RosterMember {
+    // Riot IDs are case insensitive, PUUIDs are already lowercased
+    fn key(&self) -> String {
+        match self {
+            RosterMember::RiotId(id) => id.to_string().to_lowercase(),
+            RosterMember::Puuid(puuid) => puuid.to_string(),
+        }
+    }
+
+    #[cfg(feature = "client")]
+    fn request(&self, region: AccountRegion) -> ValorantApiType<'_> {
+        match self {
+            RosterMember::RiotId(id) => ValorantApiType::MMRData {
+                region,
+                name: id.name(),
+                tag: id.tag(),
+                filter: None,
+            },
+            RosterMember::Puuid(puuid) => ValorantApiType::MMRDataByPuuid {
+                region,
+                puuid: puuid.as_str(),
+            },
+        }
+    }
+}
+
+impl fmt::Display for RosterMember {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RosterMember::RiotId(id) => id.fmt(f),
+            RosterMember::Puuid(puuid) => puuid.fmt(f),
+        }
+    }
+}
+
+impl From<RiotId> for RosterMember {
+    fn from(id: RiotId) -> Self {
+        RosterMember::RiotId(id)
+    }
+}
+
+impl From<Puuid> for RosterMember {
+    fn from(puuid: Puuid) -> Self {
+        RosterMember::Puuid(puuid)
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Roster {
+    name: String,
+    region: AccountRegion,
+    members: Vec<RosterMember>,
+}
+
+/// How a roster changed into another one.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct RosterDiff {
+    pub added: Vec<RosterMember>,
+    pub removed: Vec<RosterMember>,
+}
+
+impl Roster {
+    pub fn new(name: impl Into<String>, region: AccountRegion) -> Self {
+        Roster {
+            name: name.into(),
+            region,
+            members: Vec::new(),
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn region(&self) -> AccountRegion {
+        self.region
+    }
+
+    /// In the order they were added.
+    pub fn members(&self) -> &[RosterMember] {
+        &self.members
+    }
+
+    /// Adds a member unless they are on the roster already, comparing Riot IDs without case.
+    /// Returns whether the member was added.
+    pub fn add(&mut self, member: impl Into<RosterMember>) -> bool {
+        let member = member.into();
+        if self.contains(&member) {
+            return false;
+        }
+        self.members.push(member);
+        true
+    }
+
+    /// Returns whether the member was on the roster.
+    pub fn remove(&mut self, member: &RosterMember) -> bool {
+        let key = member.key();
+        let before = self.members.len();
+        self.members.retain(|existing| existing.key() != key);
+        self.members.len() < before
+    }
+
+    pub fn contains(&self, member: &RosterMember) -> bool {
+        let key = member.key();
+        self.members.iter().any(|existing| existing.key() == key)
+    }
+
+    /// The members `newer` added and removed compared to this roster.
+    pub fn diff(&self, newer: &Roster) -> RosterDiff {
+        let only_in = |roster: &Roster, other: &Roster| {
+            roster
+                .members
+                .iter()
+                .filter(|member| !other.contains(member))
+                .cloned()
+                .collect()
+        };
+        RosterDiff {
+            added: only_in(newer, self),
+            removed: only_in(self, newer),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Rank {
+    pub tier: u32,
+    pub tier_patched: String,
+    pub rr: u32,
+    pub elo: u32,
+}
+
+impl From<&MMRData> for Rank {
+    fn from(mmr: &MMRData) -> Self {
+        Rank {
+            tier: mmr.current_tier().id(),
+            tier_patched: mmr.current_tier_patched().to_string(),
+            rr: mmr.ranking_in_tier(),
+            elo: mmr.elo(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct MemberRank {
+    pub member: RosterMember,
+    /// `None` when the member's rank couldn't be fetched.
+    pub rank: Option<Rank>,
+}
+
+/// Every member's rank at one point in time.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct RosterSnapshot {
+    pub roster: String,
+    pub taken_at: DateTime<Utc>,
+    /// In roster order.
+    pub members: Vec<MemberRank>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct RankChange {
+    pub member: RosterMember,
+    pub before: Rank,
+    pub after: Rank,
+}
+
+impl RankChange {
+    pub fn elo_delta(&self) -> i64 {
+        i64::from(self.after.elo) - i64::from(self.before.elo)
+    }
+}
+
+impl RosterSnapshot {
+    #[cfg(feature = "client")]
+    fn from_results(roster: &Roster, results: BulkResult<MMRData>) -> Self {
+        let mut members: Vec<_> = roster
+            .members
+            .iter()
+            .map(|member| MemberRank {
+                member: member.clone(),
+                rank: None,
+            })
+            .collect();
+        for (index, mmr) in &results.succeeded {
+            members[*index].rank = Some(Rank::from(mmr));
+        }
+        RosterSnapshot {
+            roster: roster.name.clone(),
+            taken_at: Utc::now(),
+            members,
+        }
+    }
+
+    /// The members with a rank, best first.
+    pub fn standings(&self) -> Vec<&MemberRank> {
+        let mut ranked: Vec<_> = self
+            .members
+            .iter()
+            .filter(|member| member.rank.is_some())
+            .collect();
+        ranked.sort_by_key(|member| std::cmp::Reverse(member.rank.as_ref().map(|rank| rank.elo)));
+        ranked
+    }
+
+    /// Members whose rank differs from `earlier`. Members missing a rank in either snapshot
+    /// are left out.
+    pub fn changes_since(&self, earlier: &RosterSnapshot) -> Vec<RankChange> {
+        self.members
+            .iter()
+            .filter_map(|now| {
+                let after = now.rank.as_ref()?;
+                let before = earlier
+                    .members
+                    .iter()
+                    .find(|then| then.member.key() == now.member.key())?
+                    .rank
+                    .as_ref()?;
+                (before != after).then(|| RankChange {
+                    member: now.member.clone(),
+                    before: before.clone(),
+                    after: after.clone(),
+                })
+            })
+            .collect()
+    }
+
+    /// One line per member with a header, leaving the rank columns of unranked members empty.
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("member,tier,rank,rr,elo\n");
+        for MemberRank { member, rank } in &self.members {
+            let rank = match rank {
+                Some(rank) => format!(
+                    "{},{},{},{}",
+                    rank.tier,
+                    csv_field(&rank.tier_patched),
+                    rank.rr,
+                    rank.elo
+                ),
+                None => ",,,".to_string(),
+            };
+            csv.push_str(&format!("{},{rank}\n", csv_field(&member.to_string())));
+        }
+        csv
+    }
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(feature = "client")]
+impl ValorantClient<'_> {
+    /// Fetches the rank of every member of `roster`.
+    pub async fn snapshot_roster(&self, roster: &Roster) -> RosterSnapshot {
+        let requests = roster
+            .members
+            .iter()
+            .map(|member| member.request(roster.region))
+            .collect();
+        RosterSnapshot::from_results(roster, self.request_many::<MMRData>(requests).await)
+    }
+}
+
+#[cfg(feature = "client")]
+impl ValorantClient<'static> {
+    /// Snapshots `roster` every `every` and sends the rank changes since the previous snapshot,
+    /// whenever there are any. Watching stops once the receiver is dropped.
+    pub fn watch_roster(
+        self: Arc<Self>,
+        roster: Roster,
+        every: Duration,
+    ) -> mpsc::Receiver<Vec<RankChange>> {
+        let (sender, receiver) = mpsc::channel(16);
+        tokio::spawn(async move {
+            let mut previous = self.snapshot_roster(&roster).await;
+            loop {
+                tokio::select! {
+                    _ = tokio::time::sleep(every) => {}
+                    _ = sender.closed() => return,
+                }
+                let snapshot = self.snapshot_roster(&roster).await;
+                let changes = snapshot.changes_since(&previous);
+                if !changes.is_empty() && sender.send(changes).await.is_err() {
+                    return;
+                }
+                previous = snapshot;
+            }
+        });
+        receiver
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    #[cfg(feature = "client")]
+    use crate::fixtures::MMR;
+    #[cfg(feature = "client")]
+    use crate::mock_server::{MockResponse, MockServer};
+
+    fn roster() -> Roster {
+        let mut roster = Roster::new("Team NERD", AccountRegion::EU);
+        roster.add("Anonymous#0000".parse::<RiotId>().unwrap());
+        roster.add(
+            "5a4c1f0e-7d2b-5e93-a1c4-3f6b8e2d9c10"
+                .parse::<Puuid>()
+                .unwrap(),
+        );
+        roster.add("Missing#NERD".parse::<RiotId>().unwrap());
+        roster
+    }
+
+    #[test]
+    fn members_are_unique() {
+        let mut roster = roster();
+        assert!(!roster.add("anonymous#0000".parse::<RiotId>().unwrap()));
+        assert_eq!(roster.members().len(), 3);
+
+        let mut newer = roster.clone();
+        let missing = RosterMember::from("Missing#NERD".parse::<RiotId>().unwrap());
+        assert!(newer.remove(&missing));
+        newer.add("Newcomer#EUW".parse::<RiotId>().unwrap());
+        let diff = roster.diff(&newer);
+        assert_eq!(diff.removed, [missing]);
+        assert_eq!(diff.added[0].to_string(), "Newcomer#EUW");
+
+        let json = serde_json::to_string(&roster).unwrap();
+        assert_eq!(serde_json::from_str::<Roster>(&json).unwrap(), roster);
+    }
+
+    #[cfg(feature = "client")]
+    #[tokio::test]
+    async fn snapshots_compare_and_export() {
+        let server = MockServer::routes(vec![
+            ("/v2/mmr/eu/Anonymous/0000", MockResponse::json(200, MMR)),
+            (
+                "/v2/by-puuid/mmr/eu/5a4c1f0e-7d2b-5e93-a1c4-3f6b8e2d9c10",
+                MockResponse::json(200, &MMR.replace("1347", "1400")),
+            ),
+        ])
+        .await;
+        let url = server.url();
+        let client = ValorantClient::new().change_api_endpoint(&url);
+
+        let roster = roster();
+        let snapshot = client.snapshot_roster(&roster).await;
+        assert!(snapshot.members[2].rank.is_none());
+        let standings = snapshot.standings();
+        assert_eq!(standings.len(), 2);
+        assert_eq!(standings[0].member, roster.members()[1]);
+        assert_eq!(
+            snapshot.to_csv(),
+            "member,tier,rank,rr,elo\n\
+             Anonymous#0000,16,Platinum 2,47,1347\n\
+             5a4c1f0e-7d2b-5e93-a1c4-3f6b8e2d9c10,16,Platinum 2,47,1400\n\
+             Missing#NERD,,,,\n"
+        );
+
+        let mut later = snapshot.clone();
+        later.members[0].rank.as_mut().unwrap().elo = 1370;
+        let changes = later.changes_since(&snapshot);
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].elo_delta(), 23);
+    }
+
+    #[cfg(feature = "client")]
+    #[tokio::test]
+    async fn watching_reports_changes() {
+        let server = MockServer::start(vec![
+            MockResponse::json(200, MMR),
+            MockResponse::json(200, &MMR.replace("1347", "1360")),
+        ])
+        .await;
+        let url: &'static str = Box::leak(server.url().into_boxed_str());
+        let client = Arc::new(ValorantClient::new().change_api_endpoint(url));
+        let mut roster = Roster::new("Solo", AccountRegion::EU);
+        roster.add("Anonymous#0000".parse::<RiotId>().unwrap());
+
+        let mut changes = client.watch_roster(roster, Duration::from_millis(20));
+        let changes = changes.recv().await.unwrap();
+        assert_eq!(changes[0].elo_delta(), 13);
+    }
+}
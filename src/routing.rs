@@ -0,0 +1,94 @@
+//! Which path segment each endpoint expects for a region or platform, kept in one table.
+//!
+//! The API's own endpoints name regions after the account region. Riot's services behind the
+//! raw endpoint are split into shards instead, where Brazil and Latin America share the North
+//! American one. Endpoints build their paths from here rather than matching on regions
+//! themselves. Console accounts use the same regions, with [`Platform::Console`] on the
+//! endpoints that tell platforms apart.
+
+use crate::AccountRegion;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "graphql", derive(async_graphql::Enum))]
+#[serde(rename_all = "lowercase")]
+#[non_exhaustive]
+pub enum Platform {
+    Pc,
+    Console,
+}
+
+impl Platform {
+    /// The path segment of the platform-scoped endpoints, e.g. `v3/mmr`.
+    pub fn segment(self) -> &'static str {
+        match self {
+            Platform::Pc => "pc",
+            Platform::Console => "console",
+        }
+    }
+}
+
+// Account region, API path segment, Riot shard. Brazil and Latin America play on the North
+// American shard
+const ROUTES: [(AccountRegion, &str, &str); 6] = [
+    (AccountRegion::EU, "eu", "eu"),
+    (AccountRegion::NA, "na", "na"),
+    (AccountRegion::AP, "ap", "ap"),
+    (AccountRegion::KR, "kr", "kr"),
+    (AccountRegion::BR, "br", "na"),
+    (AccountRegion::LATAM, "latam", "na"),
+];
+
+impl AccountRegion {
+    /// Asia-Pacific under the name it had before the other shards were added.
+    #[deprecated(note = "use `AccountRegion::AP`")]
+    pub const AS: AccountRegion = AccountRegion::AP;
+
+    /// The path segment of the API's region-scoped endpoints.
+    pub fn segment(self) -> &'static str {
+        self.route().1
+    }
+
+    /// The Riot shard serving the region, as the raw endpoint and store affinities name it.
+    pub fn shard(self) -> &'static str {
+        self.route().2
+    }
+
+    pub(crate) fn to_value(self) -> String {
+        self.segment().to_string()
+    }
+
+    fn route(self) -> (AccountRegion, &'static str, &'static str) {
+        *ROUTES
+            .iter()
+            .find(|(region, ..)| *region == self)
+            .expect("every region has a route")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::ValorantApiType;
+
+    #[test]
+    fn regions_route_by_endpoint() {
+        assert_eq!(AccountRegion::AP.segment(), "ap");
+        assert_eq!(AccountRegion::LATAM.segment(), "latam");
+        assert_eq!(AccountRegion::BR.shard(), "na");
+        assert_eq!(AccountRegion::EU.shard(), AccountRegion::EU.segment());
+        let region: AccountRegion = serde_json::from_str(r#""as""#).unwrap();
+        assert_eq!(region, AccountRegion::AP);
+        assert_eq!(serde_json::to_string(&region).unwrap(), r#""ap""#);
+        assert_eq!(
+            ValorantApiType::MMRDataV3 {
+                region: AccountRegion::NA,
+                platform: Platform::Console,
+                name: "Anonymous",
+                tag: "0000",
+            }
+            .to_url(),
+            "v3/mmr/na/console/Anonymous/0000"
+        );
+    }
+}
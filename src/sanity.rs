@@ -0,0 +1,185 @@
+//! Invariant checks on decoded payloads, to catch upstream data corruption early.
+//!
+//! Models report broken invariants through [`ValorantAPIData::sanity_warnings`]. The client
+//! runs the checks on every successful response once a hook is set with
+//! [`ValorantClientBuilder::on_suspicious_data`](crate::ValorantClientBuilder::on_suspicious_data);
+//! suspicious payloads are still returned as they are.
+
+use crate::ValorantAPIData;
+use std::fmt;
+use std::sync::Arc;
+
+// Immortal 1; from here on RR isn't capped at 100
+const FIRST_UNCAPPED_TIER: u32 = 24;
+const LOWEST_RANKED_TIER: u32 = 3;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum SanityWarning {
+    /// RR outside of `0..=100` below Immortal.
+    RrOutOfRange {
+        tier: u32,
+        rr: u32,
+    },
+    /// An elo that doesn't follow from the tier and RR.
+    EloMismatch {
+        tier: u32,
+        rr: u32,
+        elo: u32,
+    },
+    RoundsWonExceedPlayed {
+        team: String,
+        won: u32,
+        played: u32,
+    },
+}
+
+impl fmt::Display for SanityWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SanityWarning::RrOutOfRange { tier, rr } => {
+                write!(f, "{rr} RR is out of range for tier {tier}")
+            }
+            SanityWarning::EloMismatch { tier, rr, elo } => {
+                write!(f, "Elo {elo} doesn't match tier {tier} at {rr} RR")
+            }
+            SanityWarning::RoundsWonExceedPlayed { team, won, played } => {
+                write!(f, "{team} won {won} of {played} rounds played")
+            }
+        }
+    }
+}
+
+/// Called with the warnings of a suspicious response, and the URL it was fetched from.
+pub type SanityHook = Arc<dyn Fn(&str, &[SanityWarning]) + Send + Sync>;
+
+/// Checks a rank as the API reports it, skipping unranked tiers. `elo` counts 100 per tier from
+/// Iron 1 upwards, with every Immortal and Radiant tier sharing one uncapped RR pool.
+pub(crate) fn check_rank(tier: u32, rr: u32, elo: Option<u32>, warnings: &mut Vec<SanityWarning>) {
+    if tier < LOWEST_RANKED_TIER {
+        return;
+    }
+    if tier < FIRST_UNCAPPED_TIER && rr > 100 {
+        warnings.push(SanityWarning::RrOutOfRange { tier, rr });
+    }
+    let Some(elo) = elo else {
+        return;
+    };
+    let expected = (tier.min(FIRST_UNCAPPED_TIER) - LOWEST_RANKED_TIER) * 100 + rr;
+    if elo != expected {
+        warnings.push(SanityWarning::EloMismatch { tier, rr, elo });
+    }
+}
+
+pub(crate) fn check_all<T: ValorantAPIData>(items: &[T]) -> Vec<SanityWarning> {
+    items.iter().flat_map(T::sanity_warnings).collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::fixtures::{decode, MATCH_HISTORY, MMR, MMR_HISTORY};
+    use crate::match_data::MatchData;
+    use crate::mmr_data::MMRData;
+    use crate::mmr_history::MMRHistoryEntry;
+    #[cfg(feature = "client")]
+    use crate::mock_server::{MockResponse, MockServer};
+    #[cfg(feature = "client")]
+    use crate::{AccountRegion, ValorantApiType, ValorantClient};
+    #[cfg(feature = "client")]
+    use std::sync::Mutex;
+
+    #[test]
+    fn fixtures_are_sane() {
+        assert_eq!(decode::<MMRData>(MMR).sanity_warnings(), []);
+        assert_eq!(
+            decode::<Vec<MMRHistoryEntry>>(MMR_HISTORY).sanity_warnings(),
+            []
+        );
+        assert_eq!(
+            decode::<Vec<MatchData>>(MATCH_HISTORY).sanity_warnings(),
+            []
+        );
+    }
+
+    #[test]
+    fn ranks_are_checked() {
+        let mut warnings = Vec::new();
+        check_rank(0, 0, Some(0), &mut warnings);
+        check_rank(25, 340, Some(2440), &mut warnings);
+        assert_eq!(warnings, []);
+
+        check_rank(16, 140, None, &mut warnings);
+        check_rank(16, 47, Some(1500), &mut warnings);
+        assert_eq!(
+            warnings,
+            [
+                SanityWarning::RrOutOfRange { tier: 16, rr: 140 },
+                SanityWarning::EloMismatch {
+                    tier: 16,
+                    rr: 47,
+                    elo: 1500
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn rounds_are_checked() {
+        let corrupted = MATCH_HISTORY.replacen(r#""rounds_won": 13"#, r#""rounds_won": 40"#, 1);
+        let warnings = decode::<Vec<MatchData>>(&corrupted).sanity_warnings();
+        assert!(matches!(
+            &warnings[..],
+            [SanityWarning::RoundsWonExceedPlayed { won: 40, .. }]
+        ));
+    }
+
+    #[cfg(feature = "client")]
+    #[tokio::test]
+    async fn the_client_reports_suspicious_responses() {
+        let corrupted = MMR.replace(r#""ranking_in_tier": 47"#, r#""ranking_in_tier": 147"#);
+        let server = MockServer::start(vec![MockResponse::json(200, &corrupted)]).await;
+        let url = server.url();
+        let reported = Arc::new(Mutex::new(Vec::new()));
+        let client = ValorantClient::builder()
+            .api_endpoint(&url)
+            .on_suspicious_data({
+                let reported = reported.clone();
+                move |url, warnings| {
+                    reported
+                        .lock()
+                        .unwrap()
+                        .push((url.to_string(), warnings.to_vec()))
+                }
+            })
+            .build()
+            .unwrap();
+
+        // The payload is still returned
+        client
+            .request::<MMRData>(ValorantApiType::MMRData {
+                region: AccountRegion::EU,
+                name: "Anonymous",
+                tag: "0000",
+                filter: None,
+            })
+            .await
+            .unwrap();
+        let reported = reported.lock().unwrap();
+        let [(url, warnings)] = &reported[..] else {
+            panic!("expected one report, got {reported:?}");
+        };
+        assert!(url.ends_with("v2/mmr/eu/Anonymous/0000"));
+        assert_eq!(
+            warnings,
+            &[
+                SanityWarning::RrOutOfRange { tier: 16, rr: 147 },
+                SanityWarning::EloMismatch {
+                    tier: 16,
+                    rr: 147,
+                    elo: 1347
+                },
+            ]
+        );
+    }
+}
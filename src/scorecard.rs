@@ -0,0 +1,455 @@
+//! Post-match scorecards rendered as PNG images, for bots that attach a match summary.
+//!
+//! Everything is drawn in-crate: a built-in 5x7 pixel font, flat colours and an uncompressed
+//! PNG encoder, so no image or font dependency and no headless browser is needed. Rank icons
+//! are fetched images and aren't drawn; each player's rank is shown as a swatch in the rank's
+//! colour instead, the same colours the act rank triangle uses.
+
+use crate::act_rank::colour;
+use crate::match_data::MatchData;
+
+const WIDTH: usize = 640;
+const HEADER: usize = 64;
+const ROW: usize = 36;
+const SCALE: usize = 2;
+// A glyph is 5 pixels wide, plus one pixel of spacing
+const ADVANCE: usize = 6 * SCALE;
+
+const BACKGROUND: Rgb = [0x0f, 0x19, 0x23];
+const TEXT: Rgb = [0xec, 0xe8, 0xe1];
+const MUTED: Rgb = [0x8b, 0x97, 0x8f];
+const RED: Rgb = [0xff, 0x46, 0x55];
+const BLUE: Rgb = [0x3a, 0xa0, 0xa8];
+
+type Rgb = [u8; 3];
+
+/// One player's line on a scorecard.
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct ScorecardRow {
+    pub name: String,
+    pub tag: String,
+    pub team: String,
+    pub agent: String,
+    pub tier: u32,
+    pub kills: u32,
+    pub deaths: u32,
+    pub assists: u32,
+    /// The average combat score per round.
+    pub acs: u32,
+}
+
+/// The summary of a match a scorecard shows, players ordered by team and then by ACS.
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct Scorecard {
+    pub map: String,
+    pub mode: String,
+    /// Rounds won by Red and Blue, `None` for modes without teams.
+    pub score: Option<(u32, u32)>,
+    pub rows: Vec<ScorecardRow>,
+}
+
+impl Scorecard {
+    pub fn from_match(data: &MatchData) -> Self {
+        let rounds = data.rounds_played().max(1);
+        let mut rows: Vec<ScorecardRow> = data
+            .players()
+            .iter()
+            .map(|player| {
+                let stats = player.stats();
+                ScorecardRow {
+                    name: player.name().to_string(),
+                    tag: player.tag().to_string(),
+                    team: player.team().to_string(),
+                    agent: player.agent().to_string(),
+                    tier: player.current_tier(),
+                    kills: stats.kills(),
+                    deaths: stats.deaths(),
+                    assists: stats.assists(),
+                    acs: stats.score() / rounds,
+                }
+            })
+            .collect();
+        rows.sort_by(|a, b| {
+            let side = |row: &ScorecardRow| !row.team.eq_ignore_ascii_case("red");
+            side(a).cmp(&side(b)).then(b.acs.cmp(&a.acs))
+        });
+        let score = data
+            .players()
+            .iter()
+            .find(|player| player.team().eq_ignore_ascii_case("red"))
+            .filter(|_| !data.mode().is_free_for_all())
+            .and_then(|player| data.result_for(player.puuid()))
+            .map(|outcome| (outcome.rounds_for, outcome.rounds_against));
+        Scorecard {
+            map: data.map().to_string(),
+            mode: data.mode().id().to_string(),
+            score,
+            rows,
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        WIDTH
+    }
+
+    pub fn height(&self) -> usize {
+        HEADER + self.rows.len() * ROW
+    }
+
+    /// Renders the scorecard as a PNG image.
+    pub fn to_png(&self) -> Vec<u8> {
+        let mut canvas = Canvas::new(self.width(), self.height());
+        canvas.text(16, 12, &self.map, TEXT);
+        canvas.text(16, 36, &self.mode, MUTED);
+        if let Some((red, blue)) = self.score {
+            let red = red.to_string();
+            let x = WIDTH - 16 - (red.len() + 3 + blue.to_string().len()) * ADVANCE;
+            canvas.text(x, 20, &red, RED);
+            canvas.text(x + (red.len() + 1) * ADVANCE, 20, "-", MUTED);
+            canvas.text(x + (red.len() + 3) * ADVANCE, 20, &blue.to_string(), BLUE);
+        }
+
+        for (index, row) in self.rows.iter().enumerate() {
+            let top = HEADER + index * ROW;
+            let team = if row.team.eq_ignore_ascii_case("red") {
+                RED
+            } else {
+                BLUE
+            };
+            canvas.rect(0, top + 2, 6, ROW - 4, team);
+            canvas.rect(16, top + 8, 20, 20, hex(colour(row.tier)));
+            let name = format!("{}#{}", row.name, row.tag);
+            canvas.text(48, top + 11, &clip(&name, 22), TEXT);
+            canvas.text(328, top + 11, &clip(&row.agent, 9), MUTED);
+            let kda = format!("{}/{}/{}", row.kills, row.deaths, row.assists);
+            canvas.text(448, top + 11, &kda, TEXT);
+            canvas.text(
+                WIDTH - 16 - 3 * ADVANCE,
+                top + 11,
+                &row.acs.to_string(),
+                TEXT,
+            );
+        }
+        canvas.png()
+    }
+}
+
+fn clip(value: &str, characters: usize) -> String {
+    value.chars().take(characters).collect()
+}
+
+fn hex(colour: &str) -> Rgb {
+    let channel = |index: usize| u8::from_str_radix(&colour[index..index + 2], 16).unwrap_or(0);
+    [channel(1), channel(3), channel(5)]
+}
+
+struct Canvas {
+    width: usize,
+    height: usize,
+    pixels: Vec<u8>,
+}
+
+impl Canvas {
+    fn new(width: usize, height: usize) -> Self {
+        Canvas {
+            width,
+            height,
+            pixels: BACKGROUND.repeat(width * height),
+        }
+    }
+
+    fn rect(&mut self, x: usize, y: usize, width: usize, height: usize, colour: Rgb) {
+        for row in y..(y + height).min(self.height) {
+            for column in x..(x + width).min(self.width) {
+                let offset = (row * self.width + column) * 3;
+                self.pixels[offset..offset + 3].copy_from_slice(&colour);
+            }
+        }
+    }
+
+    fn text(&mut self, x: usize, y: usize, text: &str, colour: Rgb) {
+        for (index, character) in text.chars().enumerate() {
+            let left = x + index * ADVANCE;
+            for (row, bits) in glyph(character).iter().enumerate() {
+                for column in 0..5 {
+                    if bits & (0b10000 >> column) != 0 {
+                        let (px, py) = (left + column * SCALE, y + row * SCALE);
+                        self.rect(px, py, SCALE, SCALE, colour);
+                    }
+                }
+            }
+        }
+    }
+
+    fn png(&self) -> Vec<u8> {
+        let mut raw = Vec::with_capacity(self.height * (self.width * 3 + 1));
+        for row in self.pixels.chunks(self.width * 3) {
+            // Filter type 0, the row as it is
+            raw.push(0);
+            raw.extend_from_slice(row);
+        }
+
+        let mut header = Vec::with_capacity(13);
+        header.extend_from_slice(&(self.width as u32).to_be_bytes());
+        header.extend_from_slice(&(self.height as u32).to_be_bytes());
+        // 8 bits per channel, RGB, deflate, adaptive filtering, no interlacing
+        header.extend_from_slice(&[8, 2, 0, 0, 0]);
+
+        let mut png = b"\x89PNG\r\n\x1a\n".to_vec();
+        chunk(&mut png, b"IHDR", &header);
+        chunk(&mut png, b"IDAT", &zlib_stored(&raw));
+        chunk(&mut png, b"IEND", &[]);
+        png
+    }
+}
+
+fn chunk(png: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    png.extend_from_slice(&(data.len() as u32).to_be_bytes());
+    let start = png.len();
+    png.extend_from_slice(kind);
+    png.extend_from_slice(data);
+    let crc = crc32(&png[start..]);
+    png.extend_from_slice(&crc.to_be_bytes());
+}
+
+// A zlib stream of uncompressed deflate blocks, which every PNG decoder reads
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    let mut stream = vec![0x78, 0x01];
+    let blocks: Vec<&[u8]> = data.chunks(u16::MAX as usize).collect();
+    for (index, block) in blocks.iter().enumerate() {
+        stream.push(u8::from(index + 1 == blocks.len()));
+        let len = block.len() as u16;
+        stream.extend_from_slice(&len.to_le_bytes());
+        stream.extend_from_slice(&(!len).to_le_bytes());
+        stream.extend_from_slice(block);
+    }
+    if blocks.is_empty() {
+        stream.extend_from_slice(&[1, 0, 0, 0xff, 0xff]);
+    }
+    stream.extend_from_slice(&adler32(data).to_be_bytes());
+    stream
+}
+
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for &byte in data {
+        crc ^= u32::from(byte);
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ 0xedb8_8320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    !crc
+}
+
+fn adler32(data: &[u8]) -> u32 {
+    let (mut a, mut b) = (1u32, 0u32);
+    for &byte in data {
+        a = (a + u32::from(byte)) % 65521;
+        b = (b + a) % 65521;
+    }
+    b << 16 | a
+}
+
+// Rows of a 5x7 glyph, the highest of the five bits being the leftmost pixel. Letters are
+// drawn in uppercase.
+fn glyph(character: char) -> [u8; 7] {
+    match character.to_ascii_uppercase() {
+        'A' => [
+            0b01110, 0b10001, 0b10001, 0b11111, 0b10001, 0b10001, 0b10001,
+        ],
+        'B' => [
+            0b11110, 0b10001, 0b10001, 0b11110, 0b10001, 0b10001, 0b11110,
+        ],
+        'C' => [
+            0b01110, 0b10001, 0b10000, 0b10000, 0b10000, 0b10001, 0b01110,
+        ],
+        'D' => [
+            0b11100, 0b10010, 0b10001, 0b10001, 0b10001, 0b10010, 0b11100,
+        ],
+        'E' => [
+            0b11111, 0b10000, 0b10000, 0b11110, 0b10000, 0b10000, 0b11111,
+        ],
+        'F' => [
+            0b11111, 0b10000, 0b10000, 0b11110, 0b10000, 0b10000, 0b10000,
+        ],
+        'G' => [
+            0b01110, 0b10001, 0b10000, 0b10111, 0b10001, 0b10001, 0b01111,
+        ],
+        'H' => [
+            0b10001, 0b10001, 0b10001, 0b11111, 0b10001, 0b10001, 0b10001,
+        ],
+        'I' => [
+            0b01110, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100, 0b01110,
+        ],
+        'J' => [
+            0b00111, 0b00010, 0b00010, 0b00010, 0b00010, 0b10010, 0b01100,
+        ],
+        'K' => [
+            0b10001, 0b10010, 0b10100, 0b11000, 0b10100, 0b10010, 0b10001,
+        ],
+        'L' => [
+            0b10000, 0b10000, 0b10000, 0b10000, 0b10000, 0b10000, 0b11111,
+        ],
+        'M' => [
+            0b10001, 0b11011, 0b10101, 0b10101, 0b10001, 0b10001, 0b10001,
+        ],
+        'N' => [
+            0b10001, 0b10001, 0b11001, 0b10101, 0b10011, 0b10001, 0b10001,
+        ],
+        'O' => [
+            0b01110, 0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b01110,
+        ],
+        'P' => [
+            0b11110, 0b10001, 0b10001, 0b11110, 0b10000, 0b10000, 0b10000,
+        ],
+        'Q' => [
+            0b01110, 0b10001, 0b10001, 0b10001, 0b10101, 0b10010, 0b01101,
+        ],
+        'R' => [
+            0b11110, 0b10001, 0b10001, 0b11110, 0b10100, 0b10010, 0b10001,
+        ],
+        'S' => [
+            0b01111, 0b10000, 0b10000, 0b01110, 0b00001, 0b00001, 0b11110,
+        ],
+        'T' => [
+            0b11111, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100,
+        ],
+        'U' => [
+            0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b01110,
+        ],
+        'V' => [
+            0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b01010, 0b00100,
+        ],
+        'W' => [
+            0b10001, 0b10001, 0b10001, 0b10101, 0b10101, 0b10101, 0b01010,
+        ],
+        'X' => [
+            0b10001, 0b10001, 0b01010, 0b00100, 0b01010, 0b10001, 0b10001,
+        ],
+        'Y' => [
+            0b10001, 0b10001, 0b10001, 0b01010, 0b00100, 0b00100, 0b00100,
+        ],
+        'Z' => [
+            0b11111, 0b00001, 0b00010, 0b00100, 0b01000, 0b10000, 0b11111,
+        ],
+        '0' => [
+            0b01110, 0b10001, 0b10011, 0b10101, 0b11001, 0b10001, 0b01110,
+        ],
+        '1' => [
+            0b00100, 0b01100, 0b00100, 0b00100, 0b00100, 0b00100, 0b01110,
+        ],
+        '2' => [
+            0b01110, 0b10001, 0b00001, 0b00010, 0b00100, 0b01000, 0b11111,
+        ],
+        '3' => [
+            0b11111, 0b00010, 0b00100, 0b00010, 0b00001, 0b10001, 0b01110,
+        ],
+        '4' => [
+            0b00010, 0b00110, 0b01010, 0b10010, 0b11111, 0b00010, 0b00010,
+        ],
+        '5' => [
+            0b11111, 0b10000, 0b11110, 0b00001, 0b00001, 0b10001, 0b01110,
+        ],
+        '6' => [
+            0b00110, 0b01000, 0b10000, 0b11110, 0b10001, 0b10001, 0b01110,
+        ],
+        '7' => [
+            0b11111, 0b00001, 0b00010, 0b00100, 0b01000, 0b01000, 0b01000,
+        ],
+        '8' => [
+            0b01110, 0b10001, 0b10001, 0b01110, 0b10001, 0b10001, 0b01110,
+        ],
+        '9' => [
+            0b01110, 0b10001, 0b10001, 0b01111, 0b00001, 0b00010, 0b01100,
+        ],
+        '#' => [
+            0b01010, 0b01010, 0b11111, 0b01010, 0b11111, 0b01010, 0b01010,
+        ],
+        '/' => [
+            0b00000, 0b00001, 0b00010, 0b00100, 0b01000, 0b10000, 0b00000,
+        ],
+        '-' => [
+            0b00000, 0b00000, 0b00000, 0b11111, 0b00000, 0b00000, 0b00000,
+        ],
+        '.' => [
+            0b00000, 0b00000, 0b00000, 0b00000, 0b00000, 0b01100, 0b01100,
+        ],
+        ' ' => [0; 7],
+        // Everything the font doesn't cover, including letters outside ASCII
+        _ => [
+            0b01110, 0b10001, 0b00001, 0b00010, 0b00100, 0b00000, 0b00100,
+        ],
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::fixtures::{decode, MATCH_HISTORY};
+
+    fn scorecard() -> Scorecard {
+        let data: Vec<MatchData> = decode(MATCH_HISTORY);
+        Scorecard::from_match(&data[0])
+    }
+
+    // Reads the stored blocks back, which only works for the streams written above
+    fn inflate_stored(stream: &[u8]) -> Vec<u8> {
+        let mut data = Vec::new();
+        let mut position = 2;
+        loop {
+            let last = stream[position] & 1 == 1;
+            let len = u16::from_le_bytes([stream[position + 1], stream[position + 2]]) as usize;
+            data.extend_from_slice(&stream[position + 5..position + 5 + len]);
+            position += 5 + len;
+            if last {
+                break;
+            }
+        }
+        assert_eq!(stream[position..], adler32(&data).to_be_bytes());
+        data
+    }
+
+    #[test]
+    fn summarizes_the_match() {
+        let card = scorecard();
+        assert_eq!(card.map, "Ascent");
+        assert_eq!(card.score, Some((13, 10)));
+        assert_eq!(card.rows.len(), 10);
+        assert_eq!(card.rows[0].name, "Teammate");
+        // 5850 combat score over 23 rounds
+        assert_eq!(card.rows[0].acs, 254);
+        assert!(card.rows[5..].iter().all(|row| row.team == "Blue"));
+    }
+
+    #[test]
+    fn renders_a_valid_png() {
+        let card = scorecard();
+        let png = card.to_png();
+        assert_eq!(png[..8], *b"\x89PNG\r\n\x1a\n");
+        assert_eq!(png[12..16], *b"IHDR");
+        assert_eq!(u32::from_be_bytes(png[16..20].try_into().unwrap()), 640);
+        assert_eq!(u32::from_be_bytes(png[20..24].try_into().unwrap()), 424);
+        assert_eq!(crc32(&png[12..29]).to_be_bytes(), png[29..33]);
+        assert!(png.ends_with(&[0, 0, 0, 0, b'I', b'E', b'N', b'D', 0xae, 0x42, 0x60, 0x82]));
+
+        let len = u32::from_be_bytes(png[33..37].try_into().unwrap()) as usize;
+        assert_eq!(png[37..41], *b"IDAT");
+        let raw = inflate_stored(&png[41..41 + len]);
+        assert_eq!(raw.len(), 424 * (1 + 640 * 3));
+        // The first player's row starts with the red team's stripe
+        let offset = (HEADER + 10) * (1 + 640 * 3) + 1;
+        assert_eq!(raw[offset..offset + 3], RED);
+    }
+
+    #[test]
+    fn checksums_match_reference_values() {
+        assert_eq!(crc32(b"123456789"), 0xcbf4_3926);
+        assert_eq!(adler32(b"Wikipedia"), 0x11e6_0398);
+    }
+}
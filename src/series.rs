@@ -0,0 +1,413 @@
+//! Custom matches played as a series, like a scrim block or a best of three, summarized for
+//! coaches.
+//!
+//! Teams are told apart by their players rather than their colour, since a team can play as
+//! Red on one map and Blue on the next. The first map decides which team is which, and every
+//! later map gives each colour to the team most of its players belong to.
+
+use crate::ids::MatchId;
+use crate::match_data::{MatchData, MatchPlayer};
+#[cfg(feature = "client")]
+use crate::{ValorantApiType, ValorantClient};
+use serde::Serialize;
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SideRecord {
+    pub played: u32,
+    pub won: u32,
+}
+
+impl SideRecord {
+    pub fn win_rate(&self) -> f64 {
+        match self.played {
+            0 => 0.0,
+            played => f64::from(self.won) / f64::from(played),
+        }
+    }
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct SeriesTeam {
+    /// The registered team name when the first map has one, otherwise its colour on the first
+    /// map.
+    pub name: String,
+    /// The PUUIDs of everyone who played for the team, substitutes included.
+    pub players: Vec<String>,
+    pub maps_won: u32,
+    pub attack: SideRecord,
+    pub defense: SideRecord,
+}
+
+impl SeriesTeam {
+    pub fn rounds_won(&self) -> u32 {
+        self.attack.won + self.defense.won
+    }
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct SeriesMap {
+    pub match_id: MatchId,
+    pub map: String,
+    /// The rounds each team won, in the order of [`SeriesReport::teams`].
+    pub score: [u32; 2],
+    /// The index of the winning team, `None` for a draw.
+    pub winner: Option<usize>,
+    pub forfeit: bool,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct SeriesPlayer {
+    pub puuid: String,
+    pub name: String,
+    pub tag: String,
+    /// The index of the player's team in [`SeriesReport::teams`].
+    pub team: usize,
+    pub maps: u32,
+    pub rounds: u32,
+    pub score: u32,
+    pub kills: u32,
+    pub deaths: u32,
+    pub assists: u32,
+    pub headshots: u32,
+    pub bodyshots: u32,
+    pub legshots: u32,
+    /// Every agent played, in the order they were first picked.
+    pub agents: Vec<String>,
+}
+
+impl SeriesPlayer {
+    /// Kills per death, with no deaths counting as one.
+    pub fn kd(&self) -> f64 {
+        f64::from(self.kills) / f64::from(self.deaths.max(1))
+    }
+
+    /// The average combat score over every round of the series.
+    pub fn acs(&self) -> f64 {
+        match self.rounds {
+            0 => 0.0,
+            rounds => f64::from(self.score) / f64::from(rounds),
+        }
+    }
+
+    pub fn headshot_rate(&self) -> f64 {
+        match self.headshots + self.bodyshots + self.legshots {
+            0 => 0.0,
+            shots => f64::from(self.headshots) / f64::from(shots),
+        }
+    }
+
+    fn new(player: &MatchPlayer, team: usize) -> Self {
+        SeriesPlayer {
+            puuid: player.puuid().to_ascii_lowercase(),
+            name: player.name().to_string(),
+            tag: player.tag().to_string(),
+            team,
+            maps: 0,
+            rounds: 0,
+            score: 0,
+            kills: 0,
+            deaths: 0,
+            assists: 0,
+            headshots: 0,
+            bodyshots: 0,
+            legshots: 0,
+            agents: Vec::new(),
+        }
+    }
+
+    fn add(&mut self, player: &MatchPlayer, rounds: u32) {
+        let stats = player.stats();
+        self.maps += 1;
+        self.rounds += rounds;
+        self.score += stats.score();
+        self.kills += stats.kills();
+        self.deaths += stats.deaths();
+        self.assists += stats.assists();
+        self.headshots += stats.headshots();
+        self.bodyshots += stats.bodyshots();
+        self.legshots += stats.legshots();
+        if !self.agents.iter().any(|agent| agent == player.agent()) {
+            self.agents.push(player.agent().to_string());
+        }
+    }
+}
+
+/// Maps played, per-player stats over the whole series and each team's attack and defense
+/// rounds.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct SeriesReport {
+    pub teams: [SeriesTeam; 2],
+    /// In the order the match IDs were given.
+    pub maps: Vec<SeriesMap>,
+    /// Ordered by team, then by the average combat score, best first.
+    pub players: Vec<SeriesPlayer>,
+    /// Matches that couldn't be fetched, or were played without teams like deathmatch.
+    pub missing: Vec<MatchId>,
+}
+
+impl SeriesReport {
+    /// Summarizes the matches in the given order. Matches without two teams are listed as
+    /// missing.
+    pub fn from_matches<'a>(matches: impl IntoIterator<Item = &'a MatchData>) -> Self {
+        let team = |name: &str| SeriesTeam {
+            name: name.to_string(),
+            players: Vec::new(),
+            maps_won: 0,
+            attack: SideRecord::default(),
+            defense: SideRecord::default(),
+        };
+        let mut report = SeriesReport {
+            teams: [team("Red"), team("Blue")],
+            maps: Vec::new(),
+            players: Vec::new(),
+            missing: Vec::new(),
+        };
+        for data in matches {
+            if data.attacking_team(0).is_none() || data.winning_team().is_none() && !is_draw(data) {
+                report.missing.push(data.match_id().clone());
+                continue;
+            }
+            report.add(data);
+        }
+        report.players.sort_by(|a, b| {
+            a.team
+                .cmp(&b.team)
+                .then_with(|| b.acs().total_cmp(&a.acs()))
+        });
+        report
+    }
+
+    /// The team that won more maps, `None` while it's even.
+    pub fn winner(&self) -> Option<&SeriesTeam> {
+        let [first, second] = &self.teams;
+        match first.maps_won.cmp(&second.maps_won) {
+            std::cmp::Ordering::Greater => Some(first),
+            std::cmp::Ordering::Less => Some(second),
+            std::cmp::Ordering::Equal => None,
+        }
+    }
+
+    fn add(&mut self, data: &MatchData) {
+        let first_map = self.maps.is_empty();
+        if first_map {
+            for (index, colour) in ["Red", "Blue"].into_iter().enumerate() {
+                if let Some(roster) = data.roster(colour) {
+                    self.teams[index].name = roster.name().to_string();
+                }
+            }
+        }
+        // The index of the team playing as Red on this map
+        let red = if first_map || self.overlap(data, "Red", 0) >= self.overlap(data, "Red", 1) {
+            0
+        } else {
+            1
+        };
+        let team_of = |colour: &str| {
+            if colour.eq_ignore_ascii_case("red") {
+                red
+            } else {
+                1 - red
+            }
+        };
+
+        let rounds = data.rounds().len() as u32;
+        for player in data.players() {
+            let team = team_of(player.team());
+            let puuid = player.puuid().to_ascii_lowercase();
+            if !self.teams[team].players.contains(&puuid) {
+                self.teams[team].players.push(puuid.clone());
+            }
+            let position = match self.players.iter().position(|known| known.puuid == puuid) {
+                Some(position) => position,
+                None => {
+                    self.players.push(SeriesPlayer::new(player, team));
+                    self.players.len() - 1
+                }
+            };
+            self.players[position].add(player, rounds);
+        }
+
+        let mut score = [0; 2];
+        for (index, round) in data.rounds().iter().enumerate() {
+            let (Some(attackers), winner) = (data.attacking_team(index), round.winning_team())
+            else {
+                continue;
+            };
+            let attacking = team_of(attackers);
+            let won = team_of(winner);
+            score[won] += 1;
+            for team in [attacking, 1 - attacking] {
+                let side = if team == attacking {
+                    &mut self.teams[team].attack
+                } else {
+                    &mut self.teams[team].defense
+                };
+                side.played += 1;
+                side.won += u32::from(team == won);
+            }
+        }
+
+        let winner = data.winning_team().map(team_of);
+        if let Some(winner) = winner {
+            self.teams[winner].maps_won += 1;
+        }
+        self.maps.push(SeriesMap {
+            match_id: data.match_id().clone(),
+            map: data.map().to_string(),
+            score,
+            winner,
+            forfeit: data.is_forfeit(),
+        });
+    }
+
+    // How many of the players on `colour` already played for the team at `team`
+    fn overlap(&self, data: &MatchData, colour: &str, team: usize) -> usize {
+        data.players()
+            .iter()
+            .filter(|player| player.team().eq_ignore_ascii_case(colour))
+            .filter(|player| {
+                self.teams[team]
+                    .players
+                    .contains(&player.puuid().to_ascii_lowercase())
+            })
+            .count()
+    }
+}
+
+fn is_draw(data: &MatchData) -> bool {
+    data.players()
+        .first()
+        .and_then(|player| data.team_result(player))
+        .is_some_and(|result| result == crate::match_data::MatchResult::Draw)
+}
+
+#[cfg(feature = "client")]
+impl ValorantClient<'_> {
+    /// Fetches the matches and summarizes them as one series, in the given order.
+    pub async fn series_report(&self, match_ids: &[MatchId]) -> SeriesReport {
+        // A repeated ID is the same map, which only counts once
+        let mut unique: Vec<&MatchId> = Vec::new();
+        for id in match_ids {
+            if !unique.contains(&id) {
+                unique.push(id);
+            }
+        }
+        let requests = unique
+            .iter()
+            .map(|id| ValorantApiType::Match { id: id.as_str() })
+            .collect();
+        let results = self.request_many::<MatchData>(requests).await;
+        let mut report = SeriesReport::from_matches(results.succeeded.iter().map(|(_, data)| data));
+        let unfetched = results.failed.iter().map(|(index, _)| *index);
+        let invalid = results.skipped.iter().map(|(index, _)| *index);
+        let mut missing: Vec<usize> = unfetched.chain(invalid).collect();
+        missing.sort_unstable();
+        report
+            .missing
+            .extend(missing.into_iter().map(|index| unique[index].clone()));
+        report
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::fake::FakeMatch;
+    #[cfg(feature = "client")]
+    use crate::fixtures::NOT_FOUND;
+    #[cfg(feature = "client")]
+    use crate::mock_server::{MockResponse, MockServer};
+
+    #[test]
+    fn teams_are_followed_across_colours() {
+        let matches = [
+            FakeMatch::custom().build(),
+            FakeMatch::custom()
+                .id("1a2b3c4d-5e6f-4a7b-8c9d-0e1f2a3b4c5d".parse().unwrap())
+                .swap_colours()
+                .build(),
+        ];
+        let report = SeriesReport::from_matches(&matches);
+
+        let [nerds, quitters] = &report.teams;
+        assert_eq!(nerds.name, "Nerd Herd");
+        assert_eq!(quitters.name, "Quitters");
+        assert_eq!(nerds.maps_won, 2);
+        assert_eq!(
+            report.winner().map(|team| team.name.as_str()),
+            Some("Nerd Herd")
+        );
+        assert_eq!(report.maps[1].score, [11, 9]);
+        assert_eq!(report.maps[1].winner, Some(0));
+        assert!(report.maps[0].forfeit);
+
+        // Six of twelve rounds in the first half and five of eight in the second, on either side
+        assert_eq!(
+            nerds.attack,
+            SideRecord {
+                played: 20,
+                won: 11
+            }
+        );
+        assert_eq!(
+            nerds.defense,
+            SideRecord {
+                played: 20,
+                won: 11
+            }
+        );
+        assert_eq!(nerds.rounds_won() + quitters.rounds_won(), 40);
+
+        let anonymous = report
+            .players
+            .iter()
+            .find(|player| player.name == "Anonymous")
+            .unwrap();
+        assert_eq!(anonymous.team, 0);
+        assert_eq!(anonymous.maps, 2);
+        assert_eq!(anonymous.kills, 38);
+        assert_eq!(anonymous.agents, ["Jett"]);
+        assert_eq!(anonymous.acs(), 4920.0 / 20.0);
+        assert_eq!(report.players[0].name, "Teammate");
+        assert_eq!(report.players[5].team, 1);
+    }
+
+    #[cfg(feature = "client")]
+    #[tokio::test]
+    async fn unfetched_and_teamless_matches_are_missing() {
+        let server = MockServer::routes(vec![
+            (
+                "/v2/match/9b8a7c6d-5e4f-4a3b-8c2d-1e0f9a8b7c6d",
+                MockResponse::json(200, &FakeMatch::custom().response()),
+            ),
+            (
+                "/v2/match/4f2d1c0b-9a8e-4d7c-b6a5-3e2f1d0c9b8a",
+                MockResponse::json(200, &FakeMatch::deathmatch().response()),
+            ),
+            (
+                "/v2/match/00000000-0000-4000-8000-000000000000",
+                MockResponse::json(404, NOT_FOUND),
+            ),
+        ])
+        .await;
+        let url = server.url();
+        let client = ValorantClient::new().change_api_endpoint(&url);
+        let ids: Vec<MatchId> = [
+            "9b8a7c6d-5e4f-4a3b-8c2d-1e0f9a8b7c6d",
+            "4f2d1c0b-9a8e-4d7c-b6a5-3e2f1d0c9b8a",
+            "00000000-0000-4000-8000-000000000000",
+            "9b8a7c6d-5e4f-4a3b-8c2d-1e0f9a8b7c6d",
+        ]
+        .into_iter()
+        .map(|id| id.parse().unwrap())
+        .collect();
+
+        let report = client.series_report(&ids).await;
+        assert_eq!(report.maps.len(), 1);
+        assert_eq!(report.missing, [ids[1].clone(), ids[2].clone()]);
+    }
+}
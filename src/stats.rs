@@ -0,0 +1,196 @@
+//! Rolling latency and error statistics per endpoint, kept by the client itself.
+//!
+//! Every request the client sends is recorded under its endpoint, e.g. `v2/mmr` or
+//! `v1/by-puuid/mmr-history`, whatever player or region it was about. Only the most recent
+//! [`WINDOW`] requests of each endpoint count, so the numbers follow degradation as it happens.
+
+use crate::ValorantClient;
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// How many of the latest requests per endpoint the statistics cover.
+pub const WINDOW: usize = 500;
+
+#[derive(Debug, Clone, Copy)]
+struct Sample {
+    latency: Duration,
+    failed: bool,
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct StatsRecorder {
+    samples: Mutex<HashMap<String, VecDeque<Sample>>>,
+}
+
+impl StatsRecorder {
+    fn record(&self, endpoint: String, sample: Sample) {
+        let mut samples = self.samples.lock().unwrap();
+        let window = samples.entry(endpoint).or_default();
+        if window.len() == WINDOW {
+            window.pop_front();
+        }
+        window.push_back(sample);
+    }
+}
+
+/// The statistics of one endpoint over its latest requests.
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct EndpointStats {
+    pub endpoint: String,
+    pub count: usize,
+    /// Requests that failed in transport or were answered with a 5xx or a 429. Client errors
+    /// like an unknown player are answers, not degradation.
+    pub errors: usize,
+    pub p50: Duration,
+    pub p95: Duration,
+}
+
+impl EndpointStats {
+    pub fn error_rate(&self) -> f64 {
+        match self.count {
+            0 => 0.0,
+            count => self.errors as f64 / count as f64,
+        }
+    }
+
+    fn from_samples(endpoint: &str, samples: &VecDeque<Sample>) -> Self {
+        let mut latencies: Vec<Duration> = samples.iter().map(|sample| sample.latency).collect();
+        latencies.sort_unstable();
+        // Nearest rank, so a percentile is always a latency that was actually observed
+        let percentile = |share: f64| {
+            let rank = (share * latencies.len() as f64).ceil() as usize;
+            latencies
+                .get(rank.saturating_sub(1))
+                .copied()
+                .unwrap_or_default()
+        };
+        EndpointStats {
+            endpoint: endpoint.to_string(),
+            count: samples.len(),
+            errors: samples.iter().filter(|sample| sample.failed).count(),
+            p50: percentile(0.5),
+            p95: percentile(0.95),
+        }
+    }
+}
+
+// `v1/by-puuid/...` routes keep their third segment, all others are named by the first two
+fn endpoint_of(path: &str) -> String {
+    let path = path.split('?').next().unwrap_or(path);
+    let segments = match path.split('/').nth(1) {
+        Some("by-puuid") => 3,
+        _ => 2,
+    };
+    path.split('/').take(segments).collect::<Vec<_>>().join("/")
+}
+
+impl ValorantClient<'_> {
+    /// The statistics of every endpoint the client has sent requests to, ordered by endpoint.
+    pub fn stats(&self) -> Vec<EndpointStats> {
+        let samples = self.stats.samples.lock().unwrap();
+        let mut stats: Vec<_> = samples
+            .iter()
+            .map(|(endpoint, samples)| EndpointStats::from_samples(endpoint, samples))
+            .collect();
+        stats.sort_by(|a, b| a.endpoint.cmp(&b.endpoint));
+        stats
+    }
+
+    // Sends a request to `path` with the trace context, recording how it went
+    pub(crate) async fn send_timed(
+        &self,
+        path: &str,
+        request: reqwest::RequestBuilder,
+    ) -> Result<reqwest::Response, reqwest::Error> {
+        let mut request = request.build()?;
+        self.telemetry.inject(&mut request);
+        if let Some(signer) = &self.request_signer {
+            signer.sign(&mut request);
+        }
+        let (method, url) = (request.method().clone(), request.url().clone());
+        let endpoint = endpoint_of(path);
+        let started = Instant::now();
+        #[cfg(any(test, feature = "test-support"))]
+        let response = self.execute_with_faults(&endpoint, request).await;
+        #[cfg(not(any(test, feature = "test-support")))]
+        let response = self.http.execute(request).await;
+        let elapsed = started.elapsed();
+        self.telemetry
+            .record(&endpoint, &method, &url, &response, elapsed);
+        let failed = match &response {
+            Ok(response) => {
+                self.key_limits.record(response.headers());
+                response.status().is_server_error()
+                    || response.status() == reqwest::StatusCode::TOO_MANY_REQUESTS
+            }
+            Err(_) => true,
+        };
+        let sample = Sample {
+            latency: elapsed,
+            failed,
+        };
+        self.stats.record(endpoint, sample);
+        response
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::fixtures::{ACCOUNT, NOT_FOUND};
+    use crate::mock_server::{MockResponse, MockServer};
+    use crate::prelude::*;
+
+    #[test]
+    fn endpoints_are_named_without_parameters() {
+        assert_eq!(endpoint_of("v2/mmr/eu/Anonymous/0000"), "v2/mmr");
+        assert_eq!(endpoint_of("v1/content?locale=de-DE"), "v1/content");
+        assert_eq!(
+            endpoint_of("v1/by-puuid/mmr-history/eu/81862fc9"),
+            "v1/by-puuid/mmr-history"
+        );
+    }
+
+    #[test]
+    fn percentiles_use_the_nearest_rank() {
+        let samples = (1..=20)
+            .map(|millis| Sample {
+                latency: Duration::from_millis(millis),
+                failed: millis > 18,
+            })
+            .collect();
+        let stats = EndpointStats::from_samples("v1/account", &samples);
+        assert_eq!(stats.count, 20);
+        assert_eq!(stats.p50, Duration::from_millis(10));
+        assert_eq!(stats.p95, Duration::from_millis(19));
+        assert_eq!(stats.error_rate(), 0.1);
+    }
+
+    #[tokio::test]
+    async fn the_client_records_every_request() {
+        let server = MockServer::start(vec![
+            MockResponse::json(200, ACCOUNT),
+            MockResponse::json(404, NOT_FOUND),
+            MockResponse::json(503, NOT_FOUND),
+        ])
+        .await;
+        let url = server.url();
+        let client = ValorantClient::new().change_api_endpoint(&url);
+        for name in ["Anonymous", "Nobody", "Down"] {
+            let _ = client
+                .request::<AccountData>(ValorantApiType::AccountData { name, tag: "0000" })
+                .await;
+        }
+
+        let stats = client.stats();
+        let [account] = &stats[..] else {
+            panic!("expected one endpoint, got {stats:?}");
+        };
+        assert_eq!(account.endpoint, "v1/account");
+        assert_eq!(account.count, 3);
+        // The 404 is an answer, the 503 is not
+        assert_eq!(account.errors, 1);
+    }
+}
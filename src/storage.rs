@@ -0,0 +1,234 @@
+//! Pluggable persistence for the features that keep state between runs.
+//!
+//! Data is grouped into named collections of keyed records. Values are opaque bytes; the
+//! [`StorageExt`] helpers store them as JSON.
+
+use serde::{de::DeserializeOwned, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
+
+/// A store of keyed records; implement it to keep the crate's state in your own database.
+pub trait Storage: Send + Sync {
+    fn get(&self, collection: &str, key: &str) -> io::Result<Option<Vec<u8>>>;
+
+    fn put(&self, collection: &str, key: &str, value: &[u8]) -> io::Result<()>;
+
+    fn remove(&self, collection: &str, key: &str) -> io::Result<()>;
+
+    /// All keys of a collection in ascending order.
+    fn keys(&self, collection: &str) -> io::Result<Vec<String>>;
+}
+
+/// Implemented for every [`Storage`] and not meant to be implemented by hand.
+pub trait StorageExt: Storage {
+    fn get_json<T: DeserializeOwned>(&self, collection: &str, key: &str) -> io::Result<Option<T>> {
+        match self.get(collection, key)? {
+            Some(bytes) => Ok(Some(serde_json::from_slice(&bytes)?)),
+            None => Ok(None),
+        }
+    }
+
+    fn put_json<T: Serialize>(&self, collection: &str, key: &str, value: &T) -> io::Result<()> {
+        self.put(collection, key, &serde_json::to_vec(value)?)
+    }
+}
+
+impl<S: Storage + ?Sized> StorageExt for S {}
+
+// Lets several features share one store
+impl<S: Storage + ?Sized> Storage for std::sync::Arc<S> {
+    fn get(&self, collection: &str, key: &str) -> io::Result<Option<Vec<u8>>> {
+        (**self).get(collection, key)
+    }
+
+    fn put(&self, collection: &str, key: &str, value: &[u8]) -> io::Result<()> {
+        (**self).put(collection, key, value)
+    }
+
+    fn remove(&self, collection: &str, key: &str) -> io::Result<()> {
+        (**self).remove(collection, key)
+    }
+
+    fn keys(&self, collection: &str) -> io::Result<Vec<String>> {
+        (**self).keys(collection)
+    }
+}
+
+/// Keeps everything in memory, for tests and short-lived processes.
+#[derive(Debug, Default)]
+pub struct MemoryStorage {
+    collections: RwLock<HashMap<String, BTreeMap<String, Vec<u8>>>>,
+}
+
+impl MemoryStorage {
+    pub fn new() -> Self {
+        MemoryStorage::default()
+    }
+}
+
+impl Storage for MemoryStorage {
+    fn get(&self, collection: &str, key: &str) -> io::Result<Option<Vec<u8>>> {
+        Ok(self
+            .collections
+            .read()
+            .unwrap()
+            .get(collection)
+            .and_then(|records| records.get(key).cloned()))
+    }
+
+    fn put(&self, collection: &str, key: &str, value: &[u8]) -> io::Result<()> {
+        self.collections
+            .write()
+            .unwrap()
+            .entry(collection.to_string())
+            .or_default()
+            .insert(key.to_string(), value.to_vec());
+        Ok(())
+    }
+
+    fn remove(&self, collection: &str, key: &str) -> io::Result<()> {
+        if let Some(records) = self.collections.write().unwrap().get_mut(collection) {
+            records.remove(key);
+        }
+        Ok(())
+    }
+
+    fn keys(&self, collection: &str) -> io::Result<Vec<String>> {
+        Ok(self
+            .collections
+            .read()
+            .unwrap()
+            .get(collection)
+            .map(|records| records.keys().cloned().collect())
+            .unwrap_or_default())
+    }
+}
+
+/// Stores each record as a file under `root/<collection>/`.
+#[derive(Debug, Clone)]
+pub struct FileStorage {
+    root: PathBuf,
+}
+
+impl FileStorage {
+    pub fn new(root: impl AsRef<Path>) -> Self {
+        FileStorage {
+            root: root.as_ref().to_path_buf(),
+        }
+    }
+
+    fn path(&self, collection: &str, key: &str) -> PathBuf {
+        self.root.join(encode(collection)).join(encode(key))
+    }
+}
+
+impl Storage for FileStorage {
+    fn get(&self, collection: &str, key: &str) -> io::Result<Option<Vec<u8>>> {
+        match std::fs::read(self.path(collection, key)) {
+            Ok(bytes) => Ok(Some(bytes)),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(error) => Err(error),
+        }
+    }
+
+    fn put(&self, collection: &str, key: &str, value: &[u8]) -> io::Result<()> {
+        let path = self.path(collection, key);
+        std::fs::create_dir_all(self.root.join(encode(collection)))?;
+        // Write to a temporary file first so a crash never leaves a half written record
+        let temporary = path.with_extension("tmp");
+        std::fs::write(&temporary, value)?;
+        std::fs::rename(temporary, path)
+    }
+
+    fn remove(&self, collection: &str, key: &str) -> io::Result<()> {
+        match std::fs::remove_file(self.path(collection, key)) {
+            Err(error) if error.kind() != io::ErrorKind::NotFound => Err(error),
+            _ => Ok(()),
+        }
+    }
+
+    fn keys(&self, collection: &str) -> io::Result<Vec<String>> {
+        let entries = match std::fs::read_dir(self.root.join(encode(collection))) {
+            Ok(entries) => entries,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(error) => return Err(error),
+        };
+        let mut keys = Vec::new();
+        for entry in entries {
+            let name = entry?.file_name();
+            if let Some(key) = name.to_str().and_then(decode) {
+                keys.push(key);
+            }
+        }
+        keys.sort();
+        Ok(keys)
+    }
+}
+
+// Keys may contain `#`, `/` and other characters that aren't safe in file names, so they are
+// stored hex encoded
+fn encode(key: &str) -> String {
+    key.bytes().map(|byte| format!("{byte:02x}")).collect()
+}
+
+fn decode(name: &str) -> Option<String> {
+    if !name.len().is_multiple_of(2) {
+        return None;
+    }
+    let bytes = (0..name.len())
+        .step_by(2)
+        .map(|index| u8::from_str_radix(name.get(index..index + 2)?, 16).ok())
+        .collect::<Option<Vec<_>>>()?;
+    String::from_utf8(bytes).ok()
+}
+
+#[cfg(test)]
+pub(crate) mod test {
+    use super::*;
+
+    pub(crate) fn temp_dir(name: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("valorant-api-{name}-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&path);
+        path
+    }
+
+    fn round_trip(storage: &dyn Storage) {
+        storage.put("players", "Nitro#NERD", b"first").unwrap();
+        storage.put("players", "Anoca#3945", b"second").unwrap();
+        storage.put("other", "key", b"third").unwrap();
+
+        assert_eq!(
+            storage.get("players", "Nitro#NERD").unwrap().as_deref(),
+            Some(&b"first"[..])
+        );
+        assert_eq!(
+            storage.keys("players").unwrap(),
+            ["Anoca#3945", "Nitro#NERD"]
+        );
+
+        storage.remove("players", "Nitro#NERD").unwrap();
+        storage.remove("players", "missing").unwrap();
+        assert_eq!(storage.get("players", "Nitro#NERD").unwrap(), None);
+        assert!(storage.keys("empty").unwrap().is_empty());
+
+        storage.put_json("json", "value", &vec![1, 2, 3]).unwrap();
+        assert_eq!(
+            storage.get_json::<Vec<u32>>("json", "value").unwrap(),
+            Some(vec![1, 2, 3])
+        );
+    }
+
+    #[test]
+    fn memory_storage() {
+        round_trip(&MemoryStorage::new());
+    }
+
+    #[test]
+    fn file_storage() {
+        let root = temp_dir("file-storage");
+        round_trip(&FileStorage::new(&root));
+        std::fs::remove_dir_all(root).unwrap();
+    }
+}
@@ -0,0 +1,189 @@
+//! The in-game store as exposed by the featured store endpoint.
+
+use crate::currency::Currency;
+use crate::ValorantAPIData;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// A bundle currently on sale in the featured store.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct FeaturedBundle {
+    bundle_uuid: String,
+    seconds_remaining: u64,
+    bundle_price: u32,
+    whole_sale_only: bool,
+    items: Vec<BundleItem>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct BundleItem {
+    uuid: String,
+    name: String,
+    image: Option<String>,
+    #[serde(rename = "type")]
+    item_type: String,
+    amount: u32,
+    discount_percent: f64,
+    base_price: u32,
+    discounted_price: u32,
+    promo_item: bool,
+}
+
+impl FeaturedBundle {
+    pub fn bundle_uuid(&self) -> &str {
+        &self.bundle_uuid
+    }
+
+    pub fn seconds_remaining(&self) -> u64 {
+        self.seconds_remaining
+    }
+
+    /// The price of the whole bundle.
+    pub fn bundle_price(&self) -> u32 {
+        self.bundle_price
+    }
+
+    /// Featured bundles are always sold for VP.
+    pub fn currency(&self) -> Currency {
+        Currency::ValorantPoints
+    }
+
+    /// Whether the items can only be bought together.
+    pub fn whole_sale_only(&self) -> bool {
+        self.whole_sale_only
+    }
+
+    pub fn items(&self) -> &[BundleItem] {
+        &self.items
+    }
+}
+
+impl BundleItem {
+    pub fn uuid(&self) -> &str {
+        &self.uuid
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn image(&self) -> Option<&str> {
+        self.image.as_deref()
+    }
+
+    /// The kind of item, e.g. `skin_level` or `player_card`.
+    pub fn item_type(&self) -> &str {
+        &self.item_type
+    }
+
+    pub fn base_price(&self) -> u32 {
+        self.base_price
+    }
+
+    /// The price when bought as part of the bundle.
+    pub fn discounted_price(&self) -> u32 {
+        self.discounted_price
+    }
+}
+
+impl ValorantAPIData for FeaturedBundle {}
+
+/// The player's personal discounted store, which is only open a few times per act.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct NightMarket {
+    remaining_seconds: u64,
+    #[serde(default)]
+    offers: Vec<NightMarketOffer>,
+}
+
+/// A single discounted item. Costs are keyed by currency UUID.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct NightMarketOffer {
+    bonus_offer_id: String,
+    offer_id: String,
+    item_id: String,
+    item_type_id: String,
+    cost: HashMap<String, u32>,
+    discount_percent: u32,
+    discount_costs: HashMap<String, u32>,
+    is_seen: bool,
+}
+
+impl NightMarket {
+    pub fn remaining_seconds(&self) -> u64 {
+        self.remaining_seconds
+    }
+
+    pub fn offers(&self) -> &[NightMarketOffer] {
+        &self.offers
+    }
+}
+
+impl NightMarketOffer {
+    pub fn item_id(&self) -> &str {
+        &self.item_id
+    }
+
+    pub fn item_type_id(&self) -> &str {
+        &self.item_type_id
+    }
+
+    pub fn cost(&self) -> &HashMap<String, u32> {
+        &self.cost
+    }
+
+    pub fn discount_percent(&self) -> u32 {
+        self.discount_percent
+    }
+
+    pub fn discount_costs(&self) -> &HashMap<String, u32> {
+        &self.discount_costs
+    }
+
+    /// The undiscounted price in the given currency, if the offer is sold for it.
+    pub fn price_in(&self, currency: Currency) -> Option<u32> {
+        self.cost.get(currency.uuid()).copied()
+    }
+
+    pub fn discounted_price_in(&self, currency: Currency) -> Option<u32> {
+        self.discount_costs.get(currency.uuid()).copied()
+    }
+
+    /// Whether the player has flipped the card over in game.
+    pub fn is_seen(&self) -> bool {
+        self.is_seen
+    }
+}
+
+impl ValorantAPIData for NightMarket {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::fixtures::{decode, NIGHT_MARKET, STORE_FEATURED};
+
+    #[test]
+    fn deserialize_response() {
+        let data: Vec<FeaturedBundle> = decode(STORE_FEATURED);
+        let bundle = &data[0];
+        assert_eq!(bundle.bundle_price(), 7100);
+        assert!(!bundle.whole_sale_only());
+        let items = bundle.items();
+        assert_eq!(items.len(), 3);
+        assert_eq!(items[0].name(), "Prime//2.0 Vandal");
+        assert_eq!(
+            (items[0].base_price(), items[0].discounted_price()),
+            (1775, 1189)
+        );
+        assert_eq!(items[2].image(), None);
+    }
+
+    #[test]
+    fn deserialize_night_market() {
+        let data: NightMarket = decode(NIGHT_MARKET);
+        let offer = &data.offers()[0];
+        let price = offer.discounted_price_in(Currency::ValorantPoints).unwrap();
+        assert_eq!(Currency::ValorantPoints.format(price), "1,189 VP");
+        assert_eq!(offer.price_in(Currency::RadianitePoints), None);
+    }
+}
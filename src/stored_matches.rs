@@ -0,0 +1,335 @@
+#[cfg(feature = "client")]
+use crate::error::{self, ValorantError};
+use crate::ids::MatchId;
+use crate::ValorantAPIData;
+#[cfg(feature = "client")]
+use crate::{replay_response, AccountRegion, ApiResponse, ValorantApiType, ValorantClient};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// A match from the API's own long term match store. Unlike [`MatchData`](crate::match_data::MatchData)
+/// it only holds the requested player's stats, but goes back much further than the match
+/// history.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct StoredMatch {
+    meta: StoredMatchMeta,
+    stats: StoredMatchStats,
+    teams: StoredMatchTeams,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+struct StoredMatchMeta {
+    id: MatchId,
+    map: NamedReference,
+    mode: String,
+    started_at: DateTime<Utc>,
+    season: StoredSeason,
+    region: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+struct NamedReference {
+    id: String,
+    name: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+struct StoredSeason {
+    id: String,
+    short: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+struct StoredMatchStats {
+    puuid: String,
+    team: String,
+    character: NamedReference,
+    tier: u32,
+    score: u32,
+    kills: u32,
+    deaths: u32,
+    assists: u32,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+struct StoredMatchTeams {
+    red: u32,
+    blue: u32,
+}
+
+impl StoredMatch {
+    pub fn match_id(&self) -> &MatchId {
+        &self.meta.id
+    }
+
+    pub fn map(&self) -> &str {
+        &self.meta.map.name
+    }
+
+    pub fn mode(&self) -> &str {
+        &self.meta.mode
+    }
+
+    pub fn started_at(&self) -> DateTime<Utc> {
+        self.meta.started_at
+    }
+
+    /// The short act ID, e.g. `e6a1`.
+    pub fn season(&self) -> &str {
+        &self.meta.season.short
+    }
+
+    pub fn agent(&self) -> &str {
+        &self.stats.character.name
+    }
+
+    pub fn kills(&self) -> u32 {
+        self.stats.kills
+    }
+
+    pub fn deaths(&self) -> u32 {
+        self.stats.deaths
+    }
+
+    pub fn assists(&self) -> u32 {
+        self.stats.assists
+    }
+
+    /// The rounds won by the player's team, then by the enemy team.
+    pub fn score(&self) -> (u32, u32) {
+        if self.stats.team.eq_ignore_ascii_case("blue") {
+            (self.teams.blue, self.teams.red)
+        } else {
+            (self.teams.red, self.teams.blue)
+        }
+    }
+}
+
+impl ValorantAPIData for StoredMatch {}
+
+/// The `results` block the API sends next to a page of stored matches.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct PageInfo {
+    pub total: u32,
+    pub returned: u32,
+    /// Matches on earlier pages.
+    pub before: u32,
+    /// Matches on later pages.
+    pub after: u32,
+}
+
+impl PageInfo {
+    pub fn has_more(&self) -> bool {
+        self.after > 0
+    }
+}
+
+#[cfg(feature = "client")]
+#[derive(Deserialize)]
+struct PageEnvelope {
+    results: Option<PageInfo>,
+}
+
+/// One page of stored matches, at the page size the server actually used.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct StoredMatchesPage {
+    pub matches: Vec<StoredMatch>,
+    /// Missing when the API leaves out the metadata.
+    pub results: Option<PageInfo>,
+    pub page: u32,
+    pub size: u32,
+}
+
+impl StoredMatchesPage {
+    /// How many matches come before this page.
+    pub fn offset(&self) -> u32 {
+        (self.page - 1) * self.size
+    }
+
+    /// Whether later pages hold more matches, guessed from a full page when the metadata
+    /// is missing.
+    pub fn has_more(&self) -> bool {
+        match self.results {
+            Some(results) => results.has_more(),
+            None => self.matches.len() as u32 >= self.size,
+        }
+    }
+}
+
+impl ValorantAPIData for StoredMatchesPage {}
+
+#[cfg(feature = "client")]
+impl ValorantClient<'_> {
+    /// The largest page size the stored matches endpoint served so far. Starts at
+    /// [`MAX_PAGE_SIZE`](crate::validation::MAX_PAGE_SIZE) and shrinks when the server turns out to cap pages lower.
+    pub fn stored_matches_page_limit(&self) -> u32 {
+        *self.stored_page_limit.read().unwrap()
+    }
+
+    /// Fetches a page of stored matches. Sizes above the
+    /// [limit](Self::stored_matches_page_limit) are lowered to it, and a server that caps the
+    /// page lower than asked for lowers the limit for later pages. Check
+    /// [`StoredMatchesPage::size`] for the size a page came in.
+    pub async fn stored_matches_page(
+        &self,
+        region: AccountRegion,
+        name: &str,
+        tag: &str,
+        page: u32,
+        size: u32,
+    ) -> Result<StoredMatchesPage, ValorantError> {
+        let mut size = size.min(self.stored_matches_page_limit());
+        let api_type = ValorantApiType::StoredMatches {
+            region,
+            name,
+            tag,
+            filter: None,
+            page: Some(page),
+            size: Some(size),
+        };
+        api_type.validate()?;
+        let response = self.send_authorized(&api_type).await?;
+        let status = response.status();
+        let body = response.bytes().await?.to_vec();
+        let results = serde_json::from_slice::<PageEnvelope>(&body)
+            .ok()
+            .and_then(|envelope| envelope.results);
+        // A short page with more to come means the server serves smaller pages than asked for
+        if let Some(results) = results {
+            if results.has_more() && results.returned > 0 && results.returned < size {
+                size = results.returned;
+                let mut limit = self.stored_page_limit.write().unwrap();
+                *limit = (*limit).min(size);
+            }
+        }
+        let matches = replay_response(status, body)
+            .json::<ApiResponse<Vec<StoredMatch>>>()
+            .await?;
+        Ok(StoredMatchesPage {
+            matches: error::into_data(matches)?,
+            results,
+            page,
+            size,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::fixtures::{decode, STORED_MATCHES};
+    #[cfg(feature = "client")]
+    use crate::mock_server::{MockResponse, MockServer};
+
+    #[test]
+    fn deserialize_response() {
+        let data: Vec<StoredMatch> = decode(STORED_MATCHES);
+        let game = &data[0];
+        assert_eq!(
+            game.match_id().to_string(),
+            "0c6bd2a0-2c1f-4b87-9b1e-6f3f5d2a7e11"
+        );
+        assert_eq!(
+            (game.map(), game.mode(), game.season()),
+            ("Ascent", "Competitive", "e6a1")
+        );
+        assert_eq!(game.agent(), "Jett");
+        assert_eq!((game.kills(), game.deaths(), game.assists()), (19, 9, 11));
+        assert_eq!(game.score(), (13, 10));
+        assert_eq!(data[1].score(), (7, 13));
+    }
+
+    #[cfg(feature = "client")]
+    #[tokio::test]
+    async fn discovers_a_lower_page_limit() {
+        // Asked for 20, the server answers with its own cap of 2
+        let server = MockServer::routes(vec![(
+            "/NitroSniper/NERD?page=1&size=20",
+            MockResponse::json(200, STORED_MATCHES),
+        )])
+        .await;
+        let url = server.url();
+        let client = ValorantClient::new().change_api_endpoint(&url);
+        assert_eq!(
+            client.stored_matches_page_limit(),
+            crate::validation::MAX_PAGE_SIZE
+        );
+
+        let page = client
+            .stored_matches_page(AccountRegion::EU, "NitroSniper", "NERD", 1, 20)
+            .await
+            .unwrap();
+        assert_eq!((page.size, page.matches.len()), (2, 2));
+        assert!(page.has_more());
+        assert_eq!(client.stored_matches_page_limit(), 2);
+
+        // Later pages are requested at the discovered size
+        let _ = client
+            .stored_matches_page(AccountRegion::EU, "NitroSniper", "NERD", 2, 20)
+            .await;
+        assert!(server.requests()[1].path.ends_with("?page=2&size=2"));
+    }
+
+    #[test]
+    fn filters_are_part_of_the_url() {
+        use crate::mmr_data::{EpisodeAndAct, SeasonFilter};
+
+        let page = |filter, page| {
+            crate::ValorantApiType::StoredMatches {
+                region: crate::AccountRegion::EU,
+                name: "NitroSniper",
+                tag: "NERD",
+                filter,
+                page,
+                size: Some(20),
+            }
+            .to_url()
+        };
+        assert_eq!(
+            page(None, Some(1)),
+            "v1/stored-matches/eu/NitroSniper/NERD?page=1&size=20"
+        );
+        assert_eq!(
+            page(
+                Some(SeasonFilter::Act(EpisodeAndAct::from_numbers(6, 1))),
+                None
+            ),
+            "v1/stored-matches/eu/NitroSniper/NERD?season=e6a1&size=20"
+        );
+        // Left for the client to resolve
+        assert_eq!(
+            page(Some(SeasonFilter::Current), Some(2)),
+            "v1/stored-matches/eu/NitroSniper/NERD?page=2&size=20"
+        );
+    }
+
+    #[cfg(feature = "client")]
+    #[tokio::test]
+    async fn current_act_filters_stored_matches() {
+        use crate::mmr_data::SeasonFilter;
+
+        let server = MockServer::start(vec![
+            MockResponse::json(200, crate::fixtures::CONTENT),
+            MockResponse::json(200, STORED_MATCHES),
+        ])
+        .await;
+        let url = server.url();
+        let client = ValorantClient::new().change_api_endpoint(&url);
+
+        client
+            .request::<Vec<StoredMatch>>(ValorantApiType::StoredMatches {
+                region: AccountRegion::EU,
+                name: "NitroSniper",
+                tag: "NERD",
+                filter: Some(SeasonFilter::Current),
+                page: Some(1),
+                size: None,
+            })
+            .await
+            .unwrap();
+        assert_eq!(
+            server.requests()[1].path,
+            "/v1/stored-matches/eu/NitroSniper/NERD?season=e6a1&page=1"
+        );
+    }
+}
@@ -0,0 +1,151 @@
+//! Incremental syncing of a player's matches and rank changes.
+
+use crate::deadline::{self, Deadline};
+use crate::error::ValorantError;
+use crate::ids::Puuid;
+use crate::match_data::MatchData;
+use crate::mmr_history::MMRHistoryEntry;
+use crate::{AccountRegion, ValorantAPIData, ValorantApiType, ValorantClient};
+use chrono::{DateTime, Utc};
+
+/// Everything that happened to a player after a watermark.
+#[derive(Debug)]
+pub struct PlayerSync {
+    /// New matches, newest first.
+    pub matches: Vec<MatchData>,
+    /// New ranked games, newest first.
+    pub mmr_changes: Vec<MMRHistoryEntry>,
+    /// Pass this as `since` on the next sync to only get what's new from then on.
+    pub watermark: DateTime<Utc>,
+}
+
+impl ValorantAPIData for PlayerSync {}
+
+impl ValorantClient<'_> {
+    /// Fetches the matches and MMR changes a player had after `since`. Keeping the returned
+    /// watermark and passing it back in makes every sync return only new data, so callers don't
+    /// need to de-duplicate against what they already stored.
+    pub async fn sync_player(
+        &self,
+        region: AccountRegion,
+        puuid: &Puuid,
+        since: DateTime<Utc>,
+    ) -> Result<PlayerSync, ValorantError> {
+        self.sync(region, puuid, since, None).await
+    }
+
+    /// Like [`sync_player`](Self::sync_player), but gives up once `deadline` passes.
+    pub async fn sync_player_within(
+        &self,
+        region: AccountRegion,
+        puuid: &Puuid,
+        since: DateTime<Utc>,
+        deadline: Deadline,
+    ) -> Result<PlayerSync, ValorantError> {
+        self.sync(region, puuid, since, Some(&deadline)).await
+    }
+
+    async fn sync(
+        &self,
+        region: AccountRegion,
+        puuid: &Puuid,
+        since: DateTime<Utc>,
+        deadline: Option<&Deadline>,
+    ) -> Result<PlayerSync, ValorantError> {
+        let puuid = puuid.as_str();
+        let requests = async {
+            tokio::join!(
+                self.request::<Vec<MMRHistoryEntry>>(ValorantApiType::MMRHistoryByPuuid {
+                    region,
+                    puuid
+                }),
+                self.request::<Vec<MatchData>>(ValorantApiType::MatchHistoryByPuuid {
+                    region,
+                    puuid
+                }),
+            )
+        };
+        let (history, matches) = deadline::step(deadline, 1, requests).await?;
+        let (history, matches) = (history?, matches?);
+
+        let mmr_changes = newer_than(history, since, MMRHistoryEntry::date);
+        let matches = newer_than(matches, since, MatchData::started_at);
+        let watermark = matches
+            .iter()
+            .map(MatchData::started_at)
+            .chain(mmr_changes.iter().map(MMRHistoryEntry::date))
+            .max()
+            .unwrap_or(since)
+            .max(since);
+        Ok(PlayerSync {
+            matches,
+            mmr_changes,
+            watermark,
+        })
+    }
+}
+
+fn newer_than<T>(
+    items: Vec<T>,
+    since: DateTime<Utc>,
+    time: impl Fn(&T) -> DateTime<Utc>,
+) -> Vec<T> {
+    let mut items = items
+        .into_iter()
+        .filter(|item| time(item) > since)
+        .collect::<Vec<_>>();
+    items.sort_by_key(|item| std::cmp::Reverse(time(item)));
+    items
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::fixtures::{MATCH_HISTORY, MMR_HISTORY};
+    use crate::mock_server::{MockResponse, MockServer};
+
+    #[tokio::test]
+    async fn only_returns_new_data() {
+        let puuid: Puuid = "b44adaae-ab83-5001-a296-89ea0de0bce3".parse().unwrap();
+        let server = MockServer::routes(vec![
+            (
+                "/v1/by-puuid/mmr-history/eu/b44adaae-ab83-5001-a296-89ea0de0bce3",
+                MockResponse::json(200, MMR_HISTORY),
+            ),
+            (
+                "/v3/by-puuid/matches/eu/b44adaae-ab83-5001-a296-89ea0de0bce3",
+                MockResponse::json(200, MATCH_HISTORY),
+            ),
+        ])
+        .await;
+        let url = server.url();
+        let client = ValorantClient::new().change_api_endpoint(&url);
+
+        let first = client
+            .sync_player(AccountRegion::EU, &puuid, DateTime::<Utc>::MIN_UTC)
+            .await
+            .unwrap();
+        assert_eq!(first.matches.len(), 1);
+        assert_eq!(first.mmr_changes.len(), 2);
+        assert_eq!(first.watermark.timestamp(), 1676408400);
+
+        // Between the two ranked games
+        let since = DateTime::from_timestamp(1676406000, 0).unwrap();
+        let partial = client
+            .sync_player(AccountRegion::EU, &puuid, since)
+            .await
+            .unwrap();
+        assert_eq!(partial.mmr_changes.len(), 1);
+        assert_eq!(
+            partial.mmr_changes[0].match_id().as_str(),
+            "0c6bd2a0-2c1f-4b87-9b1e-6f3f5d2a7e11"
+        );
+
+        let again = client
+            .sync_player(AccountRegion::EU, &puuid, first.watermark)
+            .await
+            .unwrap();
+        assert!(again.matches.is_empty() && again.mmr_changes.is_empty());
+        assert_eq!(again.watermark, first.watermark);
+    }
+}
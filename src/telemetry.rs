@@ -0,0 +1,326 @@
+//! W3C trace context propagation and OpenTelemetry attributes for every request, without
+//! depending on an OpenTelemetry SDK.
+//!
+//! A [`TraceContextHook`] hands the client the context of the caller's current span, which is
+//! sent along as the `traceparent` and `tracestate` headers so the API's side of the call joins
+//! the caller's trace. A [`SpanHook`] receives every finished request as a [`RequestSpan`] named
+//! and attributed after the HTTP client semantic conventions, ready to be recorded as a span by
+//! whichever tracing stack the service uses.
+
+use std::fmt;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Returns the trace context outgoing requests should carry, if any.
+pub type TraceContextHook = Arc<dyn Fn() -> Option<TraceContext> + Send + Sync>;
+
+/// Called with every request once it finished or failed.
+pub type SpanHook = Arc<dyn Fn(&RequestSpan) + Send + Sync>;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum TraceContextError {
+    /// Not a version 00 `traceparent` header.
+    Malformed(String),
+    /// All zero trace or parent IDs are invalid.
+    ZeroId,
+}
+
+impl fmt::Display for TraceContextError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TraceContextError::Malformed(header) => write!(f, "Malformed traceparent: {header:?}"),
+            TraceContextError::ZeroId => write!(f, "Trace and parent IDs can't be all zeros"),
+        }
+    }
+}
+
+impl std::error::Error for TraceContextError {}
+
+/// The part of a W3C trace context that is sent with a request.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TraceContext {
+    trace_id: [u8; 16],
+    parent_id: [u8; 8],
+    sampled: bool,
+    state: Option<String>,
+}
+
+impl TraceContext {
+    pub fn new(
+        trace_id: [u8; 16],
+        parent_id: [u8; 8],
+        sampled: bool,
+    ) -> Result<Self, TraceContextError> {
+        if trace_id == [0; 16] || parent_id == [0; 8] {
+            return Err(TraceContextError::ZeroId);
+        }
+        Ok(TraceContext {
+            trace_id,
+            parent_id,
+            sampled,
+            state: None,
+        })
+    }
+
+    /// Vendor data passed through as the `tracestate` header.
+    pub fn with_state(mut self, state: impl Into<String>) -> Self {
+        self.state = Some(state.into());
+        self
+    }
+
+    pub fn trace_id(&self) -> [u8; 16] {
+        self.trace_id
+    }
+
+    pub fn parent_id(&self) -> [u8; 8] {
+        self.parent_id
+    }
+
+    pub fn is_sampled(&self) -> bool {
+        self.sampled
+    }
+
+    /// The `traceparent` header value, e.g. `00-<trace id>-<parent id>-01`.
+    pub fn traceparent(&self) -> String {
+        format!(
+            "00-{}-{}-{:02x}",
+            hex(&self.trace_id),
+            hex(&self.parent_id),
+            u8::from(self.sampled)
+        )
+    }
+
+    pub fn tracestate(&self) -> Option<&str> {
+        self.state.as_deref()
+    }
+}
+
+/// Parses a `traceparent` header value.
+impl FromStr for TraceContext {
+    type Err = TraceContextError;
+
+    fn from_str(header: &str) -> Result<Self, Self::Err> {
+        let malformed = || TraceContextError::Malformed(header.to_string());
+        let parts: Vec<&str> = header.trim().split('-').collect();
+        let [version, trace_id, parent_id, flags] = parts[..] else {
+            return Err(malformed());
+        };
+        if version != "00" {
+            return Err(malformed());
+        }
+        let trace_id = unhex::<16>(trace_id).ok_or_else(malformed)?;
+        let parent_id = unhex::<8>(parent_id).ok_or_else(malformed)?;
+        let [flags] = unhex::<1>(flags).ok_or_else(malformed)?;
+        TraceContext::new(trace_id, parent_id, flags & 1 == 1)
+    }
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+// Only lowercase hex is valid in a traceparent
+fn unhex<const N: usize>(value: &str) -> Option<[u8; N]> {
+    if value.len() != N * 2
+        || !value
+            .bytes()
+            .all(|b| matches!(b, b'0'..=b'9' | b'a'..=b'f'))
+    {
+        return None;
+    }
+    let mut bytes = [0; N];
+    for (index, byte) in bytes.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&value[index * 2..index * 2 + 2], 16).ok()?;
+    }
+    Some(bytes)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum AttributeValue {
+    String(String),
+    Int(i64),
+}
+
+/// A finished request, described the way the semantic conventions describe an HTTP client
+/// span.
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct RequestSpan {
+    /// The method and endpoint, e.g. `GET v2/mmr`, which unlike the URL doesn't name a player.
+    pub name: String,
+    pub duration: Duration,
+    /// `http.request.method`, `url.full`, `server.address`, `server.port`, and
+    /// `http.response.status_code` or `error.type` when the request failed.
+    pub attributes: Vec<(&'static str, AttributeValue)>,
+}
+
+impl RequestSpan {
+    pub fn attribute(&self, key: &str) -> Option<&AttributeValue> {
+        self.attributes
+            .iter()
+            .find(|(known, _)| *known == key)
+            .map(|(_, value)| value)
+    }
+
+    /// Whether the span should be marked as an error, which for a client is any failure to get
+    /// an answer and any 4xx or 5xx answer.
+    pub fn is_error(&self) -> bool {
+        self.attribute("error.type").is_some()
+    }
+}
+
+#[derive(Default, Clone)]
+pub(crate) struct Telemetry {
+    pub(crate) context: Option<TraceContextHook>,
+    pub(crate) on_span: Option<SpanHook>,
+}
+
+impl Telemetry {
+    pub(crate) fn inject(&self, request: &mut reqwest::Request) {
+        let Some(context) = self.context.as_ref().and_then(|hook| hook()) else {
+            return;
+        };
+        let headers = request.headers_mut();
+        if let Ok(value) = context.traceparent().parse() {
+            headers.insert("traceparent", value);
+        }
+        if let Some(Ok(value)) = context.tracestate().map(str::parse) {
+            headers.insert("tracestate", value);
+        }
+    }
+
+    pub(crate) fn record(
+        &self,
+        endpoint: &str,
+        method: &reqwest::Method,
+        url: &reqwest::Url,
+        outcome: &Result<reqwest::Response, reqwest::Error>,
+        duration: Duration,
+    ) {
+        let Some(on_span) = &self.on_span else {
+            return;
+        };
+        let text = |value: &str| AttributeValue::String(value.to_string());
+        let mut attributes = vec![
+            ("http.request.method", text(method.as_str())),
+            ("url.full", text(url.as_str())),
+            ("server.address", text(url.host_str().unwrap_or_default())),
+        ];
+        if let Some(port) = url.port_or_known_default() {
+            attributes.push(("server.port", AttributeValue::Int(i64::from(port))));
+        }
+        match outcome {
+            Ok(response) => {
+                let status = response.status().as_u16();
+                attributes.push((
+                    "http.response.status_code",
+                    AttributeValue::Int(i64::from(status)),
+                ));
+                if status >= 400 {
+                    attributes.push(("error.type", text(&status.to_string())));
+                }
+            }
+            Err(error) => {
+                let kind = if error.is_timeout() {
+                    "timeout"
+                } else if error.is_connect() {
+                    "connect"
+                } else {
+                    "_OTHER"
+                };
+                attributes.push(("error.type", text(kind)));
+            }
+        }
+        on_span(&RequestSpan {
+            name: format!("{method} {endpoint}"),
+            duration,
+            attributes,
+        });
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::fixtures::{ACCOUNT, NOT_FOUND};
+    use crate::mock_server::{MockResponse, MockServer};
+    use crate::prelude::*;
+    use std::sync::Mutex;
+
+    #[test]
+    fn traceparent_round_trips() {
+        let header = "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01";
+        let context: TraceContext = header.parse().unwrap();
+        assert!(context.is_sampled());
+        assert_eq!(context.parent_id()[7], 0xb7);
+        assert_eq!(context.traceparent(), header);
+
+        for invalid in [
+            "01-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01",
+            "00-4BF92F3577B34DA6A3CE929D0E0E4736-00f067aa0ba902b7-01",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7",
+        ] {
+            assert!(matches!(
+                invalid.parse::<TraceContext>(),
+                Err(TraceContextError::Malformed(_))
+            ));
+        }
+        assert_eq!(
+            "00-00000000000000000000000000000000-00f067aa0ba902b7-00".parse::<TraceContext>(),
+            Err(TraceContextError::ZeroId)
+        );
+    }
+
+    #[tokio::test]
+    async fn requests_carry_the_context_and_report_spans() {
+        let server = MockServer::start(vec![
+            MockResponse::json(200, ACCOUNT),
+            MockResponse::json(404, NOT_FOUND),
+        ])
+        .await;
+        let url = server.url();
+        let spans = Arc::new(Mutex::new(Vec::new()));
+        let recorded = Arc::clone(&spans);
+        let client = ValorantClient::builder()
+            .api_endpoint(&url)
+            .trace_context(|| {
+                let context = TraceContext::new([0xab; 16], [0xcd; 8], true).ok()?;
+                Some(context.with_state("valorust=1"))
+            })
+            .on_request_span(move |span| recorded.lock().unwrap().push(span.clone()))
+            .build()
+            .unwrap();
+        let account = |name| {
+            client.request::<AccountData>(ValorantApiType::AccountData { name, tag: "0000" })
+        };
+        account("Anonymous").await.unwrap();
+        account("Nobody").await.unwrap_err();
+
+        let request = &server.requests()[0];
+        assert_eq!(
+            request.header("traceparent"),
+            Some(format!("00-{}-{}-01", "ab".repeat(16), "cd".repeat(8)).as_str())
+        );
+        assert_eq!(request.header("tracestate"), Some("valorust=1"));
+
+        let spans = spans.lock().unwrap();
+        assert_eq!(spans[0].name, "GET v1/account");
+        assert_eq!(
+            spans[0].attribute("http.response.status_code"),
+            Some(&AttributeValue::Int(200))
+        );
+        assert_eq!(
+            spans[0].attribute("url.full"),
+            Some(&AttributeValue::String(format!(
+                "{url}/v1/account/Anonymous/0000"
+            )))
+        );
+        assert!(!spans[0].is_error());
+        assert_eq!(
+            spans[1].attribute("error.type"),
+            Some(&AttributeValue::String("404".to_string()))
+        );
+    }
+}
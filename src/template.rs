@@ -0,0 +1,198 @@
+//! Message formats for chat bots, like `{name} is {tier} {rr}RR ({delta} last game)`.
+//!
+//! A [`Template`] is parsed once and rendered against any [`TemplateData`]. Placeholders name a
+//! field in braces; `{{` and `}}` stand for literal braces.
+
+use crate::account_data::AccountData;
+use crate::mmr_data::MMRData;
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum TemplateError {
+    /// A `{` without its closing `}`, at this byte offset.
+    Unclosed(usize),
+    /// A `}` that doesn't close a placeholder, at this byte offset.
+    Unopened(usize),
+    EmptyPlaceholder(usize),
+    /// The data has no field of this name.
+    UnknownField(String),
+}
+
+impl fmt::Display for TemplateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TemplateError::Unclosed(at) => write!(f, "Unclosed placeholder at {at}"),
+            TemplateError::Unopened(at) => write!(f, "Unmatched '}}' at {at}"),
+            TemplateError::EmptyPlaceholder(at) => write!(f, "Empty placeholder at {at}"),
+            TemplateError::UnknownField(field) => write!(f, "Unknown field {field:?}"),
+        }
+    }
+}
+
+impl std::error::Error for TemplateError {}
+
+/// Values a template can fill its placeholders with. Open for implementation, so applications
+/// can render their own types.
+pub trait TemplateData {
+    fn field(&self, name: &str) -> Option<String>;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Part {
+    Text(String),
+    Field(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Template {
+    parts: Vec<Part>,
+}
+
+impl Template {
+    pub fn render(&self, data: &dyn TemplateData) -> Result<String, TemplateError> {
+        let mut rendered = String::new();
+        for part in &self.parts {
+            match part {
+                Part::Text(text) => rendered.push_str(text),
+                Part::Field(name) => rendered.push_str(
+                    &data
+                        .field(name)
+                        .ok_or_else(|| TemplateError::UnknownField(name.clone()))?,
+                ),
+            }
+        }
+        Ok(rendered)
+    }
+
+    /// The placeholders in the order they appear.
+    pub fn fields(&self) -> impl Iterator<Item = &str> {
+        self.parts.iter().filter_map(|part| match part {
+            Part::Field(name) => Some(name.as_str()),
+            Part::Text(_) => None,
+        })
+    }
+}
+
+impl FromStr for Template {
+    type Err = TemplateError;
+
+    fn from_str(template: &str) -> Result<Self, Self::Err> {
+        let mut parts = Vec::new();
+        let mut text = String::new();
+        let mut chars = template.char_indices().peekable();
+        while let Some((at, c)) = chars.next() {
+            match c {
+                '{' if chars.next_if(|&(_, next)| next == '{').is_some() => text.push('{'),
+                '}' if chars.next_if(|&(_, next)| next == '}').is_some() => text.push('}'),
+                '}' => return Err(TemplateError::Unopened(at)),
+                '{' => {
+                    let mut name = String::new();
+                    loop {
+                        match chars.next() {
+                            Some((_, '}')) => break,
+                            Some((_, c)) => name.push(c),
+                            None => return Err(TemplateError::Unclosed(at)),
+                        }
+                    }
+                    let name = name.trim();
+                    if name.is_empty() {
+                        return Err(TemplateError::EmptyPlaceholder(at));
+                    }
+                    if !text.is_empty() {
+                        parts.push(Part::Text(std::mem::take(&mut text)));
+                    }
+                    parts.push(Part::Field(name.to_string()));
+                }
+                c => text.push(c),
+            }
+        }
+        if !text.is_empty() {
+            parts.push(Part::Text(text));
+        }
+        Ok(Template { parts })
+    }
+}
+
+impl<K: std::borrow::Borrow<str> + Eq + std::hash::Hash, V: fmt::Display> TemplateData
+    for HashMap<K, V>
+{
+    fn field(&self, name: &str) -> Option<String> {
+        self.get(name).map(ToString::to_string)
+    }
+}
+
+/// `name`, `tag`, `tier`, `rr`, `elo`, `delta` (signed, e.g. `+21`) and `peak`.
+impl TemplateData for MMRData {
+    fn field(&self, name: &str) -> Option<String> {
+        Some(match name {
+            "name" => self.name().to_string(),
+            "tag" => self.tag().to_string(),
+            "tier" => self.current_tier_patched().to_string(),
+            "rr" => self.ranking_in_tier().to_string(),
+            "elo" => self.elo().to_string(),
+            "delta" => format!("{:+}", self.mmr_change_to_last_game()),
+            "peak" => self.highest_tier_patched().to_string(),
+            _ => return None,
+        })
+    }
+}
+
+/// `name`, `tag`, `level`, `region` and `puuid`.
+impl TemplateData for AccountData {
+    fn field(&self, name: &str) -> Option<String> {
+        Some(match name {
+            "name" => self.name().to_string(),
+            "tag" => self.tag().to_string(),
+            "level" => self.account_level().to_string(),
+            "region" => format!("{:?}", self.region()),
+            "puuid" => self.puuid().to_string(),
+            _ => return None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::fixtures::{decode, ACCOUNT, MMR};
+
+    #[test]
+    fn fills_templates_from_models() {
+        let template: Template = "{name} is {tier} {rr}RR ({delta} last game)"
+            .parse()
+            .unwrap();
+        assert_eq!(
+            template.fields().collect::<Vec<_>>(),
+            ["name", "tier", "rr", "delta"]
+        );
+        let mmr: MMRData = decode(MMR);
+        assert_eq!(
+            template.render(&mmr).unwrap(),
+            "Anonymous is Platinum 2 47RR (-11 last game)"
+        );
+
+        let account: AccountData = decode(ACCOUNT);
+        let template: Template = "{{{ name }#{tag}}} level {level}".parse().unwrap();
+        assert_eq!(
+            template.render(&account).unwrap(),
+            "{Anonymous#0000} level 125"
+        );
+        assert_eq!(
+            template.render(&HashMap::from([("name", "a")])),
+            Err(TemplateError::UnknownField("tag".to_string()))
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_templates() {
+        assert_eq!("{name".parse::<Template>(), Err(TemplateError::Unclosed(0)));
+        assert_eq!("rr}".parse::<Template>(), Err(TemplateError::Unopened(2)));
+        assert_eq!(
+            "a { }".parse::<Template>(),
+            Err(TemplateError::EmptyPlaceholder(2))
+        );
+    }
+}
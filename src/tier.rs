@@ -0,0 +1,176 @@
+//! Competitive tiers as a type instead of the API's `currenttier` numbers.
+//!
+//! Tier 3 is Iron 1 and 27 is Radiant, with three divisions per rank in between; 1 and 2 are
+//! left over from ranks that were removed. [`Tier`] orders like the numbers do, so
+//! `tier >= Tier::Diamond1` reads as it should.
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+#[derive(
+    Serialize, Deserialize, Debug, Default, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord,
+)]
+#[serde(try_from = "u32", into = "u32")]
+pub enum Tier {
+    #[default]
+    Unranked,
+    Iron1,
+    Iron2,
+    Iron3,
+    Bronze1,
+    Bronze2,
+    Bronze3,
+    Silver1,
+    Silver2,
+    Silver3,
+    Gold1,
+    Gold2,
+    Gold3,
+    Platinum1,
+    Platinum2,
+    Platinum3,
+    Diamond1,
+    Diamond2,
+    Diamond3,
+    Ascendant1,
+    Ascendant2,
+    Ascendant3,
+    Immortal1,
+    Immortal2,
+    Immortal3,
+    Radiant,
+}
+
+/// A `currenttier` number above Radiant.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UnknownTier(pub u32);
+
+impl fmt::Display for UnknownTier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Unknown competitive tier: {}", self.0)
+    }
+}
+
+impl std::error::Error for UnknownTier {}
+
+impl Tier {
+    /// Every tier from Unranked up to Radiant.
+    pub const ALL: [Tier; 26] = [
+        Tier::Unranked,
+        Tier::Iron1,
+        Tier::Iron2,
+        Tier::Iron3,
+        Tier::Bronze1,
+        Tier::Bronze2,
+        Tier::Bronze3,
+        Tier::Silver1,
+        Tier::Silver2,
+        Tier::Silver3,
+        Tier::Gold1,
+        Tier::Gold2,
+        Tier::Gold3,
+        Tier::Platinum1,
+        Tier::Platinum2,
+        Tier::Platinum3,
+        Tier::Diamond1,
+        Tier::Diamond2,
+        Tier::Diamond3,
+        Tier::Ascendant1,
+        Tier::Ascendant2,
+        Tier::Ascendant3,
+        Tier::Immortal1,
+        Tier::Immortal2,
+        Tier::Immortal3,
+        Tier::Radiant,
+    ];
+
+    /// The tier of a `currenttier` number. The unused tiers 1 and 2 count as Unranked.
+    pub fn from_id(id: u32) -> Option<Self> {
+        match id {
+            0..=2 => Some(Tier::Unranked),
+            _ => Tier::ALL.get(id as usize - 2).copied(),
+        }
+    }
+
+    /// The `currenttier` number, 0 for Unranked.
+    pub fn id(self) -> u32 {
+        match self {
+            Tier::Unranked => 0,
+            tier => tier as u32 + 2,
+        }
+    }
+
+    pub fn is_ranked(self) -> bool {
+        self != Tier::Unranked
+    }
+}
+
+impl TryFrom<u32> for Tier {
+    type Error = UnknownTier;
+
+    fn try_from(id: u32) -> Result<Self, Self::Error> {
+        Tier::from_id(id).ok_or(UnknownTier(id))
+    }
+}
+
+impl From<Tier> for u32 {
+    fn from(tier: Tier) -> Self {
+        tier.id()
+    }
+}
+
+/// The name the API patches in, e.g. `Gold 2`.
+impl fmt::Display for Tier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        const RANKS: [&str; 8] = [
+            "Iron",
+            "Bronze",
+            "Silver",
+            "Gold",
+            "Platinum",
+            "Diamond",
+            "Ascendant",
+            "Immortal",
+        ];
+        match self {
+            Tier::Unranked => f.write_str("Unrated"),
+            Tier::Radiant => f.write_str("Radiant"),
+            tier => {
+                let index = tier.id() - 3;
+                write!(f, "{} {}", RANKS[index as usize / 3], index % 3 + 1)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn tiers_round_trip_through_ids() {
+        for tier in Tier::ALL {
+            assert_eq!(Tier::from_id(tier.id()), Some(tier));
+        }
+        assert_eq!(Tier::from_id(1), Some(Tier::Unranked));
+        assert_eq!(Tier::from_id(3), Some(Tier::Iron1));
+        assert_eq!(Tier::from_id(27), Some(Tier::Radiant));
+        assert_eq!(Tier::try_from(28), Err(UnknownTier(28)));
+        assert!(Tier::Ascendant1 > Tier::Diamond3);
+    }
+
+    #[test]
+    fn tiers_display_patched_names() {
+        assert_eq!(Tier::Unranked.to_string(), "Unrated");
+        assert_eq!(Tier::Platinum2.to_string(), "Platinum 2");
+        assert_eq!(Tier::Immortal3.to_string(), "Immortal 3");
+        assert_eq!(Tier::Radiant.to_string(), "Radiant");
+    }
+
+    #[test]
+    fn tiers_are_numbers_on_the_wire() {
+        assert_eq!(serde_json::to_string(&Tier::Diamond1).unwrap(), "18");
+        assert_eq!(serde_json::from_str::<Tier>("16").unwrap(), Tier::Platinum2);
+        assert!(serde_json::from_str::<Tier>("99").is_err());
+    }
+}
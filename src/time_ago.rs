@@ -0,0 +1,96 @@
+//! Relative times like `12 minutes ago`, computed from raw timestamps.
+//!
+//! Some payloads carry a preformatted relative time next to the raw timestamp. That text is
+//! phrased by the API and goes stale as soon as it is cached, so models expose the timestamp and
+//! [`TimeAgo`] formats it at display time instead.
+
+use crate::locale::{Catalog, Localize};
+use chrono::{DateTime, TimeDelta, Utc};
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct TimeAgo {
+    elapsed: TimeDelta,
+}
+
+impl TimeAgo {
+    pub fn since(then: DateTime<Utc>) -> Self {
+        TimeAgo::between(then, Utc::now())
+    }
+
+    /// Times after `now` count as just now.
+    pub fn between(then: DateTime<Utc>, now: DateTime<Utc>) -> Self {
+        TimeAgo {
+            elapsed: (now - then).max(TimeDelta::zero()),
+        }
+    }
+
+    pub fn elapsed(&self) -> TimeDelta {
+        self.elapsed
+    }
+
+    // The largest whole unit, e.g. `(2, "hour")` under three hours
+    fn unit(&self) -> Option<(i64, &'static str)> {
+        const UNITS: [(i64, &str); 5] = [
+            (365 * 24 * 60, "year"),
+            (30 * 24 * 60, "month"),
+            (24 * 60, "day"),
+            (60, "hour"),
+            (1, "minute"),
+        ];
+        let minutes = self.elapsed.num_minutes();
+        UNITS
+            .into_iter()
+            .find(|(length, _)| minutes >= *length)
+            .map(|(length, unit)| (minutes / length, unit))
+    }
+}
+
+impl fmt::Display for TimeAgo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.unit() {
+            Some((1, unit)) => write!(f, "1 {unit} ago"),
+            Some((count, unit)) => write!(f, "{count} {unit}s ago"),
+            None => f.write_str("just now"),
+        }
+    }
+}
+
+impl Localize for TimeAgo {
+    fn localize(&self, catalog: &Catalog) -> String {
+        match self.unit() {
+            Some((1, unit)) => catalog.format(&format!("time-{unit}-ago"), &[("count", &1)]),
+            Some((count, unit)) => {
+                catalog.format(&format!("time-{unit}s-ago"), &[("count", &count)])
+            }
+            None => catalog.format("time-just-now", &[]),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn formats_the_largest_unit() {
+        let now = DateTime::from_timestamp(1_676_749_780, 0).unwrap();
+        let ago = |seconds: i64| TimeAgo::between(now - TimeDelta::seconds(seconds), now);
+        assert_eq!(ago(-30).to_string(), "just now");
+        assert_eq!(ago(59).to_string(), "just now");
+        assert_eq!(ago(60).to_string(), "1 minute ago");
+        assert_eq!(ago(12 * 60 + 5).to_string(), "12 minutes ago");
+        assert_eq!(ago(3 * 3600).to_string(), "3 hours ago");
+        assert_eq!(ago(86_400).to_string(), "1 day ago");
+        assert_eq!(ago(400 * 86_400).to_string(), "1 year ago");
+
+        let english = Catalog::english();
+        for seconds in [0, 60, 7200, 86_400 * 45] {
+            assert_eq!(ago(seconds).localize(&english), ago(seconds).to_string());
+        }
+        assert_eq!(
+            ago(7200).localize(&Catalog::for_locale("de")),
+            "vor 2 Stunden"
+        );
+    }
+}
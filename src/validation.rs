@@ -0,0 +1,254 @@
+//! Checks requests before they are sent.
+//!
+//! The API answers malformed requests with a 400, which still costs a request from the rate
+//! limit. [`ValorantApiType::validate`] catches the common mistakes locally, and the client
+//! runs it before every request.
+
+use crate::ids::{IdParseError, MatchId, Puuid, RiotId};
+use crate::mmr_data::{EpisodeAndAct, SeasonFilter};
+use crate::{ApiError, ApiResponse, ValorantAPIData, ValorantApiType};
+use std::fmt;
+
+/// The largest page the stored matches endpoint is documented to serve. Servers may cap pages
+/// lower, which [`ValorantClient::stored_matches_page`](crate::ValorantClient::stored_matches_page)
+/// discovers and adapts to.
+pub const MAX_PAGE_SIZE: u32 = 20;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ValidationError {
+    InvalidRiotId(IdParseError),
+    InvalidPuuid(IdParseError),
+    InvalidMatchId(IdParseError),
+    /// Acts are numbered 1 to 3 within an episode.
+    InvalidAct(EpisodeAndAct),
+    /// Pages start at 1.
+    InvalidPage(u32),
+    /// Pages hold at least one entry.
+    EmptyPageSize,
+    PageSizeTooLarge(u32),
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ValidationError::InvalidRiotId(error)
+            | ValidationError::InvalidPuuid(error)
+            | ValidationError::InvalidMatchId(error) => error.fmt(f),
+            ValidationError::InvalidAct(season) => {
+                write!(f, "Invalid act {}, acts go from 1 to 3", season.to_value())
+            }
+            ValidationError::InvalidPage(page) => {
+                write!(f, "Invalid page {page}, pages start at 1")
+            }
+            ValidationError::EmptyPageSize => {
+                write!(f, "Page size 0, pages hold at least one entry")
+            }
+            ValidationError::PageSizeTooLarge(size) => {
+                write!(
+                    f,
+                    "Page size {size} is larger than the maximum of {MAX_PAGE_SIZE}"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for ValidationError {}
+
+impl ValidationError {
+    // Shaped like the API's own answer to a bad request
+    pub(crate) fn to_api_error(&self) -> ApiError {
+        ApiError {
+            message: self.to_string(),
+            code: 0,
+            details: "The request was rejected before it was sent".to_string(),
+            retry_after: None,
+        }
+    }
+}
+
+impl<T: ValorantAPIData> From<ValidationError> for ApiResponse<T> {
+    fn from(error: ValidationError) -> Self {
+        ApiResponse::Failure {
+            status: 400,
+            errors: vec![error.to_api_error()],
+        }
+    }
+}
+
+impl ValorantApiType<'_> {
+    /// Checks the Riot IDs, PUUIDs, match IDs, acts and pages of the request.
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        match self {
+            Self::MMRData {
+                name, tag, filter, ..
+            } => {
+                riot_id(name, tag)?;
+                match filter {
+                    Some(SeasonFilter::Act(season)) => act(season),
+                    _ => Ok(()),
+                }
+            }
+            Self::AccountData { name, tag }
+            | Self::MMRHistory { name, tag, .. }
+            | Self::MatchHistory { name, tag, .. }
+            | Self::NightMarket { name, tag, .. }
+            | Self::MMRDataV3 { name, tag, .. } => riot_id(name, tag),
+            Self::AccountDataByPuuid { puuid }
+            | Self::MMRDataByPuuid { puuid, .. }
+            | Self::MMRHistoryByPuuid { puuid, .. }
+            | Self::MatchHistoryByPuuid { puuid, .. } => puuid
+                .parse::<Puuid>()
+                .map(drop)
+                .map_err(ValidationError::InvalidPuuid),
+            Self::Match { id } => id
+                .parse::<MatchId>()
+                .map(drop)
+                .map_err(ValidationError::InvalidMatchId),
+            Self::Leaderboard {
+                season, page, size, ..
+            } => {
+                if let Some(season) = season {
+                    act(season)?;
+                }
+                match (page, size) {
+                    (Some(0), _) => Err(ValidationError::InvalidPage(0)),
+                    (_, Some(0)) => Err(ValidationError::EmptyPageSize),
+                    _ => Ok(()),
+                }
+            }
+            Self::StoredMatches {
+                name,
+                tag,
+                filter,
+                page,
+                size,
+                ..
+            } => {
+                riot_id(name, tag)?;
+                if let Some(SeasonFilter::Act(season)) = filter {
+                    act(season)?;
+                }
+                match (page, size) {
+                    (Some(0), _) => Err(ValidationError::InvalidPage(0)),
+                    (_, Some(0)) => Err(ValidationError::EmptyPageSize),
+                    (_, Some(size)) if *size > MAX_PAGE_SIZE => {
+                        Err(ValidationError::PageSizeTooLarge(*size))
+                    }
+                    _ => Ok(()),
+                }
+            }
+            Self::Content { .. }
+            | Self::StoreFeatured
+            | Self::Version { .. }
+            | Self::EsportsSchedule { .. }
+            | Self::Website { .. } => Ok(()),
+        }
+    }
+}
+
+fn riot_id(name: &str, tag: &str) -> Result<(), ValidationError> {
+    RiotId::new(name, tag)
+        .map(drop)
+        .map_err(ValidationError::InvalidRiotId)
+}
+
+fn act(season: &EpisodeAndAct) -> Result<(), ValidationError> {
+    match season.act() {
+        1..=3 => Ok(()),
+        _ => Err(ValidationError::InvalidAct(season.clone())),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    #[cfg(feature = "client")]
+    use crate::account_data::AccountData;
+    #[cfg(feature = "client")]
+    use crate::mock_server::MockServer;
+    use crate::AccountRegion;
+    #[cfg(feature = "client")]
+    use crate::{ValorantClient, ValorantError};
+
+    #[test]
+    fn catches_bad_inputs() {
+        let account = |tag| ValorantApiType::AccountData {
+            name: "NitroSniper",
+            tag,
+        };
+        assert!(account("NERD").validate().is_ok());
+        assert!(matches!(
+            account("N").validate(),
+            Err(ValidationError::InvalidRiotId(IdParseError::InvalidTag(_)))
+        ));
+
+        let stored = |page, size| ValorantApiType::StoredMatches {
+            region: AccountRegion::EU,
+            name: "NitroSniper",
+            tag: "NERD",
+            filter: None,
+            page,
+            size,
+        };
+        assert!(stored(Some(1), Some(MAX_PAGE_SIZE)).validate().is_ok());
+        assert_eq!(
+            stored(Some(0), None).validate(),
+            Err(ValidationError::InvalidPage(0))
+        );
+        assert_eq!(
+            stored(None, Some(100)).validate(),
+            Err(ValidationError::PageSizeTooLarge(100))
+        );
+        assert_eq!(
+            stored(None, Some(0)).validate(),
+            Err(ValidationError::EmptyPageSize)
+        );
+
+        let leaderboard = |season, page| ValorantApiType::Leaderboard {
+            region: AccountRegion::EU,
+            season,
+            page,
+            size: Some(200),
+        };
+        let sized = |size| ValorantApiType::Leaderboard {
+            region: AccountRegion::EU,
+            season: None,
+            page: Some(1),
+            size,
+        };
+        assert!(matches!(
+            leaderboard(Some(EpisodeAndAct::from_numbers(6, 4)), None).validate(),
+            Err(ValidationError::InvalidAct(_))
+        ));
+        assert_eq!(
+            leaderboard(None, Some(0)).validate(),
+            Err(ValidationError::InvalidPage(0))
+        );
+        assert!(leaderboard(None, Some(3)).validate().is_ok());
+        assert_eq!(
+            sized(Some(0)).validate(),
+            Err(ValidationError::EmptyPageSize)
+        );
+        assert!(sized(None).validate().is_ok());
+    }
+
+    #[cfg(feature = "client")]
+    #[tokio::test]
+    async fn invalid_requests_are_not_sent() {
+        let server = MockServer::routes(vec![]).await;
+        let url = server.url();
+        let client = ValorantClient::new().change_api_endpoint(&url);
+
+        let error = client
+            .request::<AccountData>(ValorantApiType::AccountData {
+                name: "NitroSniper",
+                tag: "toolong",
+            })
+            .await
+            .unwrap_err();
+        assert!(matches!(error, ValorantError::InvalidInput(_)));
+        assert!(server.requests().is_empty());
+    }
+}
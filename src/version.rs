@@ -0,0 +1,131 @@
+#[cfg(feature = "client")]
+use crate::assets::AssetClient;
+#[cfg(feature = "client")]
+use crate::error::ValorantError;
+use crate::ValorantAPIData;
+#[cfg(feature = "client")]
+use crate::{AccountRegion, ValorantApiType, ValorantClient};
+use serde::{Deserialize, Serialize};
+
+/// The game build currently live in a region.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct GameVersion {
+    region: String,
+    branch: String,
+    build_ver: String,
+    version: u64,
+    version_for_api: String,
+}
+
+impl GameVersion {
+    pub fn branch(&self) -> &str {
+        &self.branch
+    }
+
+    pub fn build_ver(&self) -> &str {
+        &self.build_ver
+    }
+
+    /// The full client version, e.g. `release-06.04-shipping-10-832489`. It changes with every
+    /// patch, including hotfixes.
+    pub fn version_for_api(&self) -> &str {
+        &self.version_for_api
+    }
+}
+
+impl ValorantAPIData for GameVersion {}
+
+#[cfg(feature = "client")]
+impl ValorantClient<'_> {
+    /// Fetches the live game version and drops cached content when the build changed since the
+    /// last check. Cached player data is left alone, since patches don't change it.
+    pub async fn check_version(&self, region: AccountRegion) -> Result<GameVersion, ValorantError> {
+        let version = self
+            .request::<GameVersion>(ValorantApiType::Version { region })
+            .await?;
+        let mut known = self.game_version.write().unwrap();
+        if known.as_deref() != Some(version.version_for_api()) {
+            if known.is_some() {
+                *self.current_act.write().unwrap() = None;
+            }
+            *known = Some(version.version_for_api().to_string());
+        }
+        Ok(version)
+    }
+}
+
+#[cfg(feature = "client")]
+impl AssetClient<'_> {
+    /// Drops cached cards and titles if `version` is a different build than the one they were
+    /// fetched under. Returns whether anything was invalidated.
+    pub fn observe_version(&self, version: &GameVersion) -> bool {
+        let mut known = self.game_version.write().unwrap();
+        let changed = known
+            .as_deref()
+            .is_some_and(|known| known != version.version_for_api());
+        if changed {
+            self.cards.write().unwrap().clear();
+            self.titles.write().unwrap().clear();
+        }
+        *known = Some(version.version_for_api().to_string());
+        changed
+    }
+}
+
+#[cfg(all(test, feature = "client"))]
+mod test {
+    use super::*;
+    use crate::fixtures::{decode, CONTENT, VERSION};
+    use crate::mock_server::{MockResponse, MockServer};
+
+    const PATCHED: &str = r#"{
+        "status": 200,
+        "data": {
+            "region": "eu",
+            "branch": "release-06.04",
+            "build_date": "Feb 16 2023",
+            "build_ver": "06.04.01.834805",
+            "last_checked": "2023-02-16T09:12:40.392Z",
+            "version": 834805,
+            "version_for_api": "release-06.04-shipping-12-834805"
+        }
+    }"#;
+
+    #[tokio::test]
+    async fn new_build_drops_content_cache() {
+        let server = MockServer::routes(vec![
+            ("/v1/content", MockResponse::json(200, CONTENT)),
+            ("/v1/version/eu", MockResponse::json(200, VERSION)),
+            ("/v1/version/na", MockResponse::json(200, PATCHED)),
+        ])
+        .await;
+        let url = server.url();
+        let client = ValorantClient::new().change_api_endpoint(&url);
+        let content_requests = || {
+            server
+                .requests()
+                .iter()
+                .filter(|request| request.path.ends_with("/v1/content"))
+                .count()
+        };
+
+        client.check_version(AccountRegion::EU).await.unwrap();
+        client.current_act().await.unwrap();
+        client.check_version(AccountRegion::EU).await.unwrap();
+        client.current_act().await.unwrap();
+        assert_eq!(content_requests(), 1);
+
+        client.check_version(AccountRegion::NA).await.unwrap();
+        client.current_act().await.unwrap();
+        assert_eq!(content_requests(), 2);
+    }
+
+    #[test]
+    fn asset_caches_follow_the_build() {
+        let version = decode::<GameVersion>;
+        let assets = AssetClient::new();
+        assert!(!assets.observe_version(&version(VERSION)));
+        assert!(!assets.observe_version(&version(VERSION)));
+        assert!(assets.observe_version(&version(PATCHED)));
+    }
+}
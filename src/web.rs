@@ -0,0 +1,143 @@
+//! Glue for serving data from the client through an [axum](https://docs.rs/axum) backend.
+//!
+//! [`RiotIdParam`] validates Riot IDs taken from the path, and [`ApiJson`] turns the client's
+//! results into responses with the status code a proxy should answer with.
+
+use crate::error::ValorantError;
+use crate::ids::{IdParseError, RiotId};
+use crate::retry::RetryHint;
+use crate::{ApiResponse, ValorantAPIData};
+use axum::http::{header, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use serde::{Deserialize, Serialize};
+
+/// A validated `/:name/:tag` path. Invalid IDs are rejected with a 400 by axum's `Path`
+/// extractor before the handler runs.
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(try_from = "RawRiotId")]
+pub struct RiotIdParam(pub RiotId);
+
+#[derive(Deserialize)]
+struct RawRiotId {
+    name: String,
+    tag: String,
+}
+
+impl TryFrom<RawRiotId> for RiotIdParam {
+    type Error = IdParseError;
+
+    fn try_from(raw: RawRiotId) -> Result<Self, Self::Error> {
+        RiotId::new(&raw.name, &raw.tag).map(RiotIdParam)
+    }
+}
+
+impl RiotIdParam {
+    pub fn name(&self) -> &str {
+        self.0.name()
+    }
+
+    pub fn tag(&self) -> &str {
+        self.0.tag()
+    }
+}
+
+/// A handler result that answers with the API's own response body. API failures keep their
+/// status code and transport problems become gateway errors.
+pub struct ApiJson<T: ValorantAPIData>(pub Result<T, ValorantError>);
+
+impl<T: ValorantAPIData> From<Result<T, ValorantError>> for ApiJson<T> {
+    fn from(result: Result<T, ValorantError>) -> Self {
+        ApiJson(result)
+    }
+}
+
+#[derive(Serialize)]
+struct ProxyError {
+    status: u16,
+    message: String,
+}
+
+impl<T: Serialize + ValorantAPIData> IntoResponse for ApiJson<T> {
+    fn into_response(self) -> Response {
+        let error = match self.0 {
+            Ok(data) => {
+                let response = ApiResponse::Success { status: 200, data };
+                return (StatusCode::OK, Json(response)).into_response();
+            }
+            Err(ValorantError::ApiError { status, errors }) => {
+                let code = u16::try_from(status)
+                    .ok()
+                    .and_then(|status| StatusCode::from_u16(status).ok())
+                    .unwrap_or(StatusCode::BAD_GATEWAY);
+                let response = ApiResponse::<T>::Failure { status, errors };
+                return (code, Json(response)).into_response();
+            }
+            Err(error) => error,
+        };
+        let status = error_status(&error);
+        let body = ProxyError {
+            status: status.as_u16(),
+            message: error.to_string(),
+        };
+        let mut response = (status, Json(body)).into_response();
+        if let Some(wait) = error.retry_after() {
+            response
+                .headers_mut()
+                .insert(header::RETRY_AFTER, wait.as_secs().into());
+        }
+        response
+    }
+}
+
+fn error_status(error: &ValorantError) -> StatusCode {
+    match error {
+        ValorantError::RateLimited { .. } => StatusCode::TOO_MANY_REQUESTS,
+        ValorantError::InvalidInput(_) => StatusCode::BAD_REQUEST,
+        ValorantError::DeadlineExceeded => StatusCode::GATEWAY_TIMEOUT,
+        ValorantError::Transport(error) if error.is_timeout() => StatusCode::GATEWAY_TIMEOUT,
+        ValorantError::Transport(error) if error.is_connect() || error.is_request() => {
+            StatusCode::BAD_GATEWAY
+        }
+        ValorantError::Decode(_) => StatusCode::BAD_GATEWAY,
+        _ => StatusCode::INTERNAL_SERVER_ERROR,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::account_data::AccountData;
+    use crate::fixtures::{ACCOUNT, NOT_FOUND};
+
+    #[test]
+    fn riot_id_param() {
+        let valid: RiotIdParam =
+            serde_json::from_str(r#"{"name": "NitroSniper", "tag": "NERD"}"#).unwrap();
+        assert_eq!(valid.name(), "NitroSniper");
+        assert!(
+            serde_json::from_str::<RiotIdParam>(r#"{"name": "NitroSniper", "tag": "N"}"#).is_err()
+        );
+    }
+
+    #[tokio::test]
+    async fn statuses() {
+        let response = |body: &str| {
+            let response = serde_json::from_str::<ApiResponse<AccountData>>(body).unwrap();
+            ApiJson(crate::error::into_data(response)).into_response()
+        };
+        assert_eq!(response(ACCOUNT).status(), StatusCode::OK);
+        assert_eq!(response(NOT_FOUND).status(), StatusCode::NOT_FOUND);
+
+        let unreachable = reqwest::get("http://127.0.0.1:1").await.unwrap_err();
+        let response = ApiJson::<AccountData>(Err(unreachable.into())).into_response();
+        assert_eq!(response.status(), StatusCode::BAD_GATEWAY);
+
+        let limited = ValorantError::RateLimited {
+            retry_after: Some(std::time::Duration::from_secs(30)),
+        };
+        let response = ApiJson::<AccountData>(Err(limited)).into_response();
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(response.headers()[header::RETRY_AFTER], "30");
+    }
+}
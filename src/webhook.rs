@@ -0,0 +1,223 @@
+//! Watcher events delivered over HTTP, for pollers running on another host.
+//!
+//! A poller serializes each [`WebhookEvent`] as JSON and posts it with the shared secret in the
+//! [`SECRET_HEADER`] header. The receiving side hands the request's headers and body to a
+//! [`WebhookReceiver`], which checks them and decodes the same types the watchers emit, e.g.
+//! the [`RankChange`]s of [`ValorantClient::watch_roster`](crate::ValorantClient::watch_roster).
+//! The receiver doesn't depend on a web framework, so it fits any server.
+
+use crate::roster::RankChange;
+use http::HeaderMap;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// The header a webhook's shared secret is sent in.
+pub const SECRET_HEADER: &str = "x-webhook-secret";
+
+// Watcher payloads are small, anything larger is not one
+const DEFAULT_MAX_BODY: usize = 64 * 1024;
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(tag = "event", rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum WebhookEvent {
+    /// What a roster watch reported after one snapshot.
+    RankChanges {
+        roster: String,
+        changes: Vec<RankChange>,
+    },
+}
+
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum WebhookError {
+    /// The secret was missing or wrong.
+    Unauthorized,
+    UnsupportedContentType,
+    TooLarge {
+        size: usize,
+        max: usize,
+    },
+    Malformed(serde_json::Error),
+}
+
+impl WebhookError {
+    /// The status code to answer the sender with.
+    pub fn status(&self) -> u16 {
+        match self {
+            WebhookError::Unauthorized => 401,
+            WebhookError::UnsupportedContentType => 415,
+            WebhookError::TooLarge { .. } => 413,
+            WebhookError::Malformed(_) => 400,
+        }
+    }
+}
+
+impl fmt::Display for WebhookError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WebhookError::Unauthorized => write!(f, "The webhook secret is missing or wrong"),
+            WebhookError::UnsupportedContentType => write!(f, "Webhook payloads must be JSON"),
+            WebhookError::TooLarge { size, max } => {
+                write!(
+                    f,
+                    "The payload is {size} bytes, more than the maximum of {max}"
+                )
+            }
+            WebhookError::Malformed(error) => write!(f, "Malformed webhook payload: {error}"),
+        }
+    }
+}
+
+impl std::error::Error for WebhookError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            WebhookError::Malformed(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<serde_json::Error> for WebhookError {
+    fn from(error: serde_json::Error) -> Self {
+        WebhookError::Malformed(error)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct WebhookReceiver {
+    secret: Option<String>,
+    max_body: usize,
+}
+
+impl WebhookReceiver {
+    /// A receiver accepting requests that carry `secret`.
+    pub fn new(secret: impl Into<String>) -> Self {
+        WebhookReceiver {
+            secret: Some(secret.into()),
+            max_body: DEFAULT_MAX_BODY,
+        }
+    }
+
+    /// A receiver accepting every request, for senders on a trusted network.
+    pub fn without_secret() -> Self {
+        WebhookReceiver {
+            secret: None,
+            max_body: DEFAULT_MAX_BODY,
+        }
+    }
+
+    /// 64 KiB by default.
+    pub fn max_body(mut self, bytes: usize) -> Self {
+        self.max_body = bytes;
+        self
+    }
+
+    /// Checks the secret, content type and size of a request, then decodes its event.
+    pub fn receive(&self, headers: &HeaderMap, body: &[u8]) -> Result<WebhookEvent, WebhookError> {
+        if let Some(secret) = &self.secret {
+            let sent = headers
+                .get(SECRET_HEADER)
+                .map(|value| value.as_bytes())
+                .unwrap_or_default();
+            if !constant_time_eq(sent, secret.as_bytes()) {
+                return Err(WebhookError::Unauthorized);
+            }
+        }
+        let json = headers
+            .get(http::header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .is_some_and(|value| value.trim_start().starts_with("application/json"));
+        if !json {
+            return Err(WebhookError::UnsupportedContentType);
+        }
+        if body.len() > self.max_body {
+            return Err(WebhookError::TooLarge {
+                size: body.len(),
+                max: self.max_body,
+            });
+        }
+        Ok(serde_json::from_slice(body)?)
+    }
+}
+
+// Compares without stopping at the first difference, so response times don't leak the secret
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |diff, (a, b)| diff | (a ^ b)) == 0
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::roster::{Rank, RosterMember};
+    use http::HeaderValue;
+
+    fn headers(secret: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(SECRET_HEADER, HeaderValue::from_str(secret).unwrap());
+        headers.insert(
+            http::header::CONTENT_TYPE,
+            HeaderValue::from_static("application/json; charset=utf-8"),
+        );
+        headers
+    }
+
+    fn rank(elo: u32) -> Rank {
+        Rank {
+            tier: 16,
+            tier_patched: "Platinum 2".to_string(),
+            rr: elo % 100,
+            elo,
+        }
+    }
+
+    #[test]
+    fn events_round_trip_through_the_receiver() {
+        let event = WebhookEvent::RankChanges {
+            roster: "Team NERD".to_string(),
+            changes: vec![RankChange {
+                member: RosterMember::RiotId("Anonymous#0000".parse().unwrap()),
+                before: rank(1347),
+                after: rank(1370),
+            }],
+        };
+        let body = serde_json::to_vec(&event).unwrap();
+        assert!(body.starts_with(br#"{"event":"rank_changes""#));
+
+        let receiver = WebhookReceiver::new("hunter2");
+        assert_eq!(receiver.receive(&headers("hunter2"), &body).unwrap(), event);
+    }
+
+    #[test]
+    fn bad_requests_are_rejected() {
+        let receiver = WebhookReceiver::new("hunter2").max_body(16);
+        let body = br#"{"event":"rank_changes","roster":"Team NERD","changes":[]}"#;
+
+        let error = receiver.receive(&headers("hunter3"), body).unwrap_err();
+        assert_eq!(error.status(), 401);
+        assert!(matches!(
+            receiver.receive(&HeaderMap::new(), body),
+            Err(WebhookError::Unauthorized)
+        ));
+        assert!(matches!(
+            receiver.receive(&headers("hunter2"), body),
+            Err(WebhookError::TooLarge { max: 16, .. })
+        ));
+
+        let mut plain = headers("hunter2");
+        plain.insert(
+            http::header::CONTENT_TYPE,
+            HeaderValue::from_static("text/plain"),
+        );
+        let receiver = WebhookReceiver::without_secret();
+        assert_eq!(receiver.receive(&plain, body).unwrap_err().status(), 415);
+        let unknown = br#"{"event":"something_else"}"#;
+        assert_eq!(
+            receiver
+                .receive(&headers(""), unknown)
+                .unwrap_err()
+                .status(),
+            400
+        );
+    }
+}